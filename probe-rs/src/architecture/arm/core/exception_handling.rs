@@ -110,6 +110,7 @@ mod test {
             dwarf_id: None,
             core_register: &XPSR,
             value: Some(RegisterValue::U32(0)),
+            origin: None,
         });
 
         let description = handler
@@ -130,12 +131,14 @@ mod test {
             dwarf_id: None,
             core_register: &XPSR,
             value: Some(RegisterValue::U32(0)),
+            origin: None,
         });
 
         registers.0.push(DebugRegister {
             dwarf_id: None,
             core_register: &RA,
             value: Some(RegisterValue::U32(0x1000_0000)),
+            origin: None,
         });
 
         let details = handler.exception_details(&mut memory, &registers).unwrap();
@@ -177,54 +180,63 @@ mod test {
             dwarf_id: None,
             core_register: &XPSR,
             value: Some(RegisterValue::U32(3)),
+            origin: None,
         });
 
         registers.0.push(DebugRegister {
             dwarf_id: None,
             core_register: &RA,
             value: Some(RegisterValue::U32(0xffff_fff9)),
+            origin: None,
         });
 
         registers.0.push(DebugRegister {
             dwarf_id: None,
             core_register: &SP,
             value: Some(RegisterValue::U32(inital_sp)),
+            origin: None,
         });
 
         registers.0.push(DebugRegister {
             dwarf_id: None,
             core_register: CORTEX_M_CORE_REGISTERS.core_register(0),
             value: None,
+            origin: None,
         });
 
         registers.0.push(DebugRegister {
             dwarf_id: None,
             core_register: CORTEX_M_CORE_REGISTERS.core_register(1),
             value: None,
+            origin: None,
         });
 
         registers.0.push(DebugRegister {
             dwarf_id: None,
             core_register: CORTEX_M_CORE_REGISTERS.core_register(2),
             value: None,
+            origin: None,
         });
 
         registers.0.push(DebugRegister {
             dwarf_id: None,
             core_register: CORTEX_M_CORE_REGISTERS.core_register(3),
             value: None,
+            origin: None,
         });
 
         registers.0.push(DebugRegister {
             dwarf_id: None,
             core_register: CORTEX_M_CORE_REGISTERS.core_register(12),
             value: None,
+            origin: None,
         });
 
         registers.0.push(DebugRegister {
             dwarf_id: None,
             core_register: &PC,
             value: None,
+            origin: None,
         });
 
         let details = handler
@@ -240,46 +252,55 @@ mod test {
                 dwarf_id: None,
                 core_register: CORTEX_M_CORE_REGISTERS.core_register(0),
                 value: Some(RegisterValue::U32(0x11_00)),
+                origin: None,
             },
             DebugRegister {
                 dwarf_id: None,
                 core_register: CORTEX_M_CORE_REGISTERS.core_register(1),
                 value: Some(RegisterValue::U32(0x11_01)),
+                origin: None,
             },
             DebugRegister {
                 dwarf_id: None,
                 core_register: CORTEX_M_CORE_REGISTERS.core_register(2),
                 value: Some(RegisterValue::U32(0x11_02)),
+                origin: None,
             },
             DebugRegister {
                 dwarf_id: None,
                 core_register: CORTEX_M_CORE_REGISTERS.core_register(3),
                 value: Some(RegisterValue::U32(0x11_03)),
+                origin: None,
             },
             DebugRegister {
                 dwarf_id: None,
                 core_register: CORTEX_M_CORE_REGISTERS.core_register(12),
                 value: Some(RegisterValue::U32(0x11_12)),
+                origin: None,
             },
             DebugRegister {
                 dwarf_id: None,
                 core_register: &SP,
                 value: Some(RegisterValue::U32(inital_sp + 0x20)), // Stack pointer has to be adjusted to account for the registers stored on the stack
+                origin: None,
             },
             DebugRegister {
                 dwarf_id: None,
                 core_register: &RA,
                 value: Some(RegisterValue::U32(stack_return_address)),
+                origin: None,
             },
             DebugRegister {
                 dwarf_id: None,
                 core_register: &PC,
                 value: Some(RegisterValue::U32(stack_program_counter)),
+                origin: None,
             },
             DebugRegister {
                 dwarf_id: None,
                 core_register: &XPSR,
                 value: Some(RegisterValue::U32(stack_xpsr)),
+                origin: None,
             },
         ]);
 
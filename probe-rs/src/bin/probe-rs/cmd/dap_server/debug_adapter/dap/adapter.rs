@@ -25,8 +25,10 @@ use parse_int::parse;
 use probe_rs::{
     architecture::{arm::ArmError, riscv::communication_interface::RiscvError},
     debug::{
-        stack_frame::StackFrameInfo, ColumnType, ObjectRef, SourceLocation, SteppingMode,
-        VariableName, VariableNodeType, VerifiedBreakpoint,
+        debug_info::{BreakpointRequest, BreakpointResolution},
+        format_address,
+        stack_frame::StackFrameInfo,
+        ColumnType, ObjectRef, SourceLocation, SteppingMode, VariableName, VariableNodeType,
     },
     Architecture::Riscv,
     CoreStatus, Error, HaltReason, MemoryInterface, RegisterValue,
@@ -314,10 +316,8 @@ impl<P: ProtocolAdapter> DebugAdapter<P> {
             variables_reference: 0_i64,
         };
 
-        if let Some(context) = &arguments.context {
-            if context == "clipboard" {
-                response_body.result = arguments.expression;
-            } else if context == "repl" {
+        if let Some(context) = arguments.context.as_deref().map(EvaluateContext::from) {
+            if context == EvaluateContext::Repl {
                 // While the target is running, we only allow a 'break' command.
                 // Override clippy, because the recommendation would change the logic.
                 #[allow(clippy::nonminimal_bool)]
@@ -406,7 +406,11 @@ impl<P: ProtocolAdapter> DebugAdapter<P> {
                     }
                 }
             } else {
-                // Handle other contexts: 'watch', 'hover', etc.
+                // Handle other contexts: 'watch', 'hover', 'clipboard', etc.
+                if context == EvaluateContext::Clipboard {
+                    // If the expression does not resolve to a register or variable, copy the expression as is.
+                    response_body.result = arguments.expression.clone();
+                }
                 // The Variables request sometimes returns the variable name, and other times the variable id, so this expression will be tested to determine if it is an id or not.
                 let expression = arguments.expression.clone();
 
@@ -439,53 +443,99 @@ impl<P: ProtocolAdapter> DebugAdapter<P> {
                         response_body.result = format!("{register_value}");
                     } else {
                         // If the expression wasn't pointing to a register, then check if is a local or static variable in our stack_frame
-                        let mut variable: Option<probe_rs::debug::Variable> = None;
-                        let mut variable_cache: Option<&mut probe_rs::debug::VariableCache> = None;
-                        // Search through available caches and stop as soon as the variable is found
-                        #[allow(clippy::manual_flatten)]
-                        for variable_cache_entry in
-                            [
-                                stack_frame.local_variables.as_mut(),
-                                stack_frame.static_variables.as_mut(),
-                                target_core.core_data.core_peripherals.as_mut().map(
-                                    |core_peripherals| &mut core_peripherals.svd_variable_cache,
-                                ),
-                            ]
-                        {
-                            if let Some(search_cache) = variable_cache_entry {
-                                if search_cache.len() == 1 {
-                                    // This is a special case where we have a single variable in the cache, and it is the root of a scope.
-                                    // These variables don't have cached children by default, so we need to resolve them before we proceed.
-                                    // We check for len() == 1, so unwrap() on first_mut() is safe.
-                                    target_core.core_data.debug_info.cache_deferred_variables(
-                                        search_cache,
-                                        &mut target_core.core,
-                                        &mut search_cache.root_variable(),
-                                        StackFrameInfo {
-                                            registers: &stack_frame.registers,
-                                            frame_base: stack_frame.frame_base,
-                                            canonical_frame_address: stack_frame
-                                                .canonical_frame_address,
-                                        },
-                                    )?;
-                                }
+                        // Expressions like `points[i].x` are resolved in the local variables of the frame.
+                        let lvalue = if expression.parse::<ObjectRef>().is_ok() {
+                            None
+                        } else {
+                            Some(target_core.core_data.debug_info.resolve_lvalue(
+                                &mut target_core.core,
+                                stack_frame,
+                                &expression,
+                            ))
+                        };
+                        let mut lvalue_error = None;
+                        let (mut variable, mut variable_cache) = match lvalue {
+                            Some(Ok(lvalue_variable)) => {
+                                (Some(lvalue_variable), stack_frame.local_variables.as_mut())
+                            }
+                            lvalue => {
+                                lvalue_error = lvalue.and_then(Result::err);
+                                let mut variable: Option<probe_rs::debug::Variable> = None;
+                                let mut variable_cache: Option<
+                                    &mut probe_rs::debug::VariableCache,
+                                > = None;
+                                // Search through available caches and stop as soon as the variable is found
+                                #[allow(clippy::manual_flatten)]
+                                for variable_cache_entry in [
+                                    stack_frame.local_variables.as_mut(),
+                                    stack_frame.static_variables.as_mut(),
+                                    target_core.core_data.core_peripherals.as_mut().map(
+                                        |core_peripherals| &mut core_peripherals.svd_variable_cache,
+                                    ),
+                                ] {
+                                    if let Some(search_cache) = variable_cache_entry {
+                                        if search_cache.len() == 1 {
+                                            // This is a special case where we have a single variable in the cache, and it is the root of a scope.
+                                            // These variables don't have cached children by default, so we need to resolve them before we proceed.
+                                            // We check for len() == 1, so unwrap() on first_mut() is safe.
+                                            target_core
+                                                .core_data
+                                                .debug_info
+                                                .cache_deferred_variables(
+                                                    search_cache,
+                                                    &mut target_core.core,
+                                                    &mut search_cache.root_variable(),
+                                                    StackFrameInfo {
+                                                        registers: &stack_frame.registers,
+                                                        frame_base: stack_frame.frame_base,
+                                                        canonical_frame_address: stack_frame
+                                                            .canonical_frame_address,
+                                                        thread_local_storage_base: None,
+                                                        registers_only: false,
+                                                        frame_torn_down: stack_frame
+                                                            .frame_torn_down,
+                                                    },
+                                                )?;
+                                        }
 
-                                if let Ok(expression_as_key) = expression.parse::<ObjectRef>() {
-                                    variable = search_cache.get_variable_by_key(expression_as_key);
-                                } else {
-                                    variable = search_cache.get_variable_by_name(
-                                        &VariableName::Named(expression.clone()),
-                                    );
-                                }
-                                if let Some(variable) = &mut variable {
-                                    if variable.variable_node_type == VariableNodeType::SvdRegister
-                                        || variable.variable_node_type == VariableNodeType::SvdField
-                                    {
-                                        variable.extract_value(&mut target_core.core, search_cache)
+                                        if let Ok(expression_as_key) =
+                                            expression.parse::<ObjectRef>()
+                                        {
+                                            variable =
+                                                search_cache.get_variable_by_key(expression_as_key);
+                                        } else {
+                                            variable = search_cache.get_variable_by_name(
+                                                &VariableName::Named(expression.clone()),
+                                            );
+                                        }
+                                        if variable.is_some() {
+                                            variable_cache = Some(search_cache);
+                                            break;
+                                        }
                                     }
-                                    variable_cache = Some(search_cache);
-                                    break;
                                 }
+                                (variable, variable_cache)
+                            }
+                        };
+                        if let (Some(found_variable), Some(search_cache)) =
+                            (&mut variable, &mut variable_cache)
+                        {
+                            if found_variable.is_volatile {
+                                // Show the current value, instead of the one that was cached when the core halted.
+                                search_cache.read_volatile_value(
+                                    found_variable.variable_key(),
+                                    &mut target_core.core,
+                                )?;
+                                if let Some(volatile_variable) =
+                                    search_cache.get_variable_by_key(found_variable.variable_key())
+                                {
+                                    *found_variable = volatile_variable;
+                                }
+                            }
+                            if found_variable.variable_node_type == VariableNodeType::SvdRegister
+                                || found_variable.variable_node_type == VariableNodeType::SvdField
+                            {
+                                found_variable.extract_value(&mut target_core.core, search_cache)
                             }
                         }
                         // Check if we found a variable.
@@ -496,14 +546,23 @@ impl<P: ProtocolAdapter> DebugAdapter<P> {
                                 indexed_child_variables_cnt,
                             ) = get_variable_reference(&variable, variable_cache);
                             response_body.indexed_variables = Some(indexed_child_variables_cnt);
-                            response_body.memory_reference =
-                                Some(format!("{}", variable.memory_location));
+                            response_body.memory_reference = Some(
+                                variable
+                                    .memory_location
+                                    .format_with_address_size(variable.address_size),
+                            );
                             response_body.named_variables = Some(named_child_variables_cnt);
-                            response_body.result = variable.get_value(variable_cache);
+                            response_body.result = variable.get_value_formatted(
+                                variable_cache,
+                                &context.format_options(arguments.format.as_ref()),
+                            );
                             response_body.type_ = Some(format!("{:?}", variable.type_name));
                             response_body.variables_reference = variables_reference.into();
-                        } else {
-                            // If we made it to here, no register or variable matched the expression.
+                        } else if let Some(error) = lvalue_error {
+                            // No register or variable matched the expression, so show why it could not be resolved.
+                            if context != EvaluateContext::Clipboard {
+                                response_body.result = error.to_string();
+                            }
                         }
                     }
                 }
@@ -651,6 +710,109 @@ impl<P: ProtocolAdapter> DebugAdapter<P> {
         }
     }
 
+    /// Assign a new value to an lvalue expression, e.g. `points[i].x`, in the scope of a stack frame.
+    pub(crate) fn set_expression(
+        &mut self,
+        target_core: &mut CoreHandle,
+        request: &Request,
+    ) -> Result<()> {
+        let arguments: SetExpressionArguments = get_arguments(self, request)?;
+
+        let Some(stack_frame) = (match arguments.frame_id.map(ObjectRef::try_from).transpose() {
+            Ok(Some(frame_id)) => target_core
+                .core_data
+                .stack_frames
+                .iter_mut()
+                .find(|stack_frame| stack_frame.id == frame_id),
+            Ok(None) => {
+                // Use the current frame_id
+                target_core.core_data.stack_frames.first_mut()
+            }
+            Err(e) => {
+                tracing::warn!("Invalid frame_id: {e}");
+                // Use the current frame_id
+                target_core.core_data.stack_frames.first_mut()
+            }
+        }) else {
+            return self.send_response::<SetExpressionResponseBody>(
+                request,
+                Err(&DebuggerError::Other(anyhow!(
+                    "Failed to update {:?}: There is no stack frame to evaluate it in.",
+                    arguments.expression
+                ))),
+            );
+        };
+
+        if stack_frame
+            .registers
+            .get_register_by_name(arguments.expression.as_str())
+            .is_some()
+        {
+            return self.send_response::<SetExpressionResponseBody>(
+                request,
+                Err(&DebuggerError::Other(anyhow!(
+                    "Set Register values is not yet supported."
+                ))),
+            );
+        }
+
+        let variable = match target_core.core_data.debug_info.resolve_lvalue(
+            &mut target_core.core,
+            stack_frame,
+            &arguments.expression,
+        ) {
+            Ok(variable) => variable,
+            Err(error) => {
+                return self.send_response::<SetExpressionResponseBody>(
+                    request,
+                    Err(&DebuggerError::Other(anyhow!(
+                        "Failed to update {:?}: {error}",
+                        arguments.expression
+                    ))),
+                );
+            }
+        };
+        // The variable was resolved in the local variables, so they are available.
+        let Some(variable_cache) = stack_frame.local_variables.as_mut() else {
+            return self.send_response::<SetExpressionResponseBody>(
+                request,
+                Err(&DebuggerError::Other(anyhow!(
+                    "Failed to update {:?}: The stack frame has no variables.",
+                    arguments.expression
+                ))),
+            );
+        };
+
+        match variable.update_value(
+            &mut target_core.core,
+            variable_cache,
+            arguments.value.clone(),
+        ) {
+            Ok(updated_value) => {
+                let (variables_reference, named_child_variables_cnt, indexed_child_variables_cnt) =
+                    get_variable_reference(&variable, variable_cache);
+                let response_body = SetExpressionResponseBody {
+                    indexed_variables: Some(indexed_child_variables_cnt),
+                    named_variables: Some(named_child_variables_cnt),
+                    presentation_hint: None,
+                    type_: Some(format!("{:?}", variable.type_name)),
+                    value: updated_value,
+                    variables_reference: Some(variables_reference.into()),
+                };
+                self.send_response(request, Ok(Some(response_body)))
+            }
+            Err(error) => self.send_response::<SetExpressionResponseBody>(
+                request,
+                Err(&DebuggerError::Other(anyhow!(
+                    "Failed to update variable: {}, with new value {:?} : {:?}",
+                    arguments.expression,
+                    arguments.value,
+                    error
+                ))),
+            ),
+        }
+    }
+
     pub(crate) fn restart(
         &mut self,
         target_core: &mut CoreHandle,
@@ -845,32 +1007,37 @@ impl<P: ProtocolAdapter> DebugAdapter<P> {
             let source_path = NativePathBuf::from(source_path).to_typed_path_buf();
 
             if let Some(requested_breakpoints) = args.breakpoints.as_ref() {
-                for bp in requested_breakpoints {
-                    // Some overrides to improve breakpoint accuracy when `DebugInfo::get_breakpoint_location()` has to select the best from multiple options
-                    let requested_breakpoint_line = if self.lines_start_at_1 {
-                        // If the debug client uses 1 based numbering, then we can use it as is.
-                        bp.line as u64
-                    } else {
-                        // If the debug client uses 0 based numbering, then we bump the number by 1
-                        bp.line as u64 + 1
-                    };
-                    let requested_breakpoint_column = if self.columns_start_at_1 {
-                        // If the debug client uses 1 based numbering, then we can use it as is.
-                        Some(bp.column.unwrap_or(1) as u64)
-                    } else {
-                        // If the debug client uses 0 based numbering, then we bump the number by 1
-                        Some(bp.column.unwrap_or(0) as u64 + 1)
-                    };
+                let requests = requested_breakpoints
+                    .iter()
+                    .map(|bp| BreakpointRequest {
+                        path: source_path.clone(),
+                        // Some overrides to improve breakpoint accuracy when `DebugInfo::resolve_breakpoints()` has to select the best from multiple options
+                        line: if self.lines_start_at_1 {
+                            // If the debug client uses 1 based numbering, then we can use it as is.
+                            bp.line as u64
+                        } else {
+                            // If the debug client uses 0 based numbering, then we bump the number by 1
+                            bp.line as u64 + 1
+                        },
+                        column: if self.columns_start_at_1 {
+                            // If the debug client uses 1 based numbering, then we can use it as is.
+                            Some(bp.column.unwrap_or(1) as u64)
+                        } else {
+                            // If the debug client uses 0 based numbering, then we bump the number by 1
+                            Some(bp.column.unwrap_or(0) as u64 + 1)
+                        },
+                    })
+                    .collect::<Vec<_>>();
 
-                    match target_core.verify_and_set_breakpoint(
-                        &source_path,
-                        requested_breakpoint_line,
-                        requested_breakpoint_column,
-                        &args.source,
-                    ) {
-                        Ok(VerifiedBreakpoint {
+                for (bp, resolution) in requested_breakpoints
+                    .iter()
+                    .zip(target_core.verify_and_set_breakpoints(&requests, &args.source))
+                {
+                    match resolution {
+                        Ok(BreakpointResolution {
                             address,
                             source_location,
+                            ..
                         }) => created_breakpoints.push(Breakpoint {
                             column: source_location.column.map(|col| match col {
                                 ColumnType::LeftEdge => 0_i64,
@@ -1074,6 +1241,7 @@ impl<P: ProtocolAdapter> DebugAdapter<P> {
         })
         .collect::<Vec<PartialStackFrameData>>();
 
+        let source_path_translation = &target_core.core_data.source_path_translation;
         let frame_list: Vec<StackFrame> = frame_set
             .iter()
             .map(|frame| {
@@ -1101,7 +1269,7 @@ impl<P: ProtocolAdapter> DebugAdapter<P> {
 
                 // Create the appropriate [`dap_types::Source`] for the response
                 let source = if let Some(source_location) = &frame.source_location {
-                    get_dap_source(source_location)
+                    get_dap_source(source_location, source_path_translation)
                 } else {
                     tracing::debug!("No source location present for frame!");
                     None
@@ -1367,7 +1535,10 @@ impl<P: ProtocolAdapter> DebugAdapter<P> {
                             memory_reference: variable
                                 .memory_location
                                 .memory_address()
-                                .map_or_else(|_| None, |address| Some(format!("{address:#010x}"))),
+                                .map_or_else(
+                                    |_| None,
+                                    |address| Some(format_address(address, variable.address_size)),
+                                ),
                             indexed_variables: Some(indexed_child_variables_cnt),
                             named_variables: Some(named_child_variables_cnt),
                             presentation_hint: None,
@@ -1407,6 +1578,9 @@ impl<P: ProtocolAdapter> DebugAdapter<P> {
                         registers: &stack_frame.registers,
                         frame_base: stack_frame.frame_base,
                         canonical_frame_address: stack_frame.canonical_frame_address,
+                        thread_local_storage_base: None,
+                        registers_only: false,
+                        frame_torn_down: stack_frame.frame_torn_down,
                     });
                     break;
                 }
@@ -1419,6 +1593,9 @@ impl<P: ProtocolAdapter> DebugAdapter<P> {
                         registers: &stack_frame.registers,
                         frame_base: stack_frame.frame_base,
                         canonical_frame_address: stack_frame.canonical_frame_address,
+                        thread_local_storage_base: None,
+                        registers_only: false,
+                        frame_torn_down: stack_frame.frame_torn_down,
                     });
                     break;
                 }
@@ -1472,6 +1649,12 @@ impl<P: ProtocolAdapter> DebugAdapter<P> {
                 }
             }
 
+            // The values of `volatile` variables may change without the core running, so always show the current value.
+            for child_variable in variable_cache.get_children(variable_ref)? {
+                variable_cache
+                    .read_volatile_value(child_variable.variable_key(), &mut target_core.core)?;
+            }
+
             let dap_variables: Vec<Variable> = variable_cache
                 .get_children(variable_ref)?
                 .iter()
@@ -1501,7 +1684,11 @@ impl<P: ProtocolAdapter> DebugAdapter<P> {
                         // Do NOT use evaluate_name. It is impossible to distinguish between duplicate variable
                         // TODO: Implement qualified names.
                         evaluate_name: None,
-                        memory_reference: Some(variable.memory_location.to_string()),
+                        memory_reference: Some(
+                            variable
+                                .memory_location
+                                .format_with_address_size(variable.address_size),
+                        ),
                         indexed_variables: Some(indexed_child_variables_cnt),
                         named_variables: Some(named_child_variables_cnt),
                         presentation_hint: None,
@@ -6,6 +6,7 @@ use crate::cmd::dap_server::DebuggerError;
 use crate::util::rtt;
 use num_traits::Num;
 use parse_int::parse;
+use probe_rs::debug::VariableFormatOptions;
 use schemafy::schemafy;
 use serde::{Deserialize, Serialize};
 use std::{convert::TryFrom, fmt::Display};
@@ -162,6 +163,57 @@ impl Display for DisassembledInstruction {
     }
 }
 
+/// The context in which an `evaluate` request is issued, which determines how much detail is included in the result.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EvaluateContext {
+    /// The Debug Console REPL, where the complete value is shown.
+    Repl,
+    /// A watch expression, where nested values are included, but very long values are truncated.
+    Watch,
+    /// A hover tooltip in the editor, where only the top level of a short value is shown.
+    Hover,
+    /// A 'Copy Value' action, where the complete value is copied to the clipboard.
+    Clipboard,
+    /// The variables view, where values are shown the same way as for watch expressions.
+    Variables,
+}
+
+impl From<&str> for EvaluateContext {
+    /// The DAP specification allows other values for `context`, which we treat the same as `watch`.
+    fn from(context: &str) -> Self {
+        match context {
+            "repl" => EvaluateContext::Repl,
+            "hover" => EvaluateContext::Hover,
+            "clipboard" => EvaluateContext::Clipboard,
+            "variables" => EvaluateContext::Variables,
+            _ => EvaluateContext::Watch,
+        }
+    }
+}
+
+impl EvaluateContext {
+    /// The [`VariableFormatOptions`] to use in this context.
+    /// If the client specified a [`ValueFormat`], it overrides the default radix for the context.
+    pub fn format_options(&self, value_format: Option<&ValueFormat>) -> VariableFormatOptions {
+        let mut format_options = match self {
+            EvaluateContext::Repl | EvaluateContext::Clipboard => VariableFormatOptions::default(),
+            EvaluateContext::Watch | EvaluateContext::Variables => VariableFormatOptions {
+                max_length: Some(256),
+                ..Default::default()
+            },
+            EvaluateContext::Hover => VariableFormatOptions {
+                max_depth: Some(1),
+                max_length: Some(80),
+                ..Default::default()
+            },
+        };
+        if let Some(hexadecimal) = value_format.and_then(|value_format| value_format.hex) {
+            format_options.hexadecimal = hexadecimal;
+        }
+        format_options
+    }
+}
+
 // SECTION: For various helper functions
 
 /// Parse the argument at the given index.
@@ -234,3 +286,117 @@ fn get_string_argument(
         }),
     }
 }
+
+#[cfg(test)]
+mod test {
+    #![allow(clippy::unwrap_used)]
+
+    use probe_rs::debug::{Variable, VariableCache, VariableName, VariableType, VariableValue};
+
+    use super::{EvaluateContext, ValueFormat};
+
+    /// Build a cache containing `buffer: Buffer { len: u32, data: [u32; 64] }`.
+    fn build_buffer_variable() -> (VariableCache, Variable) {
+        let mut cache = VariableCache::new_svd_cache();
+        let root_key = cache.root_variable().variable_key();
+
+        let mut add_variable = |parent_key, name: String, type_name, value: Option<String>| {
+            let mut variable = cache.create_variable(parent_key, None, None).unwrap();
+            variable.name = VariableName::Named(name);
+            variable.type_name = type_name;
+            if let Some(value) = value {
+                variable.set_value(VariableValue::Valid(value));
+            }
+            cache.update_variable(&variable).unwrap();
+            variable
+        };
+
+        let buffer = add_variable(
+            root_key,
+            "buffer".to_string(),
+            VariableType::Struct("Buffer".to_string()),
+            None,
+        );
+        add_variable(
+            buffer.variable_key(),
+            "len".to_string(),
+            VariableType::Base("u32".to_string()),
+            Some("64".to_string()),
+        );
+        let data = add_variable(
+            buffer.variable_key(),
+            "data".to_string(),
+            VariableType::Array {
+                item_type_name: "u32".to_string(),
+                count: 64,
+            },
+            None,
+        );
+        for index in 0..64 {
+            add_variable(
+                data.variable_key(),
+                format!("__{index}"),
+                VariableType::Base("u32".to_string()),
+                Some((index * 1000).to_string()),
+            );
+        }
+
+        (cache, buffer)
+    }
+
+    #[test]
+    fn evaluate_context_from_dap_context() {
+        assert_eq!(EvaluateContext::from("repl"), EvaluateContext::Repl);
+        assert_eq!(EvaluateContext::from("watch"), EvaluateContext::Watch);
+        assert_eq!(EvaluateContext::from("hover"), EvaluateContext::Hover);
+        assert_eq!(
+            EvaluateContext::from("clipboard"),
+            EvaluateContext::Clipboard
+        );
+        assert_eq!(
+            EvaluateContext::from("variables"),
+            EvaluateContext::Variables
+        );
+        assert_eq!(EvaluateContext::from("unknown"), EvaluateContext::Watch);
+    }
+
+    #[test]
+    fn evaluate_context_changes_formatted_value() {
+        let (cache, buffer) = build_buffer_variable();
+        let format_in_context = |context: EvaluateContext, value_format: Option<&ValueFormat>| {
+            buffer.get_value_formatted(&cache, &context.format_options(value_format))
+        };
+
+        let complete_value = buffer.get_value(&cache);
+        assert!(complete_value.chars().count() > 256);
+
+        // The clipboard and REPL get everything.
+        assert_eq!(
+            format_in_context(EvaluateContext::Clipboard, None),
+            complete_value
+        );
+        assert_eq!(
+            format_in_context(EvaluateContext::Repl, None),
+            complete_value
+        );
+
+        // Watch expressions include nested values, but are truncated.
+        let watch_value = format_in_context(EvaluateContext::Watch, None);
+        assert_eq!(watch_value, format!("{}...", &complete_value[..256]));
+
+        // Hover only shows the top level.
+        assert_eq!(
+            format_in_context(EvaluateContext::Hover, None),
+            "Buffer {\n\tlen: u32 = 64, \n\tdata: [u32; 64] = {...}}"
+        );
+
+        // The client can request a hexadecimal radix in any context.
+        assert_eq!(
+            format_in_context(
+                EvaluateContext::Hover,
+                Some(&ValueFormat { hex: Some(true) })
+            ),
+            "Buffer {\n\tlen: u32 = 0x40, \n\tdata: [u32; 64] = {...}}"
+        );
+    }
+}
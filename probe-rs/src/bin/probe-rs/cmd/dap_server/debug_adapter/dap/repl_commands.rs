@@ -289,7 +289,7 @@ pub(crate) static REPL_COMMANDS: &[ReplCommand<ReplHandler>] = &[
         help_text: "Print known information about variable.",
         sub_commands: None,
         args: Some(&[
-            ReplCommandArgs::Optional("/f (f=format[n|v])"),
+            ReplCommandArgs::Optional("/f (f=format[n|v|x])"),
             ReplCommandArgs::Required("<local variable name>"),
         ]),
         handler: |target_core, command_arguments, evaluate_arguments| {
@@ -309,6 +309,7 @@ pub(crate) static REPL_COMMANDS: &[ReplCommand<ReplHandler>] = &[
                             .check_supported_formats(&[
                                 GdbFormat::Native,
                                 GdbFormat::DapReference,
+                                GdbFormat::Hex,
                             ])
                             .map_err(|error| {
                                 DebuggerError::UserMessage(format!(
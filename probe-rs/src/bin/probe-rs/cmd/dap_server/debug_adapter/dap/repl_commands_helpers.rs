@@ -8,7 +8,7 @@ use crate::cmd::dap_server::{server::core_data::CoreHandle, DebuggerError};
 use super::{
     dap_types::{
         CompletionItem, CompletionItemType, CompletionsArguments, DisassembledInstruction,
-        EvaluateArguments, EvaluateResponseBody, Response,
+        EvaluateArguments, EvaluateContext, EvaluateResponseBody, Response, ValueFormat,
     },
     repl_commands::{ReplCommand, ReplHandler, REPL_COMMANDS},
     repl_types::*,
@@ -86,13 +86,20 @@ pub(crate) fn get_local_variable(
         presentation_hint: None,
     };
     response_body.result = "".to_string();
+    let format_options = EvaluateContext::Repl.format_options(Some(&ValueFormat {
+        hex: Some(gdb_nuf.format_specifier == GdbFormat::Hex),
+    }));
     for variable in variable_list {
         if gdb_nuf.format_specifier == GdbFormat::DapReference {
-            response_body.memory_reference = Some(format!("{}", variable.memory_location));
+            response_body.memory_reference = Some(
+                variable
+                    .memory_location
+                    .format_with_address_size(variable.address_size),
+            );
             response_body.result = format!(
                 "{} : {} ",
                 variable.name,
-                variable.get_value(variable_cache)
+                variable.get_value_formatted(variable_cache, &format_options)
             );
             response_body.type_ = Some(format!("{:?}", variable.type_name));
             response_body.variables_reference = variable.variable_key().into();
@@ -101,8 +108,10 @@ pub(crate) fn get_local_variable(
                 "\n{} [{} @ {}]: {} ",
                 variable.name,
                 variable.type_name,
-                variable.memory_location,
-                variable.get_value(variable_cache)
+                variable
+                    .memory_location
+                    .format_with_address_size(variable.address_size),
+                variable.get_value_formatted(variable_cache, &format_options)
             ));
         }
     }
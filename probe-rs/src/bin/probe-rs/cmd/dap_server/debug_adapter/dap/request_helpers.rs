@@ -10,7 +10,7 @@ use capstone::{
 };
 use num_traits::Zero;
 use probe_rs::{
-    debug::{ColumnType, ObjectRef, SourceLocation},
+    debug::{ColumnType, ObjectRef, SourceLocation, SourcePathTranslation},
     CoreType, InstructionSet, MemoryInterface,
 };
 use std::{fmt::Write, time::Duration};
@@ -152,8 +152,8 @@ pub(crate) fn disassemble_target_memory(
                             .debug_info
                             .get_source_location(instruction.address()) {
                             if let Some(previous_source_location) = stored_source_location.clone() {
-                                if current_source_location != previous_source_location {
-                                    location = get_dap_source(&current_source_location);
+                                if !current_source_location.same_statement_as(&previous_source_location) {
+                                    location = get_dap_source(&current_source_location, &target_core.core_data.source_path_translation);
                                     line = current_source_location.line.map(|line| line as i64);
                                     column = current_source_location.column.map(|col| match col {
                                         ColumnType::LeftEdge => 0_i64,
@@ -269,14 +269,18 @@ pub(crate) fn get_capstone(target_core: &mut CoreHandle) -> Result<Capstone, Deb
     Ok(cs)
 }
 
-/// A helper function to create a [`Source`] struct from a [`SourceLocation`]
-pub(crate) fn get_dap_source(source_location: &SourceLocation) -> Option<Source> {
+/// A helper function to create a [`Source`] struct from a [`SourceLocation`],
+/// with the path of the source file on the host, as it is translated by `translation`.
+pub(crate) fn get_dap_source(
+    source_location: &SourceLocation,
+    translation: &SourcePathTranslation,
+) -> Option<Source> {
     // Attempt to construct the path for the source code
     let directory = source_location.directory.as_ref()?;
 
     // Try to convert the path to the native Path of the current OS,
     // and then check if the source file exists
-    if let Ok(mut native_path) = std::path::PathBuf::try_from(directory.clone()) {
+    if let Some(mut native_path) = translation.to_host_path(directory) {
         if native_path.is_relative() {
             if let Ok(current_dir) = std::env::current_dir() {
                 native_path = current_dir.join(native_path);
@@ -415,7 +419,10 @@ pub(crate) fn set_instruction_breakpoint(
                     .get_source_location(memory_reference)
                 {
                     Some(source_location) => {
-                        breakpoint_response.source = get_dap_source(&source_location);
+                        breakpoint_response.source = get_dap_source(
+                            &source_location,
+                            &target_core.core_data.source_path_translation,
+                        );
                         breakpoint_response.line = source_location.line.map(|line| line as i64);
                         breakpoint_response.column = source_location.column.map(|col| match col {
                             ColumnType::LeftEdge => 0_i64,
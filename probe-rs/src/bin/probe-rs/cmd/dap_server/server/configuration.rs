@@ -2,7 +2,10 @@ use crate::util::common_options::ProbeOptions;
 use crate::util::rtt;
 use crate::{cmd::dap_server::DebuggerError, FormatOptions};
 use anyhow::{anyhow, Result};
-use probe_rs::probe::{DebugProbeSelector, WireProtocol};
+use probe_rs::{
+    debug::SourcePathTranslation,
+    probe::{DebugProbeSelector, WireProtocol},
+};
 use serde::{Deserialize, Serialize};
 use std::{env::current_dir, path::PathBuf};
 
@@ -215,10 +218,38 @@ pub struct CoreConfig {
     /// CMSIS-SVD file for the target. Relative to `cwd`, or fully qualified.
     pub(crate) svd_file: Option<PathBuf>,
 
+    /// Map the source file paths in the debug information of the `program_binary` to other directories on the host,
+    /// e.g. when the binary was built in another directory, or on another machine.
+    #[serde(default)]
+    pub(crate) source_path_mappings: Vec<SourcePathMapping>,
+
     #[serde(flatten)]
     pub(crate) rtt_config: rtt::RttConfig,
 }
 
+impl CoreConfig {
+    /// The translation of the source file paths in the debug information to the paths on the host,
+    /// with the configured `source_path_mappings`.
+    pub(crate) fn source_path_translation(&self) -> SourcePathTranslation {
+        self.source_path_mappings.iter().fold(
+            SourcePathTranslation::default(),
+            |translation, mapping| {
+                translation.with_mapping(mapping.target_path.as_str(), mapping.host_path.clone())
+            },
+        )
+    }
+}
+
+/// A mapping of the source file paths below a directory in the debug information to a directory on the host.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SourcePathMapping {
+    /// The directory of the source files, as it is recorded in the debug information, e.g. `C:\work\firmware`.
+    pub(crate) target_path: String,
+    /// The directory on the host that contains the same source files.
+    pub(crate) host_path: PathBuf,
+}
+
 fn default_console_log() -> Option<ConsoleLog> {
     Some(ConsoleLog::Console)
 }
@@ -18,7 +18,9 @@ use crate::util::rtt::{self, ChannelMode, DataFormat, RttActiveTarget};
 use anyhow::{anyhow, Result};
 use probe_rs::{
     debug::{
-        debug_info::DebugInfo, stack_frame::StackFrameInfo, ColumnType, ObjectRef,
+        debug_info::{BreakpointRequest, BreakpointResolution, DebugInfo},
+        stack_frame::StackFrameInfo,
+        ColumnType, DebugError, ObjectRef, SourceLocation, SourcePathTranslation,
         VerifiedBreakpoint,
     },
     rtt::{Rtt, ScanRegion},
@@ -41,6 +43,8 @@ pub struct CoreData {
     pub last_known_status: CoreStatus,
     pub target_name: String,
     pub debug_info: DebugInfo,
+    /// The translation of the source file paths in `debug_info` to the paths on the host.
+    pub source_path_translation: SourcePathTranslation,
     pub core_peripherals: Option<SvdCache>,
     pub stack_frames: Vec<probe_rs::debug::stack_frame::StackFrame>,
     pub breakpoints: Vec<session_data::ActiveBreakpoint>,
@@ -316,28 +320,65 @@ impl<'p> CoreHandle<'p> {
         requested_source: &Source,
     ) -> Result<VerifiedBreakpoint, DebuggerError> {
         let VerifiedBreakpoint {
-                 address,
-                 source_location,
-             } = self.core_data
+            address,
+            source_location,
+        } = self
+            .core_data
             .debug_info
             .get_breakpoint_location(
                 source_path,
                 requested_breakpoint_line,
                 requested_breakpoint_column,
+                &self.core_data.source_path_translation,
             )
-            .map_err(|debug_error|
-                DebuggerError::Other(anyhow!("Cannot set breakpoint here. Try reducing compile time-, and link time-, optimization in your build configuration, or choose a different source location: {debug_error}")))?;
+            .map_err(unresolved_breakpoint_error)?;
+        self.set_source_breakpoint(address, &source_location, requested_source)?;
+        Ok(VerifiedBreakpoint {
+            address,
+            source_location,
+        })
+    }
+
+    /// Set the breakpoints of a source file, like [`Self::verify_and_set_breakpoint`], but resolve them as one batch.
+    ///
+    /// The result contains one entry for each of the `requests`, in the same order,
+    /// so that the breakpoints that can be set are not affected by those that can not.
+    pub(crate) fn verify_and_set_breakpoints(
+        &mut self,
+        requests: &[BreakpointRequest],
+        requested_source: &Source,
+    ) -> Vec<Result<BreakpointResolution, DebuggerError>> {
+        self.core_data
+            .debug_info
+            .resolve_breakpoints(requests, &self.core_data.source_path_translation)
+            .into_iter()
+            .map(|resolution| {
+                let resolution = resolution.map_err(unresolved_breakpoint_error)?;
+                self.set_source_breakpoint(
+                    resolution.address,
+                    &resolution.source_location,
+                    requested_source,
+                )?;
+                Ok(resolution)
+            })
+            .collect()
+    }
+
+    /// Set a breakpoint at `address`, which was resolved for a breakpoint in the `requested_source`,
+    /// at the actual `source_location` of the address.
+    fn set_source_breakpoint(
+        &mut self,
+        address: u64,
+        source_location: &SourceLocation,
+        requested_source: &Source,
+    ) -> Result<(), DebuggerError> {
         self.set_breakpoint(
             address,
             BreakpointType::SourceBreakpoint {
                 source: requested_source.clone(),
                 location: SourceLocationScope::Specific(source_location.clone()),
             },
-        )?;
-        Ok(VerifiedBreakpoint {
-            address,
-            source_location,
-        })
+        )
     }
 
     /// In the case where a new binary is flashed as part of a restart, we need to recompute the breakpoint address,
@@ -413,6 +454,9 @@ impl<'p> CoreHandle<'p> {
                         registers: &frame.registers,
                         frame_base: frame.frame_base,
                         canonical_frame_address: frame.canonical_frame_address,
+                        thread_local_storage_base: None,
+                        registers_only: false,
+                        frame_torn_down: frame.frame_torn_down,
                     },
                 );
                 all_discrete_memory_ranges.append(&mut variable_cache.get_discrete_memory_ranges());
@@ -429,6 +473,11 @@ impl<'p> CoreHandle<'p> {
     }
 }
 
+/// The error for a source breakpoint that could not be resolved to an address.
+fn unresolved_breakpoint_error(debug_error: DebugError) -> DebuggerError {
+    DebuggerError::Other(anyhow!("Cannot set breakpoint here. Try reducing compile time-, and link time-, optimization in your build configuration, or choose a different source location: {debug_error}"))
+}
+
 /// Return a Vec of memory ranges that consolidate the adjacent memory ranges of the input ranges.
 /// Note: The concept of "adjacent" is calculated to include a gap of up to specicied number of bytes between ranges.
 /// This serves to consolidate memory ranges that are separated by a small gap, but are still close enough for the purpose of the caller.
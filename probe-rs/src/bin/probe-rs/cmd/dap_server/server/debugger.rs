@@ -231,6 +231,7 @@ impl Debugger {
                     "readMemory" => debug_adapter.read_memory(&mut target_core, &request),
                     "writeMemory" => debug_adapter.write_memory(&mut target_core, &request),
                     "setVariable" => debug_adapter.set_variable(&mut target_core, &request),
+                    "setExpression" => debug_adapter.set_expression(&mut target_core, &request),
                     "configurationDone" => {
                         debug_adapter.configuration_done(&mut target_core, &request)
                     }
@@ -824,6 +825,7 @@ impl Debugger {
             supports_delayed_stack_trace_loading: Some(true),
             supports_read_memory_request: Some(true),
             supports_write_memory_request: Some(true),
+            supports_set_expression: Some(true),
             supports_set_variable: Some(true),
             supports_clipboard_context: Some(true),
             supports_disassemble_request: Some(true),
@@ -977,6 +979,7 @@ mod test {
             supports_read_memory_request: Some(true),
             supports_write_memory_request: Some(true),
             supports_restart_request: Some(true),
+            supports_set_expression: Some(true),
             supports_set_variable: Some(true),
             supports_stepping_granularity: Some(true),
             support_terminate_debuggee: Some(true),
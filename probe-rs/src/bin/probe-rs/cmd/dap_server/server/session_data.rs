@@ -112,6 +112,7 @@ impl SessionData {
                     target_session.target().name
                 ),
                 debug_info: debug_info_from_binary(core_configuration)?,
+                source_path_translation: core_configuration.source_path_translation(),
                 core_peripherals: None,
                 stack_frames: vec![],
                 breakpoints: vec![],
@@ -137,6 +138,7 @@ impl SessionData {
             .find(|core_data| core_data.core_index == core_configuration.core_index)
         {
             core_data.debug_info = debug_info_from_binary(core_configuration)?;
+            core_data.source_path_translation = core_configuration.source_path_translation();
             Ok(())
         } else {
             Err(DebuggerError::UnableToOpenProbe(Some(
@@ -760,6 +760,9 @@ impl DebugCli {
                                         frame_base: current_frame.frame_base,
                                         canonical_frame_address: current_frame
                                             .canonical_frame_address,
+                                        thread_local_storage_base: None,
+                                        registers_only: false,
+                                        frame_torn_down: current_frame.frame_torn_down,
                                     },
                                 )
                             {
@@ -131,6 +131,7 @@ impl CoreDump {
                             None
                         }
                     },
+                    origin: None,
                 });
             } else {
                 tracing::trace!(
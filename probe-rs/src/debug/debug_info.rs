@@ -1,14 +1,25 @@
 use super::ObjectRef;
 use super::{
-    function_die::FunctionDie, get_object_reference, unit_info::UnitInfo, variable::*, DebugError,
-    DebugRegisters, SourceLocation, StackFrame, VariableCache,
+    extract_file, extract_name,
+    function_die::FunctionDie,
+    get_object_reference, is_declaration,
+    return_value::{return_value_location, ReturnValueAbi},
+    type_info::type_has_name,
+    unit_info::UnitInfo,
+    variable::*,
+    DebugError, DebugRegisters, LineRow, RegisterOrigin, SourceLocation, SourcePathTranslation,
+    SourceRange, StackFrame, VariableCache,
 };
 use crate::core::UnwindRule;
 use crate::debug::source_statement::SourceStatement;
 use crate::debug::stack_frame::StackFrameInfo;
 use crate::{
     core::{ExceptionInterface, RegisterRole, RegisterValue},
-    debug::{registers, source_statement::SourceStatements},
+    debug::{
+        debug_step::get_compile_unit_info,
+        registers,
+        source_statement::{get_program_info_at_pc, SourceStatements},
+    },
     MemoryInterface,
 };
 use anyhow::anyhow;
@@ -21,7 +32,15 @@ use probe_rs_target::InstructionSet;
 use typed_path::{TypedPath, TypedPathBuf};
 
 use std::{
-    borrow, cmp::Ordering, convert::TryInto, num::NonZeroU64, ops::ControlFlow, path::Path, rc::Rc,
+    borrow,
+    cell::OnceCell,
+    cmp::Ordering,
+    collections::HashMap,
+    convert::TryInto,
+    num::NonZeroU64,
+    ops::{ControlFlow, Range},
+    path::Path,
+    rc::Rc,
     str::from_utf8,
 };
 
@@ -31,6 +50,15 @@ pub(crate) type GimliAttribute = gimli::Attribute<GimliReader>;
 
 pub(crate) type DwarfReader = gimli::read::EndianRcSlice<gimli::LittleEndian>;
 
+/// The unit index and offset of the type definitions, by tag and name.
+type TypeDefinitions = HashMap<(gimli::DwTag, String), (usize, gimli::UnitOffset)>;
+
+/// The default for [`DebugInfoOptions::max_array_elements`].
+pub const DEFAULT_MAX_ARRAY_ELEMENTS: usize = 1000;
+
+/// The default for [`DebugInfoOptions::max_string_length`].
+pub const DEFAULT_MAX_STRING_LENGTH: usize = 256;
+
 /// Capture the required information when a breakpoint is set based on a requested source location.
 /// It is possible that the requested source location cannot be resolved to a valid instruction address,
 /// in which case the first 'valid' instruction address will be used, and the source location will be
@@ -43,6 +71,183 @@ pub struct VerifiedBreakpoint {
     pub source_location: SourceLocation,
 }
 
+/// The result of resolving a breakpoint request for a source location, which may not uniquely identify a single instruction address.
+/// For example, the requested line may only specify the line (and not the column),
+/// or the code on that line may have been inlined in several places.
+#[derive(Clone, Debug)]
+pub struct BreakpointResolution {
+    /// The preferred address in target memory, where the breakpoint should be set.
+    pub address: u64,
+    /// The resolved source location of `address`, which may differ from the requested source location.
+    pub source_location: SourceLocation,
+    /// Other valid addresses for the requested source location, e.g. for other instances of inlined code.
+    /// These do not include `address`.
+    pub alternatives: Vec<u64>,
+}
+
+/// A requested breakpoint location, as one entry of a batch passed to [`DebugInfo::resolve_breakpoints`].
+#[derive(Clone, Debug)]
+pub struct BreakpointRequest {
+    /// The source file of the requested breakpoint.
+    pub path: TypedPathBuf,
+    /// The requested line in `path`.
+    pub line: u64,
+    /// The requested column, if any.
+    pub column: Option<u64>,
+}
+
+/// The declaration of a variable or parameter, as found by [`DebugInfo::variables_of_type`].
+#[derive(Clone, Debug)]
+pub struct VariableDeclaration {
+    /// The name of the variable, if it has one.
+    pub name: Option<String>,
+    /// The offset of the compilation unit that contains the variable.
+    pub unit_header_offset: gimli::DebugInfoOffset,
+    /// The offset of the variable DIE in the compilation unit.
+    pub variable_unit_offset: gimli::UnitOffset,
+    /// Where the variable is declared, from its `DW_AT_decl_file` and `DW_AT_decl_line`.
+    pub source_location: Option<SourceLocation>,
+}
+
+/// The tokens of an lvalue expression, see [`DebugInfo::resolve_lvalue`].
+struct LvalueTokens<'a> {
+    /// The complete expression, for error messages.
+    expression: &'a str,
+    /// The part of the expression that has not been parsed yet.
+    remaining: &'a str,
+}
+
+impl<'a> LvalueTokens<'a> {
+    /// Consume the next token, if it is the character `token`.
+    fn consume(&mut self, token: char) -> bool {
+        match self.remaining.trim_start().strip_prefix(token) {
+            Some(remaining) => {
+                self.remaining = remaining;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Consume the next token, if it is an identifier.
+    fn identifier(&mut self) -> Option<&'a str> {
+        let remaining = self.remaining.trim_start();
+        if !remaining.starts_with(|c: char| c.is_alphabetic() || c == '_') {
+            return None;
+        }
+        let end = remaining
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(remaining.len());
+        self.remaining = &remaining[end..];
+        Some(&remaining[..end])
+    }
+
+    /// Consume the next token, if it is a decimal or hexadecimal integer.
+    fn integer(&mut self) -> Option<i64> {
+        let remaining = self.remaining.trim_start();
+        let end = remaining
+            .find(|c: char| !c.is_ascii_alphanumeric())
+            .unwrap_or(remaining.len());
+        let token = &remaining[..end];
+        let integer = match token.strip_prefix("0x") {
+            Some(hex) => i64::from_str_radix(hex, 16).ok()?,
+            None => token.parse().ok()?,
+        };
+        self.remaining = &remaining[end..];
+        Some(integer)
+    }
+
+    /// An error about the expression, at the current token.
+    fn error(&self, message: String) -> DebugError {
+        match self.remaining.trim() {
+            "" => DebugError::Other(anyhow!("{message} at the end of `{}`.", self.expression)),
+            remaining => DebugError::Other(anyhow!(
+                "{message} in `{}`, at `{remaining}`.",
+                self.expression
+            )),
+        }
+    }
+}
+
+/// Reports the progress of loading [`DebugInfo`], e.g. to display a progress bar.
+#[derive(Clone)]
+pub struct DebugInfoProgress {
+    handler: Rc<dyn Fn(usize, usize)>,
+}
+
+impl DebugInfoProgress {
+    /// Create a new `DebugInfoProgress` with a `handler` that is called with the number of
+    /// compilation units processed so far, and the total number of compilation units.
+    pub fn new(handler: impl Fn(usize, usize) + 'static) -> Self {
+        Self {
+            handler: Rc::new(handler),
+        }
+    }
+
+    fn emit(&self, units_processed: usize, total_units: usize) {
+        (self.handler)(units_processed, total_units);
+    }
+}
+
+impl std::fmt::Debug for DebugInfoProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DebugInfoProgress").finish_non_exhaustive()
+    }
+}
+
+/// Options to control how [`DebugInfo`] is loaded.
+///
+/// ```
+/// use probe_rs::debug::{DebugInfoOptions, DebugInfoProgress};
+///
+/// let mut options = DebugInfoOptions::new();
+///
+/// options.progress = Some(DebugInfoProgress::new(|units_processed, total_units| {
+///     println!("Loaded {units_processed} of {total_units} units");
+/// }));
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct DebugInfoOptions {
+    /// An optional progress reporter, which is called after each compilation unit is loaded.
+    pub progress: Option<DebugInfoProgress>,
+    /// Which compiler generated variables are included when resolving the variables of a function.
+    pub artificial_variables: ArtificialVariables,
+    /// The offset between the addresses in the ELF file, and the addresses where the program is loaded,
+    /// e.g. for position independent code that is relocated at run-time.
+    ///
+    /// This is added to the addresses of statically located variables, i.e. the results of `DW_OP_addr` and `DW_OP_addrx`.
+    pub load_bias: u64,
+    /// The maximum number of members of an array that are resolved when the array is expanded.
+    ///
+    /// The remaining members are replaced by a single child that shows how many were omitted,
+    /// so that a corrupted array length does not exhaust the memory of the host, or the probe bandwidth.
+    /// Arrays are expanded in a single pass, without an offset to page through their members,
+    /// so the omitted members can not be shown.
+    pub max_array_elements: usize,
+    /// The maximum number of characters that are read from target memory for the string that a C `char *` points to.
+    pub max_string_length: usize,
+}
+
+impl DebugInfoOptions {
+    /// DebugInfoOptions with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Default for DebugInfoOptions {
+    fn default() -> Self {
+        Self {
+            progress: None,
+            artificial_variables: ArtificialVariables::default(),
+            load_bias: 0,
+            max_array_elements: DEFAULT_MAX_ARRAY_ELEMENTS,
+            max_string_length: DEFAULT_MAX_STRING_LENGTH,
+        }
+    }
+}
+
 /// Debug information which is parsed from DWARF debugging information.
 pub struct DebugInfo {
     pub(crate) dwarf: gimli::Dwarf<DwarfReader>,
@@ -52,19 +257,57 @@ pub struct DebugInfo {
     pub(crate) debug_line_section: gimli::DebugLine<DwarfReader>,
 
     pub(crate) unit_infos: Vec<UnitInfo>,
+
+    pub(crate) artificial_variables: ArtificialVariables,
+
+    pub(crate) load_bias: u64,
+
+    pub(crate) max_array_elements: usize,
+
+    pub(crate) max_string_length: usize,
+
+    /// The byte order of the target.
+    pub(crate) endianness: gimli::RunTimeEndian,
+
+    /// How functions return their values.
+    pub(crate) return_value_abi: ReturnValueAbi,
+
+    /// The complete type definitions by tag and name, built on the first lookup of an incomplete type.
+    type_definitions: OnceCell<TypeDefinitions>,
 }
 
 impl DebugInfo {
     /// Read debug info directly from a ELF file.
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<DebugInfo, DebugError> {
+        DebugInfo::from_file_with_options(path, DebugInfoOptions::default())
+    }
+
+    /// Read debug info directly from a ELF file, using the given `options`.
+    pub fn from_file_with_options<P: AsRef<Path>>(
+        path: P,
+        options: DebugInfoOptions,
+    ) -> Result<DebugInfo, DebugError> {
         let data = std::fs::read(path)?;
 
-        DebugInfo::from_raw(&data)
+        DebugInfo::from_raw_with_options(&data, options)
     }
 
     /// Parse debug information directly from a buffer containing an ELF file.
     pub fn from_raw(data: &[u8]) -> Result<Self, DebugError> {
+        DebugInfo::from_raw_with_options(data, DebugInfoOptions::default())
+    }
+
+    /// Parse debug information directly from a buffer containing an ELF file, using the given `options`.
+    pub fn from_raw_with_options(
+        data: &[u8],
+        options: DebugInfoOptions,
+    ) -> Result<Self, DebugError> {
         let object = object::File::parse(data)?;
+        let endianness = if object.is_little_endian() {
+            gimli::RunTimeEndian::Little
+        } else {
+            gimli::RunTimeEndian::Big
+        };
 
         // Load a section and return as `Cow<[u8]>`.
         let load_section = |id: gimli::SectionId| -> Result<DwarfReader, gimli::Error> {
@@ -92,6 +335,20 @@ impl DebugInfo {
 
         let mut unit_infos = Vec::new();
 
+        // Counting the units requires an extra pass over the unit headers,
+        // so only do it when someone is interested in the progress.
+        let total_units = if options.progress.is_some() {
+            let mut headers = dwarf_cow.units();
+            let mut count = 0;
+            while let Ok(Some(_)) = headers.next() {
+                count += 1;
+            }
+            count
+        } else {
+            0
+        };
+        let mut units_processed = 0;
+
         let mut iter = dwarf_cow.units();
 
         while let Ok(Some(header)) = iter.next() {
@@ -100,6 +357,11 @@ impl DebugInfo {
                 frame_section.set_address_size(unit.encoding().address_size);
                 unit_infos.push(UnitInfo::new(unit));
             };
+
+            units_processed += 1;
+            if let Some(progress) = &options.progress {
+                progress.emit(units_processed, total_units);
+            }
         }
 
         Ok(DebugInfo {
@@ -109,6 +371,13 @@ impl DebugInfo {
             address_section,
             debug_line_section,
             unit_infos,
+            artificial_variables: options.artificial_variables,
+            load_bias: options.load_bias,
+            max_array_elements: options.max_array_elements,
+            max_string_length: options.max_string_length,
+            endianness,
+            return_value_abi: ReturnValueAbi::from_object(&object),
+            type_definitions: OnceCell::new(),
         })
     }
 
@@ -144,6 +413,31 @@ impl DebugInfo {
         Ok(None)
     }
 
+    /// Get the source file of the function that contains `address`, from its `DW_AT_decl_file`.
+    ///
+    /// Unlike the file of the [`SourceLocation`] of `address`, this is the file of the non-inlined function,
+    /// even if `address` is in code that was inlined into it from another file, e.g. to label the function.
+    pub fn primary_source_file(&self, address: u64) -> Option<TypedPathBuf> {
+        for unit_info in &self.unit_infos {
+            let functions = match unit_info.get_function_dies(self, address, false) {
+                Ok(functions) => functions,
+                Err(error) => {
+                    tracing::warn!(
+                        "Unable to find functions for address {:#010x}: {:?}",
+                        address,
+                        error
+                    );
+                    continue;
+                }
+            };
+
+            if let Some(function) = functions.first() {
+                return function.decl_file(self);
+            }
+        }
+        None
+    }
+
     /// Try get the [`SourceLocation`] for a given address.
     pub fn get_source_location(&self, address: u64) -> Option<SourceLocation> {
         for unit_info in &self.unit_infos {
@@ -217,8 +511,8 @@ impl DebugInfo {
                                             column: Some(previous_row.column().into()),
                                             file,
                                             directory,
-                                            low_pc: Some(target_seq.start as u32),
-                                            high_pc: Some(target_seq.end as u32),
+                                            low_pc: Some(target_seq.start),
+                                            high_pc: Some(target_seq.end),
                                         });
                                     }
                                 }
@@ -237,8 +531,8 @@ impl DebugInfo {
                                         column: Some(row.column().into()),
                                         file,
                                         directory,
-                                        low_pc: Some(target_seq.start as u32),
-                                        high_pc: Some(target_seq.end as u32),
+                                        low_pc: Some(target_seq.start),
+                                        high_pc: Some(target_seq.end),
                                     });
                                 }
                             }
@@ -251,109 +545,1065 @@ impl DebugInfo {
         None
     }
 
-    /// We do not actually resolve the children of `[VariableName::StaticScope]` automatically, and only create the necessary header in the `VariableCache`.
-    /// This allows us to resolve the `[VariableName::StaticScope]` on demand/lazily, when a user requests it from the debug client.
-    /// This saves a lot of overhead when a user only wants to see the `[VariableName::LocalScope]` or `[VariableName::Registers]` while stepping through code (the most common use cases)
-    pub(crate) fn create_static_scope_cache(
-        &self,
-        unit_info: &UnitInfo,
-    ) -> Result<VariableCache, gimli::Error> {
-        // Only process statics for this unit header.
-        let abbrevs = &unit_info.unit.abbreviations;
-        // Navigate the current unit from the header down.
-        let mut header_tree = unit_info.unit.header.entries_tree(abbrevs, None)?;
-        let unit_node = header_tree.root()?;
+    /// Find the address of the first statement after the source line that contains `address`,
+    /// e.g. to set a temporary breakpoint when stepping over the current line.
+    ///
+    /// The rows of a line can recur later in the sequence, e.g. the increment of a `for` loop follows the body of the loop.
+    /// The line table is followed in the order of the sequence from the first row of the current line,
+    /// so the returned address is the statement after its first run of rows, e.g. the body of the loop,
+    /// and always for a different line.
+    /// Returns `None` if there is no such statement in the sequence that contains `address`.
+    pub fn next_line_address(&self, address: u64) -> Option<u64> {
+        for unit_info in &self.unit_infos {
+            let Some(line_program) = unit_info.unit.line_program.clone() else {
+                continue;
+            };
+            let Ok((program, sequences)) = line_program.sequences() else {
+                continue;
+            };
+            let Some(sequence) = sequences
+                .iter()
+                .find(|sequence| sequence.start <= address && address < sequence.end)
+            else {
+                continue;
+            };
 
-        Ok(VariableCache::new_dwarf_cache(
-            unit_node.entry().offset(),
-            VariableName::StaticScopeRoot,
-            Some(unit_info),
-        ))
+            let mut sequence_rows = Vec::new();
+            let mut rows = program.resume_from(sequence);
+            while let Ok(Some((_, row))) = rows.next_row() {
+                if row.end_sequence() {
+                    break;
+                }
+                sequence_rows.push(*row);
+            }
+
+            let row_line = |row: &gimli::LineRow| (row.file_index(), row.line());
+            let current_line = sequence_rows
+                .iter()
+                .take_while(|row| row.address() <= address)
+                .last()
+                .map(row_line)?;
+            return sequence_rows
+                .iter()
+                .skip_while(|row| row_line(row) != current_line)
+                .find(|row| row.is_stmt() && row.line().is_some() && row_line(row) != current_line)
+                .map(|row| row.address());
+        }
+        None
     }
 
-    /// Creates the unpopulated cache for `function` variables
-    pub(crate) fn create_function_scope_cache(
-        &self,
-        die_cursor_state: &FunctionDie,
-        unit_info: &UnitInfo,
-    ) -> Result<VariableCache, DebugError> {
-        let abbrevs = &unit_info.unit.abbreviations;
-        let mut tree = unit_info
-            .unit
-            .header
-            .entries_tree(abbrevs, Some(die_cursor_state.function_die.offset()))?;
-        let function_node = tree.root()?;
+    /// Get the [`SourceLocation`] where the core halted, e.g. after hitting a breakpoint.
+    ///
+    /// The halted `program_counter` does not have to be the address that was requested for the breakpoint,
+    /// so this uses the line table row that is in effect at `program_counter`, rather than one that starts there.
+    /// When several rows start at the same address (e.g. the entry of nested inlined functions),
+    /// the last statement is used, which is the innermost inlined source line.
+    pub fn get_halt_location(&self, program_counter: u64) -> Option<SourceLocation> {
+        let program_unit = get_compile_unit_info(self, program_counter).ok()?;
+        let (program, sequence) =
+            get_program_info_at_pc(self, program_unit, program_counter).ok()?;
+
+        let mut halt_row: Option<gimli::LineRow> = None;
+        let mut halt_row_end = sequence.end;
+        let mut rows = program.resume_from(&sequence);
+        while let Ok(Some((_, row))) = rows.next_row() {
+            // The row that ends the sequence is always after `program_counter`.
+            if row.address() > program_counter {
+                halt_row_end = row.address();
+                break;
+            }
+            let replaces_halt_row = match halt_row {
+                Some(halt_row) if halt_row.address() == row.address() => {
+                    row.is_stmt() || !halt_row.is_stmt()
+                }
+                _ => true,
+            };
+            if replaces_halt_row {
+                halt_row = Some(*row);
+            }
+        }
 
-        let function_variable_cache = VariableCache::new_dwarf_cache(
-            function_node.entry().offset(),
-            VariableName::LocalScopeRoot,
-            Some(unit_info),
-        );
+        let halt_row = halt_row?;
+        let header = program.header();
+        let (file, directory) =
+            self.find_file_and_directory(&program_unit.unit, header, halt_row.file(header)?)?;
+        Some(SourceLocation {
+            line: halt_row.line().map(NonZeroU64::get),
+            column: Some(halt_row.column().into()),
+            file,
+            directory,
+            low_pc: Some(halt_row.address()),
+            high_pc: Some(halt_row_end),
+        })
+    }
 
-        Ok(function_variable_cache)
+    /// Get the [`SourceRange`] of the instructions in `address_range`.
+    ///
+    /// Returns `None` if the range is empty, or if there is no source location for its first or last instruction.
+    pub fn source_range(&self, address_range: Range<u64>) -> Option<SourceRange> {
+        if address_range.is_empty() {
+            return None;
+        }
+        Some(SourceRange {
+            start: self.get_source_location(address_range.start)?,
+            end: self.get_source_location(address_range.end - 1)?,
+            low_pc: address_range.start,
+            high_pc: address_range.end,
+        })
     }
 
-    /// This effects the on-demand expansion of lazy/deferred load of all the 'child' `Variable`s for a given 'parent'.
-    pub fn cache_deferred_variables(
-        &self,
-        cache: &mut VariableCache,
-        memory: &mut dyn MemoryInterface,
-        parent_variable: &mut Variable,
-        frame_info: StackFrameInfo<'_>,
-    ) -> Result<(), DebugError> {
-        if !parent_variable.is_valid() {
-            // Do nothing. The parent_variable.get_value() will already report back the debug_error value.
-            return Ok(());
+    /// Get the [`SourceRange`]s of the function that contains `address`, one for each of its address ranges.
+    ///
+    /// Functions that are inlined into this function are part of its ranges.
+    /// Returns an empty list if `address` is not inside a function.
+    pub fn function_source_ranges(&self, address: u64) -> Result<Vec<SourceRange>, DebugError> {
+        for unit_info in &self.unit_infos {
+            let Some(function_die) = unit_info
+                .get_function_dies(self, address, false)?
+                .into_iter()
+                .next()
+            else {
+                continue;
+            };
+            let ranges = self
+                .dwarf
+                .die_ranges(&unit_info.unit, &function_die.function_die)?;
+            return self.source_ranges(ranges);
         }
+        Ok(Vec::new())
+    }
 
-        // Only attempt this part if we have not yet resolved the referenced children.
-        if cache.has_children(parent_variable)? {
-            return Ok(());
+    /// Check if `address` and `other_address` are inside the same function,
+    /// e.g. to detect that stepping has left the current function.
+    ///
+    /// A function can have several address ranges, e.g. when the compiler moves rarely executed code
+    /// to a separate `.cold` part, and the addresses may be in different ranges of the function.
+    /// Functions that are inlined into a function are part of it.
+    /// Returns `false` if either address is not inside a function.
+    pub fn same_function(&self, address: u64, other_address: u64) -> bool {
+        match (
+            self.function_die_offset(address),
+            self.function_die_offset(other_address),
+        ) {
+            (Some(function_offset), Some(other_function_offset)) => {
+                function_offset == other_function_offset
+            }
+            _ => false,
         }
+    }
 
-        let Some(header_offset) = parent_variable.unit_header_offset else {
-            return Ok(());
+    /// The offset of the DIE of the function that contains `address`, in the `.debug_info` section.
+    fn function_die_offset(&self, address: u64) -> Option<gimli::UnitSectionOffset> {
+        for unit_info in &self.unit_infos {
+            match unit_info.get_function_dies(self, address, false) {
+                Ok(function_dies) => {
+                    if let Some(function_die) = function_dies.first() {
+                        return Some(
+                            function_die
+                                .function_die
+                                .offset()
+                                .to_unit_section_offset(&unit_info.unit),
+                        );
+                    }
+                }
+                Err(error) => {
+                    tracing::warn!(
+                        "Unable to find the function for address {:#010x}: {:?}",
+                        address,
+                        error
+                    );
+                }
+            }
+        }
+        None
+    }
+
+    /// Get the [`SourceRange`]s of the compilation unit that contains `address`, one for each of its address ranges.
+    ///
+    /// Returns an empty list if `address` is not inside a compilation unit.
+    pub fn unit_source_ranges(&self, address: u64) -> Result<Vec<SourceRange>, DebugError> {
+        for unit_info in &self.unit_infos {
+            let mut ranges = self.dwarf.unit_ranges(&unit_info.unit)?;
+            while let Some(range) = ranges.next()? {
+                if range.begin <= address && address < range.end {
+                    return self.source_ranges(self.dwarf.unit_ranges(&unit_info.unit)?);
+                }
+            }
+        }
+        Ok(Vec::new())
+    }
+
+    /// Get the rows of the line number program of the compilation unit that contains `address`,
+    /// in the order in which they are encoded, e.g. to inspect why an address is attributed to an unexpected source line.
+    ///
+    /// The file of each row is resolved to its name and directory, so that the tables of DWARF 4 and DWARF 5 units,
+    /// which number their files differently, can be compared.
+    /// Returns an empty list if `address` is not inside a compilation unit, or the unit has no line number program.
+    pub fn line_table_rows(&self, address: u64) -> Result<Vec<LineRow>, DebugError> {
+        for unit_info in &self.unit_infos {
+            let mut ranges = self.dwarf.unit_ranges(&unit_info.unit)?;
+            while let Some(range) = ranges.next()? {
+                if range.begin <= address && address < range.end {
+                    return self.unit_line_rows(&unit_info.unit);
+                }
+            }
+        }
+        Ok(Vec::new())
+    }
+
+    /// Decode all the rows of the line number program of `unit`.
+    fn unit_line_rows(&self, unit: &gimli::Unit<GimliReader>) -> Result<Vec<LineRow>, DebugError> {
+        let Some(line_program) = unit.line_program.clone() else {
+            return Ok(Vec::new());
         };
 
-        let unit_header = self.dwarf.debug_info.header_from_offset(header_offset)?;
-        let unit_info = UnitInfo::new(gimli::Unit::new(&self.dwarf, unit_header)?);
+        let mut line_rows = Vec::new();
+        let mut rows = line_program.rows();
+        while let Some((header, row)) = rows.next_row()? {
+            let (file, directory) = row
+                .file(header)
+                .and_then(|file_entry| self.find_file_and_directory(unit, header, file_entry))
+                .unwrap_or_default();
+            line_rows.push(LineRow {
+                address: row.address(),
+                file,
+                directory,
+                line: row.line().map(NonZeroU64::get),
+                column: row.column().into(),
+                is_stmt: row.is_stmt(),
+                end_sequence: row.end_sequence(),
+            });
+        }
+        Ok(line_rows)
+    }
 
-        match parent_variable.variable_node_type {
-            VariableNodeType::ReferenceOffset(reference_offset) => {
-                // Reference to a type, or an node.entry() to another type or a type modifier which will point to another type.
-                let mut type_tree = unit_info
-                    .unit
-                    .header
-                    .entries_tree(&unit_info.unit.abbreviations, Some(reference_offset))?;
-                let referenced_node = type_tree.root()?;
-                let mut referenced_variable = cache.create_variable(
-                    parent_variable.variable_key,
-                    Some(referenced_node.entry().offset()),
-                    Some(&unit_info),
-                )?;
+    /// Convert the gimli address ranges of a DIE or unit to [`SourceRange`]s,
+    /// skipping ranges without source locations.
+    fn source_ranges(
+        &self,
+        mut ranges: gimli::RangeIter<GimliReader>,
+    ) -> Result<Vec<SourceRange>, DebugError> {
+        let mut source_ranges = Vec::new();
+        while let Some(range) = ranges.next()? {
+            source_ranges.extend(self.source_range(range.begin..range.end));
+        }
+        Ok(source_ranges)
+    }
 
-                referenced_variable.name = match &parent_variable.name {
-                    VariableName::Named(name) if name.starts_with("Some ") => VariableName::Named(name.replacen('&', "*", 1)) ,
-                    VariableName::Named(name) => VariableName::Named(format!("*{name}")),
-                    other => VariableName::Named(format!("Error: Unable to generate name, parent variable does not have a name but is special variable {other:?}")),
-                };
+    /// Check if the instruction at `address` is in the epilogue of a function,
+    /// i.e. the line program marks the row that contains it with `epilogue_begin`.
+    ///
+    /// In the epilogue, the stack frame of the function is being torn down, so the frame base may no longer be valid.
+    pub fn is_in_epilogue(&self, address: u64) -> bool {
+        let Ok(program_unit) = get_compile_unit_info(self, address) else {
+            return false;
+        };
+        let Ok((program, sequence)) = get_program_info_at_pc(self, program_unit, address) else {
+            return false;
+        };
 
-                referenced_variable = unit_info.extract_type(
-                    self,
-                    referenced_node,
-                    parent_variable,
-                    referenced_variable,
-                    memory,
-                    cache,
-                    frame_info,
-                )?;
+        // Of several rows for the same address, the last one applies.
+        let mut epilogue_begin = false;
+        let mut rows = program.resume_from(&sequence);
+        while let Ok(Some((_, row))) = rows.next_row() {
+            if row.address() > address {
+                break;
+            }
+            epilogue_begin = row.epilogue_begin();
+        }
+        epilogue_begin
+    }
 
-                if referenced_variable.type_name == VariableType::Base("()".to_owned()) {
-                    // Only use this, if it is NOT a unit datatype.
-                    cache.remove_cache_entry(referenced_variable.variable_key)?;
-                }
+    /// Find all the variables and parameters of type `type_name`, e.g. to find all uses of a type.
+    ///
+    /// The type of a variable matches if it has the name `type_name`, or is a typedef, or a `const` or `volatile` qualified
+    /// version of such a type, e.g. `volatile struct Nested`, or a `uint32_t` for `unsigned int`.
+    /// Pointers to the type, and variables that contain it as a member, are not included.
+    pub fn variables_of_type(&self, type_name: &str) -> Vec<VariableDeclaration> {
+        let mut variables = Vec::new();
+        for unit_info in &self.unit_infos {
+            if let Err(error) = self.unit_variables_of_type(unit_info, type_name, &mut variables) {
+                tracing::warn!(
+                    "Unable to find the variables of type {}: {:?}",
+                    type_name,
+                    error
+                );
             }
-            VariableNodeType::TypeOffset(type_offset) => {
+        }
+        variables
+    }
+
+    /// Add the variables of `unit_info` with type `type_name` to `variables`.
+    fn unit_variables_of_type(
+        &self,
+        unit_info: &UnitInfo,
+        type_name: &str,
+        variables: &mut Vec<VariableDeclaration>,
+    ) -> Result<(), DebugError> {
+        let unit = &unit_info.unit;
+        let mut entries = unit.entries();
+        while let Some((_, entry)) = entries.next_dfs()? {
+            if !matches!(
+                entry.tag(),
+                gimli::DW_TAG_variable | gimli::DW_TAG_formal_parameter
+            ) {
+                continue;
+            }
+            let Some(gimli::AttributeValue::UnitRef(type_offset)) =
+                entry.attr_value(gimli::DW_AT_type)?
+            else {
+                continue;
+            };
+            if !type_has_name(self, unit_info, type_offset, type_name)? {
+                continue;
+            }
+
+            let file = entry
+                .attr_value(gimli::DW_AT_decl_file)?
+                .and_then(|file| extract_file(self, unit, file));
+            let source_location = file.map(|(directory, file)| SourceLocation {
+                line: entry
+                    .attr(gimli::DW_AT_decl_line)
+                    .ok()
+                    .flatten()
+                    .and_then(|line| line.udata_value()),
+                file: Some(file),
+                directory: Some(directory),
+                ..Default::default()
+            });
+            variables.push(VariableDeclaration {
+                name: entry
+                    .attr_value(gimli::DW_AT_name)?
+                    .map(|name| extract_name(self, name)),
+                unit_header_offset: unit_info.debug_info_offset()?,
+                variable_unit_offset: entry.offset(),
+                source_location,
+            });
+        }
+        Ok(())
+    }
+
+    /// Get the call sites of the inlined functions that contain `address`.
+    ///
+    /// The first entry is the call site in the non-inlined function, and each following entry is
+    /// the call site inside the previous inlined function, up to the innermost inlined function.
+    /// This does not require unwinding, so it is cheaper than [`DebugInfo::unwind`] when only the
+    /// inlined calls are of interest.
+    /// Returns an empty list if `address` is not inside an inlined function.
+    pub fn inline_chain_at(&self, address: u64) -> Vec<SourceLocation> {
+        for unit_info in &self.unit_infos {
+            let functions = match unit_info.get_function_dies(self, address, true) {
+                Ok(functions) => functions,
+                Err(error) => {
+                    tracing::warn!(
+                        "Unable to find functions for address {:#010x}: {:?}",
+                        address,
+                        error
+                    );
+                    continue;
+                }
+            };
+
+            if !functions.is_empty() {
+                return functions
+                    .iter()
+                    .filter_map(|function| function.inline_call_location(self))
+                    .collect();
+            }
+        }
+        Vec::new()
+    }
+
+    /// Count how many of the sampled program counters in `samples` belong to each source line,
+    /// e.g. to profile a running target by repeatedly sampling its program counter.
+    ///
+    /// Each address is attributed to the line of the innermost inlined function that contains it.
+    /// The locations are normalized to the file and line, so that samples from different
+    /// columns and instructions of the same line are counted together.
+    /// Samples without a source location, e.g. in code without debug information, are not counted.
+    pub fn attribute_samples(&self, samples: &[u64]) -> HashMap<SourceLocation, u32> {
+        let mut locations = HashMap::<u64, Option<SourceLocation>>::new();
+        let mut counts = HashMap::new();
+
+        for &address in samples {
+            // The same addresses are typically sampled many times, so only look them up once.
+            let location = locations.entry(address).or_insert_with(|| {
+                self.get_source_location(address)
+                    .map(|location| SourceLocation {
+                        line: location.line,
+                        file: location.file,
+                        directory: location.directory.map(|directory| directory.normalize()),
+                        ..Default::default()
+                    })
+            });
+
+            if let Some(location) = location {
+                *counts.entry(location.clone()).or_insert(0) += 1;
+            }
+        }
+
+        counts
+    }
+
+    /// Decode the value returned by the function that contains `function_address`,
+    /// using the `registers` immediately after it returned to its caller, e.g. after stepping out of it.
+    ///
+    /// Values that fit in the return registers (e.g. `r0:r1` on ARM, `a0:a1` on RISC-V) are read from `registers`.
+    /// Floating point values are read from the floating point registers, if the ABI of the program uses them,
+    /// e.g. `s0` with the hard-float ABI on ARM.
+    /// Larger values are returned in memory, through a hidden pointer argument from the caller.
+    /// Most architectures do not preserve that pointer, so it has to be provided as `return_buffer_address`,
+    /// e.g. by reading the first argument register on entry to the function.
+    ///
+    /// Returns `None` if the function does not return a value.
+    /// Otherwise, the return value is the root variable of the returned cache.
+    pub fn function_return_value(
+        &self,
+        function_address: u64,
+        registers: &DebugRegisters,
+        return_buffer_address: Option<u64>,
+        memory: &mut dyn MemoryInterface,
+    ) -> Result<Option<VariableCache>, DebugError> {
+        let Some((unit_info, function_die)) = self.function_at(function_address) else {
+            return Err(DebugError::Other(anyhow!(
+                "No function found at address {function_address:#010x}"
+            )));
+        };
+
+        let Some(gimli::AttributeValue::UnitRef(type_offset)) = function_die
+            .attribute(gimli::DW_AT_type)
+            .map(|attribute| attribute.value())
+        else {
+            // Functions without a return type do not return a value.
+            return Ok(None);
+        };
+
+        let mut cache = VariableCache::new_dwarf_cache(
+            type_offset,
+            VariableName::Named("<return value>".to_string()),
+            Some(unit_info),
+        );
+        let mut return_value = cache.root_variable();
+        return_value.variable_node_type = VariableNodeType::RecurseToBaseType;
+        return_value.memory_location = match return_value_location(
+            self.return_value_abi,
+            unit_info,
+            type_offset,
+            registers,
+            return_buffer_address,
+        )? {
+            VariableLocation::Error(message) => {
+                return_value.set_value(VariableValue::Error(message));
+                VariableLocation::Unavailable(UnavailableReason::NoLocation)
+            }
+            location => location,
+        };
+
+        let frame_info = StackFrameInfo {
+            registers,
+            frame_base: None,
+            canonical_frame_address: None,
+            thread_local_storage_base: None,
+            registers_only: false,
+            frame_torn_down: false,
+        };
+        let mut type_tree = unit_info
+            .unit
+            .header
+            .entries_tree(&unit_info.unit.abbreviations, Some(type_offset))?;
+        let parent_variable = Variable::new(None, None);
+        return_value = unit_info.extract_type(
+            self,
+            type_tree.root()?,
+            &parent_variable,
+            return_value,
+            memory,
+            &mut cache,
+            frame_info,
+        )?;
+        cache.update_variable(&return_value)?;
+
+        // Resolve the members of small aggregates now, while the values of the registers are known.
+        cache.recurse_deferred_variables(self, memory, None, 3, 0, frame_info);
+
+        Ok(Some(cache))
+    }
+
+    /// Check if `address` is the entry point of a function, where its arguments are still in the argument registers.
+    pub(crate) fn is_function_entry(&self, address: u64) -> bool {
+        self.function_at(address)
+            .is_some_and(|(_, function_die)| function_die.low_pc == address)
+    }
+
+    /// The unit at `unit_header_offset` in the `.debug_info` section.
+    pub(crate) fn unit_info_at_offset(
+        &self,
+        unit_header_offset: gimli::DebugInfoOffset,
+    ) -> Option<&UnitInfo> {
+        self.unit_infos
+            .iter()
+            .find(|unit_info| unit_info.debug_info_offset().ok() == Some(unit_header_offset))
+    }
+
+    /// The unit, and the outermost function that contains `address`.
+    fn function_at(&self, address: u64) -> Option<(&UnitInfo, FunctionDie<'_, '_, '_>)> {
+        self.unit_infos.iter().find_map(|unit_info| {
+            unit_info
+                .get_function_dies(self, address, false)
+                .ok()?
+                .into_iter()
+                .next()
+                .map(|function_die| (unit_info, function_die))
+        })
+    }
+
+    /// The arguments of the function of `frame`, in the order of their declaration,
+    /// e.g. to show the frame as `function(x = 3)`.
+    ///
+    /// For inlined functions, the arguments have the location of the inlined instance,
+    /// with the name and type of the parameter in the abstract origin of the function.
+    /// The top level local variables of the frame are resolved, if they were still deferred.
+    pub fn stack_frame_arguments(
+        &self,
+        memory: &mut dyn MemoryInterface,
+        frame: &mut StackFrame,
+    ) -> Result<Vec<Variable>, DebugError> {
+        let Some(local_variables) = &mut frame.local_variables else {
+            return Ok(Vec::new());
+        };
+        let mut root_variable = local_variables.root_variable().clone();
+        self.cache_deferred_variables(
+            local_variables,
+            memory,
+            &mut root_variable,
+            StackFrameInfo {
+                registers: &frame.registers,
+                frame_base: frame.frame_base,
+                canonical_frame_address: frame.canonical_frame_address,
+                thread_local_storage_base: None,
+                registers_only: false,
+                frame_torn_down: frame.frame_torn_down,
+            },
+        )?;
+
+        Ok(local_variables
+            .get_children(root_variable.variable_key)?
+            .into_iter()
+            .filter(|variable| variable.is_parameter)
+            .collect())
+    }
+
+    /// Resolve `member_name` as a member of the receiver of the method in `frame`, i.e. `this->member_name` in C++,
+    /// or `self.member_name` in Rust, e.g. to evaluate a bare identifier that is not a local variable.
+    ///
+    /// The receiver is the parameter that the function refers to with `DW_AT_object_pointer`,
+    /// or otherwise a parameter named `this` or `self`, e.g. for Rust, or inlined methods. Pointers to the receiver are dereferenced first.
+    pub fn receiver_member(
+        &self,
+        memory: &mut dyn MemoryInterface,
+        frame: &mut StackFrame,
+        member_name: &str,
+    ) -> Result<Variable, DebugError> {
+        let Some(local_variables) = &mut frame.local_variables else {
+            return Err(DebugError::Other(anyhow!(
+                "Unable to resolve `{member_name}`, because the stack frame has no variables."
+            )));
+        };
+        let frame_info = StackFrameInfo {
+            registers: &frame.registers,
+            frame_base: frame.frame_base,
+            canonical_frame_address: frame.canonical_frame_address,
+            thread_local_storage_base: None,
+            registers_only: false,
+            frame_torn_down: frame.frame_torn_down,
+        };
+
+        self.receiver_member_in_scope(
+            local_variables,
+            memory,
+            &frame.function_name,
+            member_name,
+            frame_info,
+        )
+    }
+
+    /// Resolve `member_name` as a member of the receiver, like [`DebugInfo::receiver_member`],
+    /// for the function `function_name` with the local variables in `cache`.
+    fn receiver_member_in_scope(
+        &self,
+        cache: &mut VariableCache,
+        memory: &mut dyn MemoryInterface,
+        function_name: &str,
+        member_name: &str,
+        frame_info: StackFrameInfo<'_>,
+    ) -> Result<Variable, DebugError> {
+        let mut root_variable = cache.root_variable();
+        self.cache_deferred_variables(cache, memory, &mut root_variable, frame_info)?;
+        let arguments = cache
+            .get_children(root_variable.variable_key)?
+            .into_iter()
+            .filter(|variable| variable.is_parameter)
+            .collect::<Vec<_>>();
+
+        let object_pointer = self.object_pointer(&root_variable);
+        let Some(mut container) = arguments
+            .iter()
+            .find(|argument| {
+                object_pointer.is_some() && argument.variable_unit_offset == object_pointer
+            })
+            .or_else(|| {
+                arguments.iter().find(|argument| {
+                    matches!(&argument.name, VariableName::Named(name) if name == "this" || name == "self")
+                })
+            })
+            .cloned()
+        else {
+            return Err(DebugError::Other(anyhow!(
+                "Unable to resolve `{member_name}`, because `{function_name}` is not a method with a `this` or `self` receiver."
+            )));
+        };
+
+        let member_name = VariableName::Named(member_name.to_string());
+        loop {
+            self.cache_deferred_variables(cache, memory, &mut container, frame_info)?;
+            if let Some(member) =
+                cache.get_variable_by_name_and_parent(&member_name, container.variable_key)
+            {
+                return Ok(member);
+            }
+
+            // Continue with the value that a pointer to the receiver refers to.
+            let VariableNodeType::ReferenceOffset(_) = container.variable_node_type else {
+                break;
+            };
+            match cache
+                .get_children(container.variable_key)?
+                .into_iter()
+                .next()
+            {
+                Some(referenced_variable) => container = referenced_variable,
+                None => break,
+            }
+        }
+
+        Err(DebugError::Other(anyhow!(
+            "The receiver of `{function_name}` has no member `{member_name}`."
+        )))
+    }
+
+    /// Resolve the variable that the lvalue `expression` refers to in `frame`, e.g. to evaluate a watch expression.
+    ///
+    /// The expression starts with the name of a variable in scope, or of a member of the receiver of a method
+    /// (see [`DebugInfo::receiver_member`]), and continues with any number of
+    /// struct members (`.member`), and array elements (`[index]`). The index of an array element is either an integer,
+    /// or itself an lvalue expression with an integer value, e.g. `values[i]` or `points[selection.index].x`.
+    /// Indexes are checked against the bounds of the array.
+    pub fn resolve_lvalue(
+        &self,
+        memory: &mut dyn MemoryInterface,
+        frame: &mut StackFrame,
+        expression: &str,
+    ) -> Result<Variable, DebugError> {
+        let Some(local_variables) = &mut frame.local_variables else {
+            return Err(DebugError::Other(anyhow!(
+                "Unable to resolve `{expression}`, because the stack frame has no variables."
+            )));
+        };
+        let frame_info = StackFrameInfo {
+            registers: &frame.registers,
+            frame_base: frame.frame_base,
+            canonical_frame_address: frame.canonical_frame_address,
+            thread_local_storage_base: None,
+            registers_only: false,
+            frame_torn_down: frame.frame_torn_down,
+        };
+
+        let mut root_variable = local_variables.root_variable();
+        self.cache_deferred_variables(local_variables, memory, &mut root_variable, frame_info)?;
+
+        let mut tokens = LvalueTokens {
+            expression,
+            remaining: expression,
+        };
+        let variable = self.resolve_lvalue_tokens(
+            local_variables,
+            memory,
+            &root_variable,
+            &frame.function_name,
+            &mut tokens,
+            frame_info,
+        )?;
+        if !tokens.remaining.trim().is_empty() {
+            return Err(tokens.error(format!("Unexpected `{}`", tokens.remaining.trim())));
+        }
+        Ok(variable)
+    }
+
+    /// Resolve the lvalue at the start of `tokens`, see [`DebugInfo::resolve_lvalue`].
+    fn resolve_lvalue_tokens(
+        &self,
+        cache: &mut VariableCache,
+        memory: &mut dyn MemoryInterface,
+        root_variable: &Variable,
+        function_name: &str,
+        tokens: &mut LvalueTokens<'_>,
+        frame_info: StackFrameInfo<'_>,
+    ) -> Result<Variable, DebugError> {
+        let name = tokens
+            .identifier()
+            .ok_or_else(|| tokens.error("Expected the name of a variable".to_string()))?;
+        let variable_name = VariableName::Named(name.to_string());
+        let mut variable = match cache
+            .get_variable_by_name_and_parent(&variable_name, root_variable.variable_key)
+        {
+            Some(variable) => variable,
+            // A name that is not a local variable can be a member of the receiver, e.g. `count` for `this->count`.
+            None => self
+                .receiver_member_in_scope(cache, memory, function_name, name, frame_info)
+                .map_err(|_| tokens.error(format!("`{name}` is not a variable in scope")))?,
+        };
+        let mut path = name.to_string();
+
+        loop {
+            if tokens.consume('.') {
+                let member_name = tokens
+                    .identifier()
+                    .ok_or_else(|| tokens.error("Expected the name of a member".to_string()))?;
+                self.cache_deferred_variables(cache, memory, &mut variable, frame_info)?;
+                variable = cache
+                    .get_variable_by_name_and_parent(
+                        &VariableName::Named(member_name.to_string()),
+                        variable.variable_key,
+                    )
+                    .ok_or_else(|| {
+                        tokens.error(format!("`{path}` has no member `{member_name}`"))
+                    })?;
+                path = format!("{path}.{member_name}");
+            } else if tokens.consume('[') {
+                let index = match tokens.integer() {
+                    Some(index) => index,
+                    None => {
+                        let index_variable = self.resolve_lvalue_tokens(
+                            cache,
+                            memory,
+                            root_variable,
+                            function_name,
+                            tokens,
+                            frame_info,
+                        )?;
+                        let index_value = index_variable.get_value(cache);
+                        index_value.parse::<i64>().map_err(|_| {
+                            tokens.error(format!(
+                                "The index of `{path}` is `{index_value}`, which is not an integer"
+                            ))
+                        })?
+                    }
+                };
+                if !tokens.consume(']') {
+                    return Err(tokens.error("Expected `]`".to_string()));
+                }
+
+                self.cache_deferred_variables(cache, memory, &mut variable, frame_info)?;
+                if !matches!(variable.type_name, VariableType::Array { .. }) {
+                    return Err(tokens.error(format!("`{path}` is not an array")));
+                }
+                let bounds = variable.subrange_bounds();
+                if !bounds.contains(&index) {
+                    return Err(tokens.error(format!(
+                        "The index {index} is out of bounds for `{path}`, which has {} elements",
+                        bounds.count()
+                    )));
+                }
+                variable = cache
+                    .get_variable_by_name_and_parent(
+                        &VariableName::Named(format!("__{index}")),
+                        variable.variable_key,
+                    )
+                    .ok_or_else(|| tokens.error(format!("`{path}` has no element {index}")))?;
+                path = format!("{path}[{index}]");
+            } else {
+                return Ok(variable);
+            }
+        }
+    }
+
+    /// Get the offset of the receiver parameter, from the `DW_AT_object_pointer` of the function
+    /// that is the `root_variable` of a function scope cache.
+    fn object_pointer(&self, root_variable: &Variable) -> Option<gimli::UnitOffset> {
+        let unit_header = self
+            .dwarf
+            .debug_info
+            .header_from_offset(root_variable.unit_header_offset?)
+            .ok()?;
+        let unit = gimli::Unit::new(&self.dwarf, unit_header).ok()?;
+        let function = unit.entry(root_variable.variable_unit_offset?).ok()?;
+
+        match function.attr_value(gimli::DW_AT_object_pointer).ok()? {
+            Some(gimli::AttributeValue::UnitRef(object_pointer)) => Some(object_pointer),
+            _ => None,
+        }
+    }
+
+    /// Find the variable that occupies `address` in target memory, e.g. when an address is selected in a memory view.
+    ///
+    /// The static variables of all units are searched, using the current `registers` of the core,
+    /// as well as the local variables of `frame`, if provided.
+    /// Members of structs, unions and arrays are included, and when several variables contain `address`,
+    /// the most specific one (with the smallest memory range) is returned.
+    pub fn variable_at_address(
+        &self,
+        memory: &mut dyn MemoryInterface,
+        registers: &DebugRegisters,
+        address: u64,
+        frame: Option<&StackFrame>,
+    ) -> Option<Variable> {
+        let mut candidates = Vec::new();
+        if let Some(frame) = frame {
+            if let Some(mut local_variables) = frame.local_variables.clone() {
+                candidates.extend(self.variable_at_address_in_scope(
+                    &mut local_variables,
+                    memory,
+                    address,
+                    StackFrameInfo {
+                        registers: &frame.registers,
+                        frame_base: frame.frame_base,
+                        canonical_frame_address: frame.canonical_frame_address,
+                        thread_local_storage_base: None,
+                        registers_only: false,
+                        frame_torn_down: frame.frame_torn_down,
+                    },
+                ));
+            }
+        }
+        for unit_info in &self.unit_infos {
+            let Ok(mut static_variables) = self.create_static_scope_cache(unit_info) else {
+                continue;
+            };
+            candidates.extend(self.variable_at_address_in_scope(
+                &mut static_variables,
+                memory,
+                address,
+                StackFrameInfo {
+                    registers,
+                    frame_base: None,
+                    canonical_frame_address: None,
+                    thread_local_storage_base: None,
+                    registers_only: false,
+                    frame_torn_down: false,
+                },
+            ));
+        }
+
+        candidates.into_iter().min_by_key(|variable| {
+            variable
+                .memory_range()
+                .map_or(u64::MAX, |range| range.end - range.start)
+        })
+    }
+
+    /// Find the variable that contains `address` in the scope of `cache`.
+    ///
+    /// Only the top-level variables of the scope are resolved first, and only the ones whose memory contains
+    /// `address` are expanded to find the most specific member.
+    fn variable_at_address_in_scope(
+        &self,
+        cache: &mut VariableCache,
+        memory: &mut dyn MemoryInterface,
+        address: u64,
+        frame_info: StackFrameInfo<'_>,
+    ) -> Option<Variable> {
+        // The maximum depth of nested members that is resolved to find the variable.
+        const MAX_DEPTH: usize = 10;
+
+        let mut scope_root = cache.root_variable();
+        self.cache_deferred_variables(cache, memory, &mut scope_root, frame_info)
+            .ok()?;
+        for mut variable in cache.get_children(scope_root.variable_key).ok()? {
+            if variable
+                .memory_range()
+                .is_some_and(|memory_range| memory_range.contains(&address))
+            {
+                cache.recurse_deferred_variables(
+                    self,
+                    memory,
+                    Some(&mut variable),
+                    MAX_DEPTH,
+                    0,
+                    frame_info,
+                );
+            }
+        }
+        cache.variable_at_address(address)
+    }
+
+    /// Find the complete definition of a type that is only declared (`DW_AT_declaration`) where it is used,
+    /// e.g. an opaque `struct` that is defined in another compilation unit.
+    ///
+    /// All units in `.debug_info` are searched, which includes DWARF 5 type units.
+    pub(crate) fn find_type_definition(
+        &self,
+        tag: gimli::DwTag,
+        type_name: &str,
+    ) -> Result<Option<(&UnitInfo, gimli::UnitOffset)>, DebugError> {
+        let type_definitions = match self.type_definitions.get() {
+            Some(type_definitions) => type_definitions,
+            None => {
+                let type_definitions = self.index_type_definitions()?;
+                self.type_definitions.get_or_init(|| type_definitions)
+            }
+        };
+        Ok(type_definitions
+            .get(&(tag, type_name.to_string()))
+            .map(|&(unit_index, offset)| (&self.unit_infos[unit_index], offset)))
+    }
+
+    /// Index the named definitions of the types that can be declared without a definition,
+    /// in all units, by their tag and name.
+    ///
+    /// Where a type is defined more than once, the first definition wins.
+    fn index_type_definitions(&self) -> Result<TypeDefinitions, DebugError> {
+        let mut type_definitions = HashMap::new();
+        for (unit_index, unit_info) in self.unit_infos.iter().enumerate() {
+            let mut entries = unit_info.unit.entries();
+            while let Some((_, entry)) = entries.next_dfs()? {
+                if !matches!(
+                    entry.tag(),
+                    gimli::DW_TAG_structure_type
+                        | gimli::DW_TAG_class_type
+                        | gimli::DW_TAG_union_type
+                        | gimli::DW_TAG_enumeration_type
+                ) || is_declaration(entry)
+                {
+                    continue;
+                }
+                let Some(name) = entry.attr_value(gimli::DW_AT_name)? else {
+                    continue;
+                };
+                type_definitions
+                    .entry((entry.tag(), extract_name(self, name)))
+                    .or_insert((unit_index, entry.offset()));
+            }
+        }
+        Ok(type_definitions)
+    }
+
+    /// We do not actually resolve the children of `[VariableName::StaticScope]` automatically, and only create the necessary header in the `VariableCache`.
+    /// This allows us to resolve the `[VariableName::StaticScope]` on demand/lazily, when a user requests it from the debug client.
+    /// This saves a lot of overhead when a user only wants to see the `[VariableName::LocalScope]` or `[VariableName::Registers]` while stepping through code (the most common use cases)
+    pub(crate) fn create_static_scope_cache(
+        &self,
+        unit_info: &UnitInfo,
+    ) -> Result<VariableCache, gimli::Error> {
+        // Only process statics for this unit header.
+        let abbrevs = &unit_info.unit.abbreviations;
+        // Navigate the current unit from the header down.
+        let mut header_tree = unit_info.unit.header.entries_tree(abbrevs, None)?;
+        let unit_node = header_tree.root()?;
+
+        Ok(VariableCache::new_dwarf_cache(
+            unit_node.entry().offset(),
+            VariableName::StaticScopeRoot,
+            Some(unit_info),
+        ))
+    }
+
+    /// Creates the unpopulated cache for `function` variables
+    pub(crate) fn create_function_scope_cache(
+        &self,
+        die_cursor_state: &FunctionDie,
+        unit_info: &UnitInfo,
+    ) -> Result<VariableCache, DebugError> {
+        let abbrevs = &unit_info.unit.abbreviations;
+        let mut tree = unit_info
+            .unit
+            .header
+            .entries_tree(abbrevs, Some(die_cursor_state.function_die.offset()))?;
+        let function_node = tree.root()?;
+
+        let function_variable_cache = VariableCache::new_dwarf_cache(
+            function_node.entry().offset(),
+            VariableName::LocalScopeRoot,
+            Some(unit_info),
+        );
+
+        Ok(function_variable_cache)
+    }
+
+    /// This effects the on-demand expansion of lazy/deferred load of all the 'child' `Variable`s for a given 'parent'.
+    pub fn cache_deferred_variables(
+        &self,
+        cache: &mut VariableCache,
+        memory: &mut dyn MemoryInterface,
+        parent_variable: &mut Variable,
+        frame_info: StackFrameInfo<'_>,
+    ) -> Result<(), DebugError> {
+        if !parent_variable.is_valid() {
+            // Do nothing. The parent_variable.get_value() will already report back the debug_error value.
+            return Ok(());
+        }
+
+        // Only attempt this part if we have not yet resolved the referenced children.
+        if cache.has_children(parent_variable)? {
+            return Ok(());
+        }
+
+        let Some(header_offset) = parent_variable.unit_header_offset else {
+            return Ok(());
+        };
+
+        let unit_header = self.dwarf.debug_info.header_from_offset(header_offset)?;
+        let unit_info = UnitInfo::new(gimli::Unit::new(&self.dwarf, unit_header)?);
+
+        match parent_variable.variable_node_type {
+            VariableNodeType::ReferenceOffset(reference_offset) => {
+                // Reference to a type, or an node.entry() to another type or a type modifier which will point to another type.
+                let mut type_tree = unit_info
+                    .unit
+                    .header
+                    .entries_tree(&unit_info.unit.abbreviations, Some(reference_offset))?;
+                let referenced_node = type_tree.root()?;
+                let mut referenced_variable = cache.create_variable(
+                    parent_variable.variable_key,
+                    Some(referenced_node.entry().offset()),
+                    Some(&unit_info),
+                )?;
+
+                referenced_variable.name = match &parent_variable.name {
+                    VariableName::Named(name) if name.starts_with("Some ") => VariableName::Named(name.replacen('&', "*", 1)) ,
+                    VariableName::Named(name) => VariableName::Named(format!("*{name}")),
+                    other => VariableName::Named(format!("Error: Unable to generate name, parent variable does not have a name but is special variable {other:?}")),
+                };
+
+                referenced_variable = unit_info.extract_type(
+                    self,
+                    referenced_node,
+                    parent_variable,
+                    referenced_variable,
+                    memory,
+                    cache,
+                    frame_info,
+                )?;
+
+                if referenced_variable.type_name == VariableType::Base("()".to_owned()) {
+                    // Only use this, if it is NOT a unit datatype.
+                    cache.remove_cache_entry(referenced_variable.variable_key)?;
+                }
+
+                // The string that a C `char *` points to is only read when the pointer is expanded.
+                if parent_variable.value.is_empty() {
+                    if let Some(value) = unit_info.c_string_value(
+                        self,
+                        reference_offset,
+                        parent_variable,
+                        &referenced_variable,
+                        memory,
+                    )? {
+                        parent_variable.set_value(value);
+                        cache.update_variable(parent_variable)?;
+                    }
+                }
+            }
+            VariableNodeType::TypeOffset(type_offset) => {
                 // Find the parent node
                 let mut type_tree = unit_info
                     .unit
@@ -378,6 +1628,14 @@ impl DebugInfo {
                 )?;
 
                 cache.adopt_grand_children(parent_variable, &temporary_variable)?;
+                // The discriminant of a tagged union is read while resolving the active variant.
+                if temporary_variable.discriminant_value.is_some()
+                    || temporary_variable.active_variant_name.is_some()
+                {
+                    parent_variable.discriminant_value = temporary_variable.discriminant_value;
+                    parent_variable.active_variant_name = temporary_variable.active_variant_name;
+                    cache.update_variable(parent_variable)?;
+                }
             }
             VariableNodeType::DirectLookup => {
                 // Find the parent node
@@ -464,9 +1722,16 @@ impl DebugInfo {
                 registers: unwind_registers,
                 frame_base: None,
                 canonical_frame_address: cfa,
+                thread_local_storage_base: None,
+                registers_only: false,
+                frame_torn_down: false,
             },
         )?;
 
+        // In the epilogue, a frame base in a register may already have been restored to the value of the caller.
+        let frame_torn_down =
+            functions[0].has_register_frame_base()? && self.is_in_epilogue(address);
+
         // Handle all functions which contain further inlined functions. For
         // these functions, the location is the call site of the inlined function.
         for (index, function_die) in functions[0..functions.len() - 1].iter().enumerate() {
@@ -530,10 +1795,12 @@ impl DebugInfo {
                     registers: unwind_registers.clone(),
                     pc: inlined_call_site,
                     frame_base,
+                    frame_torn_down,
                     is_inlined: function_die.is_inline(),
                     static_variables,
                     local_variables,
                     canonical_frame_address: cfa,
+                    return_location: None,
                 });
             } else {
                 tracing::warn!(
@@ -592,10 +1859,12 @@ impl DebugInfo {
                 _ => RegisterValue::from(address),
             },
             frame_base,
+            frame_torn_down,
             is_inlined: last_function.is_inline(),
             static_variables,
             local_variables,
             canonical_frame_address: cfa,
+            return_location: None,
         });
 
         Ok(frames)
@@ -726,10 +1995,12 @@ impl DebugInfo {
                                 _ => RegisterValue::from(address),
                             },
                             frame_base: None,
+                            frame_torn_down: false,
                             is_inlined: false,
                             static_variables: None,
                             local_variables: None,
                             canonical_frame_address: None,
+                            return_location: None,
                         }
                     } else {
                         let address = frame_pc;
@@ -753,10 +2024,12 @@ impl DebugInfo {
                                 _ => RegisterValue::from(address),
                             },
                             frame_base: None,
+                            frame_torn_down: false,
                             is_inlined: false,
                             static_variables: None,
                             local_variables: None,
                             canonical_frame_address: None,
+                            return_location: None,
                         }
                     }
                 }
@@ -796,6 +2069,7 @@ impl DebugInfo {
                     // If we are at an exception handler frame, we need to overwrite the unwind registers.
                     // This will allow us to continue unwinding from the exception handler frame.
                     unwind_registers = exception_info.calling_frame_registers;
+                    unwind_registers.clear_origins();
 
                     stack_frames.push(return_frame);
                     continue;
@@ -829,7 +2103,8 @@ impl DebugInfo {
                         .and_then(|lr| lr.value);
 
                     if let Some(calling_pc) = unwind_registers.get_program_counter_mut() {
-                        if let ControlFlow::Break(error) = unwind_register(
+                        let calling_pc_id = calling_pc.core_register.id;
+                        let calling_pc_origin = match unwind_register(
                             calling_pc,
                             &callee_frame_registers,
                             None,
@@ -838,15 +2113,28 @@ impl DebugInfo {
                             memory,
                             instruction_set,
                         ) {
-                            // This is not fatal, but we cannot continue unwinding beyond the current frame.
-                            tracing::error!("{:?}", &error);
-                            return_frame.function_name =
-                                format!("{} : ERROR : {error}", &return_frame.function_name);
-                            stack_frames.push(return_frame);
-                            break 'unwind;
-                        }
+                            ControlFlow::Continue(origin) => origin,
+                            ControlFlow::Break(error) => {
+                                // This is not fatal, but we cannot continue unwinding beyond the current frame.
+                                tracing::error!("{:?}", &error);
+                                return_frame.function_name =
+                                    format!("{} : ERROR : {error}", &return_frame.function_name);
+                                stack_frames.push(return_frame);
+                                break 'unwind;
+                            }
+                        };
 
                         // The unwind registers were updated with the calling frame's PC, so we can continue unwinding.
+                        // All the other registers keep the values of the called frame.
+                        for register in &mut unwind_registers.0 {
+                            register.origin = Some(if register.core_register.id == calling_pc_id {
+                                calling_pc_origin
+                            } else if register.value.is_none() {
+                                RegisterOrigin::Undefined
+                            } else {
+                                RegisterOrigin::SameAsCallee
+                            });
+                        }
                     }
 
                     stack_frames.push(return_frame);
@@ -866,7 +2154,7 @@ impl DebugInfo {
             // We sometimes need to keep a copy of the LR value to calculate the PC. For both ARM, and RISC-V, The LR will be unwound before the PC, so we can reference it safely.
             let mut unwound_return_address: Option<RegisterValue> = None;
             for debug_register in unwind_registers.0.iter_mut() {
-                if let ControlFlow::Break(error) = unwind_register(
+                match unwind_register(
                     debug_register,
                     &callee_frame_registers,
                     Some(unwind_info),
@@ -875,11 +2163,14 @@ impl DebugInfo {
                     memory,
                     instruction_set,
                 ) {
-                    tracing::error!("{:?}", &error);
-                    return_frame.function_name =
-                        format!("{} : ERROR: {error}", &return_frame.function_name);
-                    stack_frames.push(return_frame);
-                    break 'unwind;
+                    ControlFlow::Continue(origin) => debug_register.origin = Some(origin),
+                    ControlFlow::Break(error) => {
+                        tracing::error!("{:?}", &error);
+                        return_frame.function_name =
+                            format!("{} : ERROR: {error}", &return_frame.function_name);
+                        stack_frames.push(return_frame);
+                        break 'unwind;
+                    }
                 };
             }
 
@@ -900,6 +2191,7 @@ impl DebugInfo {
                         exception_handler.exception_details(memory, &unwind_registers)?
                     {
                         unwind_registers = details.calling_frame_registers;
+                        unwind_registers.clear_origins();
                         let address = frame_pc;
 
                         let exception_frame = StackFrame {
@@ -913,10 +2205,12 @@ impl DebugInfo {
                                 _ => RegisterValue::from(address),
                             },
                             frame_base: None,
+                            frame_torn_down: false,
                             is_inlined: false,
                             static_variables: None,
                             local_variables: None,
                             canonical_frame_address: None,
+                            return_location: None,
                         };
 
                         stack_frames.push(exception_frame);
@@ -925,17 +2219,117 @@ impl DebugInfo {
             }
         }
 
+        self.set_return_locations(&mut stack_frames);
+
         Ok(stack_frames)
     }
 
+    /// Record in each of the unwound `stack_frames` the source location in its calling frame that it will return to.
+    ///
+    /// The calling frame of an inlined function shares its program counter, and is located at the call site already.
+    /// For other functions, the program counter of the calling frame is the return address,
+    /// so we use the instruction before it, to attribute the return to the line of the call.
+    fn set_return_locations(&self, stack_frames: &mut [StackFrame]) {
+        for index in 1..stack_frames.len() {
+            let (callees, callers) = stack_frames.split_at_mut(index);
+            let callee = &mut callees[index - 1];
+            let caller = &callers[0];
+
+            callee.return_location = if callee.is_inlined {
+                caller.source_location.clone()
+            } else {
+                let return_address: Option<u64> = caller.pc.try_into().ok();
+                return_address
+                    .and_then(|return_address| return_address.checked_sub(1))
+                    .and_then(|call_address| self.get_source_location(call_address))
+            };
+        }
+    }
+
+    /// Calculate the CFA (Canonical Frame Address) of the function at the program counter of `registers`,
+    /// using the unwind information.
+    ///
+    /// This allows expressions with `DW_OP_call_frame_cfa` to be evaluated when the CFA was not supplied by the unwinder,
+    /// e.g. for the locations of variables of functions that do not have a `DW_AT_frame_base`.
+    pub(crate) fn canonical_frame_address(&self, registers: &DebugRegisters) -> Option<u64> {
+        let program_counter = registers
+            .get_program_counter()
+            .and_then(|register| register.value)?
+            .try_into()
+            .ok()?;
+        let mut unwind_context = Box::new(gimli::UnwindContext::new());
+        let unwind_info =
+            get_unwind_info(&mut unwind_context, &self.frame_section, program_counter).ok()?;
+        determine_cfa(registers, unwind_info).ok().flatten()
+    }
+
     /// Find the program counter where a breakpoint should be set,
     /// given a source file, a line and optionally a column.
+    ///
+    /// The source file is matched like in [`Self::resolve_breakpoint_with`].
     pub fn get_breakpoint_location(
         &self,
         path: &TypedPathBuf,
         line: u64,
         column: Option<u64>,
+        translation: &SourcePathTranslation,
     ) -> Result<VerifiedBreakpoint, DebugError> {
+        self.resolve_breakpoint_with(path, line, column, translation)
+            .map(|resolution| VerifiedBreakpoint {
+                address: resolution.address,
+                source_location: resolution.source_location,
+            })
+    }
+
+    /// Resolve a batch of breakpoint requests like [`Self::resolve_breakpoint_with`], e.g. all breakpoints of a source file.
+    ///
+    /// The result contains one entry for each request, in the same order,
+    /// so that a request that can not be resolved does not fail the whole batch.
+    pub fn resolve_breakpoints(
+        &self,
+        requests: &[BreakpointRequest],
+        translation: &SourcePathTranslation,
+    ) -> Vec<Result<BreakpointResolution, DebugError>> {
+        requests
+            .iter()
+            .map(|request| {
+                self.resolve_breakpoint_with(
+                    &request.path,
+                    request.line,
+                    request.column,
+                    translation,
+                )
+            })
+            .collect()
+    }
+
+    /// Resolve the best address for a breakpoint, given a source file, a line and optionally a column,
+    /// as well as any alternative addresses that also match the request.
+    ///
+    /// The preferred address is chosen as follows:
+    /// 1. The first statement (after the prologue, if the line is the start of a function) that matches the line and column.
+    /// 2. If there is no exact column match, or no column was requested, the first statement on the line.
+    pub fn resolve_breakpoint(
+        &self,
+        path: &TypedPathBuf,
+        line: u64,
+        column: Option<u64>,
+    ) -> Result<BreakpointResolution, DebugError> {
+        self.resolve_breakpoint_with(path, line, column, &SourcePathTranslation::default())
+    }
+
+    /// Resolve a breakpoint like [`Self::resolve_breakpoint`], for a source file at `path` on the host,
+    /// e.g. as it was sent by an editor.
+    ///
+    /// If the source files in the debug information are translated to other paths on the host,
+    /// `path` also matches the source files that `translation` maps to it.
+    pub fn resolve_breakpoint_with(
+        &self,
+        path: &TypedPathBuf,
+        line: u64,
+        column: Option<u64>,
+        translation: &SourcePathTranslation,
+    ) -> Result<BreakpointResolution, DebugError> {
         tracing::debug!(
             "Looking for breakpoint location for {}:{}:{}",
             path.to_path().display(),
@@ -945,50 +2339,202 @@ impl DebugInfo {
                 .unwrap_or_else(|| "-".to_owned())
         );
 
+        let mut candidates: Vec<(VerifiedBreakpoint, &UnitInfo)> = Vec::new();
         for unit_header in &self.unit_infos {
             let Some(ref line_program) = &unit_header.unit.line_program else {
                 continue;
             };
 
-            if let Some(location) =
-                self.get_breakpoint_location_in_unit(unit_header, line_program, path, line, column)?
+            for candidate in self.get_breakpoint_locations_in_unit(
+                unit_header,
+                line_program,
+                path,
+                line,
+                column,
+                translation,
+            )? {
+                if !candidates
+                    .iter()
+                    .any(|(existing, _)| existing.address == candidate.address)
+                {
+                    candidates.push((candidate, unit_header));
+                }
+            }
+        }
+
+        let requested_column = column.map(super::ColumnType::Column);
+        let preferred_index = candidates
+            .iter()
+            .position(|(candidate, _)| {
+                requested_column.is_some() && candidate.source_location.column == requested_column
+            })
+            .unwrap_or(0);
+
+        if preferred_index >= candidates.len() {
+            return Err(DebugError::Other(anyhow::anyhow!(
+                "No valid breakpoint information found for file: {}, line: {:?}, column: {:?}",
+                path.to_path().display(),
+                line,
+                column
+            )));
+        }
+
+        let (preferred, preferred_unit) = candidates.remove(preferred_index);
+
+        // Other statements of the same instance of the line, e.g. the first statement when the column matched
+        // a later one, are not alternatives. Only one address is used for each other instance.
+        let mut alternatives = Vec::new();
+        if !candidates.is_empty() {
+            let mut instances = vec![self.line_instance(preferred_unit, preferred.address)];
+            for (candidate, unit_info) in candidates {
+                let instance = self.line_instance(unit_info, candidate.address);
+                if instance.is_some() && instances.contains(&instance) {
+                    continue;
+                }
+                instances.push(instance);
+                alternatives.push(candidate.address);
+            }
+        }
+
+        Ok(BreakpointResolution {
+            address: preferred.address,
+            source_location: preferred.source_location,
+            alternatives,
+        })
+    }
+
+    /// Find the address to move the program counter to, for a "set next statement" (or "jump") to `line` of the
+    /// source file at `path`, while halted at `program_counter`.
+    ///
+    /// The address is the start of a statement on the line, resolved like a breakpoint, but only inside the function
+    /// that contains `program_counter`, because moving the program counter into another function would continue
+    /// with a stack frame that does not belong to it.
+    /// Returns `None` if the line has no statement in the current function.
+    pub fn address_for_set_next_statement(
+        &self,
+        path: &TypedPathBuf,
+        line: u64,
+        program_counter: u64,
+        translation: &SourcePathTranslation,
+    ) -> Option<u64> {
+        let resolution = self
+            .resolve_breakpoint_with(path, line, None, translation)
+            .ok()?;
+        std::iter::once(resolution.address)
+            .chain(resolution.alternatives)
+            .find(|&address| self.same_function(program_counter, address))
+    }
+
+    /// Check if any row of the line tables maps to `line` of the source file at `path`,
+    /// e.g. to show which lines can have a breakpoint, and which ones are only comments or declarations.
+    ///
+    /// Lines of functions that were inlined everywhere, and have no code of their own,
+    /// also have code, through the rows of the functions they are inlined into.
+    ///
+    /// The source file is matched like in [`Self::resolve_breakpoint_with`].
+    pub fn line_has_code(
+        &self,
+        path: &TypedPathBuf,
+        line: u64,
+        translation: &SourcePathTranslation,
+    ) -> bool {
+        self.unit_infos.iter().any(|unit_info| {
+            self.unit_line_has_code(unit_info, path, line, translation)
+                .unwrap_or(false)
+        })
+    }
+
+    /// Check if any row of the line table of `unit_info` maps to `line` of the source file at `path`.
+    fn unit_line_has_code(
+        &self,
+        unit_info: &UnitInfo,
+        path: &TypedPathBuf,
+        line: u64,
+        translation: &SourcePathTranslation,
+    ) -> Result<bool, DebugError> {
+        let unit = &unit_info.unit;
+        let Some(line_program) = unit.line_program.as_ref() else {
+            return Ok(false);
+        };
+
+        // Only units that include the file can have code for it.
+        if !self.unit_references_file(unit_info, path, translation) {
+            return Ok(false);
+        }
+
+        let mut rows = line_program.clone().rows();
+        while let Some((header, row)) = rows.next_row()? {
+            // The row that ends a sequence is the address after the last instruction.
+            if row.end_sequence() || row.line().map(NonZeroU64::get) != Some(line) {
+                continue;
+            }
+            if row
+                .file(header)
+                .and_then(|file_entry| self.get_path(unit, header, file_entry))
+                .is_some_and(|row_path| source_path_eq(path, &row_path, translation))
             {
-                return Ok(location);
-            };
+                return Ok(true);
+            }
         }
+        Ok(false)
+    }
 
-        let p = path.to_path();
+    /// Check if any of the file names in the line program header of `unit_info` match `path`, with `translation`.
+    fn unit_references_file(
+        &self,
+        unit_info: &UnitInfo,
+        path: &TypedPathBuf,
+        translation: &SourcePathTranslation,
+    ) -> bool {
+        let unit = &unit_info.unit;
+        let Some(line_program) = unit.line_program.as_ref() else {
+            return false;
+        };
+        let header = line_program.header();
+        header.file_names().iter().any(|file_name| {
+            self.get_path(unit, header, file_name)
+                .is_some_and(|file_path| source_path_eq(path, &file_path, translation))
+        })
+    }
 
-        Err(DebugError::Other(anyhow::anyhow!(
-            "No valid breakpoint information found for file: {}, line: {:?}, column: {:?}",
-            p.display(),
-            line,
-            column
-        )))
+    /// The offset of the innermost function, or inlined function, in `unit_info` that contains `address`,
+    /// which identifies the instance of a line that the address belongs to.
+    fn line_instance(
+        &self,
+        unit_info: &UnitInfo,
+        address: u64,
+    ) -> Option<gimli::UnitSectionOffset> {
+        unit_info
+            .get_function_dies(self, address, true)
+            .ok()?
+            .last()
+            .map(|function_die| {
+                function_die
+                    .function_die
+                    .offset()
+                    .to_unit_section_offset(&unit_info.unit)
+            })
     }
 
-    fn get_breakpoint_location_in_unit(
+    /// Find all the valid breakpoint locations in a unit, for the given source file, line and optionally a column.
+    /// For each instance of the line, the statement with a matching column is listed before the first statement on the line.
+    fn get_breakpoint_locations_in_unit(
         &self,
         unit_header: &UnitInfo,
         line_program: &gimli::IncompleteLineProgram<GimliReader, usize>,
         path: &TypedPathBuf,
         line: u64,
         column: Option<u64>,
-    ) -> Result<Option<VerifiedBreakpoint>, DebugError> {
-        let unit = &unit_header.unit;
-        let header: &LineProgramHeader<GimliReader, usize> = line_program.header();
-
-        // Check if any of the file names in the header match the path we are looking for.
-        if !header.file_names().iter().any(|file_name| {
-            let combined_path = self.get_path(unit, header, file_name);
-
-            combined_path
-                .map(|p| canonical_path_eq(path, &p))
-                .unwrap_or(false)
-        }) {
-            return Ok(None);
+        translation: &SourcePathTranslation,
+    ) -> Result<Vec<VerifiedBreakpoint>, DebugError> {
+        // Only units that include the file can have statements for it.
+        if !self.unit_references_file(unit_header, path, translation) {
+            return Ok(Vec::new());
         }
 
+        let unit = &unit_header.unit;
+        let mut locations: Vec<VerifiedBreakpoint> = Vec::new();
+
         let mut rows = line_program.clone().rows();
 
         while let Some((header, row)) = rows.next_row()? {
@@ -997,7 +2543,7 @@ impl DebugInfo {
                 .and_then(|file_entry| self.get_path(unit, header, file_entry));
 
             if row_path
-                .map(|p| !canonical_path_eq(path, &p))
+                .map(|p| !source_path_eq(path, &p, translation))
                 .unwrap_or(true)
             {
                 continue;
@@ -1011,11 +2557,22 @@ impl DebugInfo {
                 continue;
             }
 
-            // The first match of the file and row will be used to build the SourceStatements, and then:
+            // Each match of the file and row will be used to build the SourceStatements, and then:
             // 1. If there is an exact column match, we will use the low_pc of the statement at that column and line.
-            // 2. If there is no exact column match, we use the first available statement in the line.
-            let source_statements =
-                SourceStatements::new(self, unit_header, row.address())?.statements;
+            // 2. We also use the first available statement in the line.
+            let source_statements = match SourceStatements::new(self, unit_header, row.address()) {
+                Ok(source_statements) => source_statements.statements,
+                Err(error) => {
+                    // There may be no valid halt locations after this row, e.g. when it is part of the epilogue,
+                    // while other instances of the line are still valid.
+                    tracing::debug!(
+                        "No breakpoint location for the row at {:#010x}: {:?}",
+                        row.address(),
+                        error
+                    );
+                    continue;
+                }
+            };
 
             let halt_address_and_location = |source_statement: &SourceStatement| {
                 (
@@ -1034,44 +2591,49 @@ impl DebugInfo {
                                 column: Some(source_statement.column.into()),
                                 file,
                                 directory,
-                                low_pc: Some(source_statement.low_pc() as u32),
-                                high_pc: Some(source_statement.instruction_range.end as u32),
+                                low_pc: Some(source_statement.low_pc()),
+                                high_pc: Some(source_statement.instruction_range.end),
                             })
                         }),
                 )
             };
 
+            // The statements can alternate between files, e.g. for code that is inlined from a header,
+            // so the line only matches a statement in the same file as the row.
+            let is_requested_line = |statement: &&SourceStatement| {
+                statement.file_index == row.file_index() && statement.line == Some(cur_line)
+            };
+
             let first_find = source_statements.iter().find(|statement| {
                 column
                     .and_then(NonZeroU64::new)
                     .map(ColumnType::Column)
                     .map_or(false, |col| col == statement.column)
-                    && statement.line == Some(cur_line)
+                    && is_requested_line(statement)
             });
 
-            if let Some((halt_address, Some(halt_location))) =
-                first_find.map(halt_address_and_location)
-            {
-                return Ok(Some(VerifiedBreakpoint {
-                    address: halt_address,
-                    source_location: halt_location,
-                }));
-            }
-            let second_find = source_statements
-                .iter()
-                .find(|statement| statement.line == Some(cur_line));
+            let second_find = source_statements.iter().find(is_requested_line);
 
-            if let Some((halt_address, Some(halt_location))) =
-                second_find.map(halt_address_and_location)
+            for (halt_address, halt_location) in [first_find, second_find]
+                .into_iter()
+                .flatten()
+                .map(halt_address_and_location)
             {
-                return Ok(Some(VerifiedBreakpoint {
-                    address: halt_address,
-                    source_location: halt_location,
-                }));
+                if let Some(halt_location) = halt_location {
+                    if !locations
+                        .iter()
+                        .any(|location| location.address == halt_address)
+                    {
+                        locations.push(VerifiedBreakpoint {
+                            address: halt_address,
+                            source_location: halt_location,
+                        });
+                    }
+                }
             }
         }
 
-        Ok(None)
+        Ok(locations)
     }
 
     /// Get the path for an entry in a line program header, using the compilation unit's directory and file entries.
@@ -1085,22 +2647,31 @@ impl DebugInfo {
         let file_name_attr_string = self.dwarf.attr_string(unit, file_entry.path_name()).ok()?;
         let name_path = from_utf8(&file_name_attr_string).ok()?;
 
-        let dir_name_attr_string = file_entry
-            .directory(header)
-            .and_then(|dir| self.dwarf.attr_string(unit, dir).ok());
-
-        let dir_path = dir_name_attr_string.and_then(|dir_name| {
-            from_utf8(&dir_name)
+        let directory_path = |directory_index: u64| {
+            let dir_name_attr_string = header
+                .directory(directory_index)
+                .and_then(|dir| self.dwarf.attr_string(unit, dir).ok())?;
+            from_utf8(&dir_name_attr_string)
                 .ok()
                 .map(|p| TypedPath::derive(p).to_path_buf())
-        });
+        };
 
-        let mut combined_path = match dir_path {
+        let mut combined_path = match directory_path(file_entry.directory_index()) {
             Some(dir_path) => dir_path.join(name_path),
             None => TypedPath::derive(name_path).to_path_buf(),
         };
 
-        if combined_path.is_relative() {
+        // Relative paths are relative to the compilation directory, which is the directory with index 0.
+        // In DWARF 5, this is an entry of the line program, while for earlier versions,
+        // gimli substitutes the `DW_AT_comp_dir` of the unit.
+        if combined_path.is_relative() && file_entry.directory_index() != 0 {
+            if let Some(compilation_directory) = directory_path(0) {
+                combined_path = compilation_directory.join(&combined_path);
+            }
+        }
+
+        // The compilation directory of a DWARF 5 line program may itself be relative to the `DW_AT_comp_dir`.
+        if combined_path.is_relative() && header.version() >= 5 {
             let comp_dir = unit
                 .comp_dir
                 .as_ref()
@@ -1146,6 +2717,25 @@ pub(crate) fn canonical_path_eq(
     primary_path.normalize() == secondary_path.normalize()
 }
 
+/// Check if the source file at `requested_path`, e.g. as it was sent by an editor, is the source file at
+/// `debug_info_path` in the debug information.
+///
+/// The paths match if they are equal, or if `translation` maps one to the other, in either direction.
+fn source_path_eq(
+    requested_path: &TypedPathBuf,
+    debug_info_path: &TypedPathBuf,
+    translation: &SourcePathTranslation,
+) -> bool {
+    canonical_path_eq(requested_path, debug_info_path)
+        || translation
+            .to_target_path(requested_path)
+            .is_some_and(|target_path| canonical_path_eq(&target_path, debug_info_path))
+        || translation
+            .to_host_path(debug_info_path)
+            .and_then(|host_path| host_path.to_str().map(TypedPathBuf::from))
+            .is_some_and(|host_path| canonical_path_eq(requested_path, &host_path))
+}
+
 /// Get a handle to the [`gimli::UnwindTableRow`] for this call frame, so that we can reference it to unwind register values.
 fn get_unwind_info<'a>(
     unwind_context: &'a mut UnwindContext<DwarfReader>,
@@ -1186,7 +2776,10 @@ fn determine_cfa<R: gimli::Reader>(
     unwind_info: &UnwindTableRow<R>,
 ) -> Result<Option<u64>, crate::Error> {
     let gimli::CfaRule::RegisterAndOffset { register, offset } = unwind_info.cfa() else {
-        unimplemented!()
+        return Err(crate::Error::Other(anyhow::anyhow!(
+            "UNWIND: Unsupported CFA rule {:?}",
+            unwind_info.cfa()
+        )));
     };
 
     let reg_val = unwind_registers
@@ -1227,6 +2820,7 @@ fn determine_cfa<R: gimli::Reader>(
 }
 
 /// A per_register unwind, applying register rules and updating the [`registers::DebugRegister`] value as appropriate, before returning control to the calling function.
+/// Returns how the value of the register was determined.
 fn unwind_register(
     debug_register: &mut super::DebugRegister,
     // The callee_frame_registers are used to lookup values and never updated.
@@ -1236,7 +2830,7 @@ fn unwind_register(
     unwound_return_address: &mut Option<RegisterValue>,
     memory: &mut dyn MemoryInterface,
     instruction_set: Option<InstructionSet>,
-) -> ControlFlow<crate::Error, ()> {
+) -> ControlFlow<crate::Error, RegisterOrigin> {
     use gimli::read::RegisterRule;
 
     // If we do not have unwind info, or there is no register rule, then use UnwindRule::Undefined.
@@ -1248,6 +2842,8 @@ fn unwind_register(
         .unwrap_or(RegisterRule::Undefined);
 
     let mut register_rule_string = format!("{register_rule:?}");
+    // Values that were not recovered with the unwind rules are passed through from the called frame.
+    let mut origin = RegisterOrigin::Recovered;
 
     let new_value = match register_rule {
         RegisterRule::Undefined => {
@@ -1258,6 +2854,7 @@ fn unwind_register(
                     .register_has_role(RegisterRole::FramePointer) =>
                 {
                     register_rule_string = "FP=CFA (dwarf Undefined)".to_string();
+                    origin = RegisterOrigin::SameAsCallee;
                     callee_frame_registers
                         .get_frame_pointer()
                         .and_then(|fp| fp.value)
@@ -1306,6 +2903,7 @@ fn unwind_register(
                     match other_register.core_register.unwind_rule {
                         UnwindRule::Preserve => {
                             register_rule_string = "Preserve".to_string();
+                            origin = RegisterOrigin::SameAsCallee;
                             callee_frame_registers
                                 .get_register(other_register.core_register.id)
                                 .and_then(|reg| reg.value)
@@ -1326,9 +2924,12 @@ fn unwind_register(
             }
         }
 
-        RegisterRule::SameValue => callee_frame_registers
-            .get_register(debug_register.core_register.id)
-            .and_then(|reg| reg.value),
+        RegisterRule::SameValue => {
+            origin = RegisterOrigin::SameAsCallee;
+            callee_frame_registers
+                .get_register(debug_register.core_register.id)
+                .and_then(|reg| reg.value)
+        }
 
         RegisterRule::Offset(address_offset) => {
             // "The previous value of this register is saved at the address CFA+N where CFA is the current CFA value and N is a signed offset"
@@ -1397,6 +2998,9 @@ fn unwind_register(
         _ => unimplemented!(),
     };
     debug_register.value = new_value;
+    if debug_register.value.is_none() {
+        origin = RegisterOrigin::Undefined;
+    }
 
     tracing::trace!(
         "UNWIND - {:>10}: Caller: {}\tCallee: {}\tRule: {}",
@@ -1408,7 +3012,7 @@ fn unwind_register(
             .unwrap_or_default(),
         register_rule_string,
     );
-    ControlFlow::Continue(())
+    ControlFlow::Continue(origin)
 }
 
 /// Helper function to determine the program counter value for the previous frame.
@@ -1485,30 +3089,25 @@ mod test {
             exception_handling::{ArmV6MExceptionHandler, ArmV7MExceptionHandler},
             registers::cortex_m::CORTEX_M_CORE_REGISTERS,
         },
-        core::exception_handler_for_core,
+        core::{exception_handler_for_core, RegisterId, RegisterRole},
         debug::{
+            source_statement::{emits_empty_statement_views, SourceStatements},
             stack_frame::{StackFrameInfo, TestFormatter},
-            DebugInfo, DebugRegister, DebugRegisters,
+            BreakpointRequest, ColumnType, DebugInfo, DebugInfoOptions, DebugInfoProgress,
+            DebugRegister, DebugRegisters, FoldedStackOrder, RegisterOrigin, SectionMemory,
+            SourcePathTranslation, StackFrame, VariableCache, VariableLocation, VariableName,
+            VariableType,
+        },
+        test::{
+            cortex_m_registers, get_path_for_test_files, load_test_elf_as_debug_info,
+            x86_64::{RBP, RIP, RSP},
+            x86_64_registers, MockMemory,
         },
-        test::MockMemory,
         CoreDump, RegisterValue,
     };
-    use std::path::{Path, PathBuf};
+    use std::{cell::RefCell, path::Path, rc::Rc};
     use test_case::test_case;
-
-    /// Get the full path to a file in the `tests` directory.
-    fn get_path_for_test_files(relative_file: &str) -> PathBuf {
-        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-        path.push("tests");
-        path.push(relative_file);
-        path
-    }
-
-    /// Load the DebugInfo from the `elf_file` for the test.
-    /// `elf_file` should be the name of a file(or relative path) in the `tests` directory.
-    fn load_test_elf_as_debug_info(elf_file: &str) -> DebugInfo {
-        DebugInfo::from_file(get_path_for_test_files(elf_file)).unwrap()
-    }
+    use typed_path::TypedPathBuf;
 
     #[test]
     fn unwinding_first_instruction_after_exception() {
@@ -1564,6 +3163,7 @@ mod test {
             dwarf_id: Some(id as u16),
             core_register: CORTEX_M_CORE_REGISTERS.core_register(id),
             value: Some(RegisterValue::U32(r)),
+            origin: None,
         })
         .collect();
 
@@ -1711,6 +3311,7 @@ mod test {
             dwarf_id: Some(id as u16),
             core_register: CORTEX_M_CORE_REGISTERS.core_register(id),
             value: Some(RegisterValue::U32(r)),
+            origin: None,
         })
         .collect();
 
@@ -1836,6 +3437,7 @@ mod test {
             dwarf_id: Some(id as u16),
             core_register: CORTEX_M_CORE_REGISTERS.core_register(id),
             value: Some(RegisterValue::U32(r)),
+            origin: None,
         })
         .collect();
 
@@ -1941,6 +3543,7 @@ mod test {
             dwarf_id: Some(id as u16),
             core_register: CORTEX_M_CORE_REGISTERS.core_register(id),
             value: Some(RegisterValue::U32(r)),
+            origin: None,
         })
         .collect();
 
@@ -1961,26 +3564,258 @@ mod test {
 
         let exception_handler = Box::new(ArmV7MExceptionHandler {});
 
-        let frames = debug_info
-            .unwind_impl(
-                regs,
-                &mut dummy_mem,
+        let frames = debug_info
+            .unwind_impl(
+                regs,
+                &mut dummy_mem,
+                exception_handler.as_ref(),
+                Some(probe_rs_target::InstructionSet::Thumb2),
+            )
+            .unwrap();
+
+        let printed_backtrace = frames
+            .into_iter()
+            .map(|f| TestFormatter(&f).to_string())
+            .collect::<Vec<String>>()
+            .join("");
+
+        insta::assert_snapshot!(printed_backtrace);
+    }
+
+    /// Unwind the stack of the `inlined-functions` example, with the registers and stack of `unwinding_inlined`.
+    fn unwind_inlined_functions() -> (DebugInfo, Vec<StackFrame>) {
+        let debug_info = load_test_elf_as_debug_info("inlined-functions");
+
+        let registers = cortex_m_registers(|id| match id {
+            0 => 0xfffffecc,
+            1 => 0x00000001,
+            3 => 0x40008140,
+            4 => 0x000f4240,
+            5 => 0xfffffec0,
+            7 | 13 => 0x20003ff0,
+            12 => 0x5000050c,
+            14 => 0x00200000,
+            15 => 0x000002e4,
+            _ => 0,
+        });
+        let mut memory = MockMemory::new();
+        memory.add_word_range(
+            0x2000_3ff0,
+            &[0x20003ff8, 0x00000161, 0x00000000, 0x0000013d],
+        );
+
+        let frames = debug_info
+            .unwind_impl(
+                registers,
+                &mut memory,
+                &ArmV7MExceptionHandler {},
+                Some(probe_rs_target::InstructionSet::Thumb2),
+            )
+            .unwrap();
+
+        (debug_info, frames)
+    }
+
+    #[test]
+    fn register_origins_of_unwound_frames() {
+        let (_, frames) = unwind_inlined_functions();
+        let origin = |frame: &StackFrame, register_id: u16| {
+            frame
+                .registers
+                .get_register(RegisterId(register_id))
+                .unwrap()
+                .origin
+        };
+
+        // The registers of the innermost function, and the functions inlined into it, were read from the core.
+        assert_eq!(origin(&frames[0], 7), None);
+        assert_eq!(frames[5].function_name, "__cortex_m_rt_main");
+        assert_eq!(origin(&frames[5], 7), None);
+
+        // `__cortex_m_rt_main` saved R7 and LR on the stack, at the CFA-8 and CFA-4.
+        let trampoline = &frames[6];
+        assert_eq!(trampoline.function_name, "__cortex_m_rt_main_trampoline");
+        assert_eq!(origin(trampoline, 7), Some(RegisterOrigin::Recovered));
+        assert_eq!(
+            trampoline
+                .registers
+                .get_register(RegisterId(7))
+                .unwrap()
+                .value,
+            Some(RegisterValue::U32(0x2000_3ff8))
+        );
+        assert_eq!(origin(trampoline, 14), Some(RegisterOrigin::Recovered));
+        // The SP is the CFA, and the PC is the return address.
+        assert_eq!(origin(trampoline, 13), Some(RegisterOrigin::Recovered));
+        assert_eq!(origin(trampoline, 15), Some(RegisterOrigin::Recovered));
+        // R4 is callee-saved, but was not modified, so it keeps the value of the called frame.
+        assert_eq!(origin(trampoline, 4), Some(RegisterOrigin::SameAsCallee));
+        // R0 is not preserved across calls.
+        assert_eq!(origin(trampoline, 0), Some(RegisterOrigin::Undefined));
+    }
+
+    #[test_case(
+        FoldedStackOrder::RootFirst,
+        true,
+        concat!(
+            "<unknown function @ 0x0000013c>;<unknown function @ 0x0000013c>;",
+            "__cortex_m_rt_main_trampoline;__cortex_m_rt_main;",
+            "delay_ms<nrf52833_pac::TIMER0, nrf_hal_common::timer::OneShot>;",
+            "delay_ms<nrf52833_pac::TIMER0, nrf_hal_common::timer::OneShot>;",
+            "delay_us<nrf52833_pac::TIMER0, nrf_hal_common::timer::OneShot>;",
+            "delay<nrf52833_pac::TIMER0, nrf_hal_common::timer::OneShot>;",
+            "wait<nrf52833_pac::TIMER0, nrf_hal_common::timer::OneShot>",
+        );
+        "root first with inlined functions"
+    )]
+    #[test_case(
+        FoldedStackOrder::LeafFirst,
+        false,
+        concat!(
+            "__cortex_m_rt_main;__cortex_m_rt_main_trampoline;",
+            "<unknown function @ 0x0000013c>;<unknown function @ 0x0000013c>",
+        );
+        "leaf first without inlined functions"
+    )]
+    fn folded_stack(order: FoldedStackOrder, include_inlined: bool, expected: &str) {
+        let (_, stack_frames) = unwind_inlined_functions();
+
+        assert_eq!(
+            StackFrame::folded_stack(&stack_frames, order, include_inlined),
+            expected
+        );
+    }
+
+    #[test]
+    fn folded_stack_replaces_separators_in_function_names() {
+        let (_, mut stack_frames) = unwind_inlined_functions();
+        stack_frames.retain(|frame| !frame.is_inlined);
+        stack_frames[0].function_name = "main;loop ".to_string();
+
+        assert_eq!(
+            StackFrame::folded_stack(&stack_frames, FoldedStackOrder::LeafFirst, false),
+            concat!(
+                "main:loop;__cortex_m_rt_main_trampoline;",
+                "<unknown function @ 0x0000013c>;<unknown function @ 0x0000013c>",
+            )
+        );
+    }
+
+    #[test]
+    fn test_print_stacktrace() {
+        let elf = Path::new("./tests/gpio-hal-blinky/elf");
+        let coredump = include_bytes!("../../tests/gpio-hal-blinky/coredump");
+
+        let mut adapter = CoreDump::load_raw(coredump).unwrap();
+        let debug_info = DebugInfo::from_file(elf).unwrap();
+
+        let initial_registers = adapter.debug_registers();
+        let exception_handler = exception_handler_for_core(adapter.core_type());
+        let instruction_set = adapter.instruction_set();
+
+        let stack_frames = debug_info
+            .unwind(
+                &mut adapter,
+                initial_registers,
+                exception_handler.as_ref(),
+                Some(instruction_set),
+            )
+            .unwrap();
+
+        let printed_backtrace = stack_frames
+            .into_iter()
+            .map(|f| TestFormatter(&f).to_string())
+            .collect::<Vec<String>>()
+            .join("");
+
+        insta::assert_snapshot!(printed_backtrace);
+    }
+
+    #[test]
+    fn primary_source_file_of_inlined_code() {
+        let elf = Path::new("./tests/gpio-hal-blinky/elf");
+        let coredump = include_bytes!("../../tests/gpio-hal-blinky/coredump");
+
+        let adapter = CoreDump::load_raw(coredump).unwrap();
+        let debug_info = DebugInfo::from_file(elf).unwrap();
+        let program_counter: u64 = adapter
+            .debug_registers()
+            .get_program_counter()
+            .and_then(|register| register.value)
+            .unwrap()
+            .try_into()
+            .unwrap();
+
+        // The program counter is in `read` of the PAC, which is inlined into `timer_running` of the HAL.
+        let line_file = debug_info
+            .get_source_location(program_counter)
+            .and_then(|location| location.combined_typed_path())
+            .unwrap();
+        assert!(line_file.ends_with("nrf51-pac-0.10.1/src/generic.rs"));
+
+        let primary_file = debug_info.primary_source_file(program_counter).unwrap();
+        assert!(primary_file.ends_with("nrf-hal-common-0.14.1/src/timer.rs"));
+    }
+
+    #[test]
+    fn inlined_function_arguments() {
+        let elf = Path::new("./tests/gpio-hal-blinky/elf");
+        let coredump = include_bytes!("../../tests/gpio-hal-blinky/coredump");
+
+        let mut adapter = CoreDump::load_raw(coredump).unwrap();
+        let debug_info = DebugInfo::from_file(elf).unwrap();
+
+        let initial_registers = adapter.debug_registers();
+        let exception_handler = exception_handler_for_core(adapter.core_type());
+        let instruction_set = adapter.instruction_set();
+
+        let mut stack_frames = debug_info
+            .unwind(
+                &mut adapter,
+                initial_registers,
                 exception_handler.as_ref(),
-                Some(probe_rs_target::InstructionSet::Thumb2),
+                Some(instruction_set),
             )
             .unwrap();
+        let mut arguments = |frame: &mut StackFrame| {
+            let arguments = debug_info
+                .stack_frame_arguments(&mut adapter, frame)
+                .unwrap();
+            let local_variables = frame.local_variables.as_ref().unwrap();
+            arguments
+                .iter()
+                .map(|argument| {
+                    format!(
+                        "{}: {} = {}",
+                        argument.name,
+                        argument.type_name,
+                        argument.get_value(local_variables)
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
 
-        let printed_backtrace = frames
-            .into_iter()
-            .map(|f| TestFormatter(&f).to_string())
-            .collect::<Vec<String>>()
-            .join("");
-
-        insta::assert_snapshot!(printed_backtrace);
+        // The parameter of the inlined instance of `read` only has a location,
+        // and refers to the abstract origin for its name and type.
+        assert!(stack_frames[0].is_inlined);
+        assert_eq!(
+            arguments(&mut stack_frames[0]),
+            vec!["self: &nrf51_pac::generic::Reg<nrf51_pac::timer0::events_compare::EVENTS_COMPARE_SPEC> = Reg<nrf51_pac::timer0::events_compare::EVENTS_COMPARE_SPEC> @ 0x40008140"]
+        );
+        assert_eq!(
+            arguments(&mut stack_frames[4]),
+            vec![
+                "self: &mut nrf_hal_common::timer::Timer<nrf51_pac::TIMER0, nrf_hal_common::timer::OneShot> = Timer<nrf51_pac::TIMER0, nrf_hal_common::timer::OneShot> @ 0x20003FE8",
+                "us: u32 = 1000000",
+            ]
+        );
+        // The locals of `main` are not arguments.
+        assert_eq!(stack_frames[7].function_name, "__cortex_m_rt_main");
+        assert!(arguments(&mut stack_frames[7]).is_empty());
     }
 
     #[test]
-    fn test_print_stacktrace() {
+    fn return_location_of_nested_call() {
         let elf = Path::new("./tests/gpio-hal-blinky/elf");
         let coredump = include_bytes!("../../tests/gpio-hal-blinky/coredump");
 
@@ -1999,14 +3834,36 @@ mod test {
                 Some(instruction_set),
             )
             .unwrap();
+        let return_line = |frame: &StackFrame| {
+            frame
+                .return_location()
+                .map(|location| (location.file.unwrap(), location.line.unwrap()))
+        };
 
-        let printed_backtrace = stack_frames
-            .into_iter()
-            .map(|f| TestFormatter(&f).to_string())
-            .collect::<Vec<String>>()
-            .join("");
-
-        insta::assert_snapshot!(printed_backtrace);
+        // `read` is inlined into `timer_running`, so it returns to the call site in `timer_running`.
+        assert_eq!(
+            stack_frames[0].function_name,
+            "read<nrf51_pac::timer0::events_compare::EVENTS_COMPARE_SPEC>"
+        );
+        assert_eq!(
+            return_line(&stack_frames[0]),
+            Some(("timer.rs".to_string(), 397))
+        );
+        // `delay_us` is called from line 297 of `delay_ms`, while the return address is already on line 298.
+        assert_eq!(
+            stack_frames[4].function_name,
+            "delay_us<nrf51_pac::TIMER0, nrf_hal_common::timer::OneShot>"
+        );
+        assert_eq!(
+            stack_frames[5].source_location.as_ref().unwrap().line,
+            Some(298)
+        );
+        assert_eq!(
+            return_line(&stack_frames[4]),
+            Some(("timer.rs".to_string(), 297))
+        );
+        // The bottom frame has no caller to return to.
+        assert_eq!(stack_frames.last().unwrap().return_location(), None);
     }
 
     #[test_case("RP2040"; "Armv6-m using RP2040")]
@@ -2058,6 +3915,9 @@ mod test {
                         registers: &frame.registers,
                         frame_base: frame.frame_base,
                         canonical_frame_address: frame.canonical_frame_address,
+                        thread_local_storage_base: None,
+                        registers_only: false,
+                        frame_torn_down: frame.frame_torn_down,
                     },
                 );
             }
@@ -2067,4 +3927,772 @@ mod test {
         // and also because they provide better diffs.
         insta::assert_yaml_snapshot!(snapshot_name, stack_frames);
     }
+
+    #[test_case("RP2040"; "Armv6-m using RP2040")]
+    #[test_case("nRF52833_xxAA"; "Armv7-m using nRF52833_xxAA")]
+    fn unwind_from_supplied_registers(chip_name: &str) {
+        let debug_info =
+            load_test_elf_as_debug_info(format!("debug-unwind-tests/{chip_name}.elf").as_str());
+        let mut adapter = CoreDump::load(&get_path_for_test_files(
+            format!("debug-unwind-tests/{chip_name}.coredump").as_str(),
+        ))
+        .unwrap();
+        let exception_handler = exception_handler_for_core(adapter.core_type());
+        let instruction_set = adapter.instruction_set();
+        let core_registers = adapter.debug_registers();
+
+        let core_stack_frames = debug_info
+            .unwind(
+                &mut adapter,
+                core_registers.clone(),
+                exception_handler.as_ref(),
+                Some(instruction_set),
+            )
+            .unwrap();
+
+        // Start from the top-of-stack registers of the caller, e.g. like a saved context,
+        // instead of the registers of the core.
+        let caller_registers = &core_stack_frames[1].registers;
+        let mut supplied_registers = core_registers.clone();
+        for role in [
+            RegisterRole::ProgramCounter,
+            RegisterRole::StackPointer,
+            RegisterRole::FramePointer,
+            RegisterRole::ReturnAddress,
+        ] {
+            let value = caller_registers.get_register_by_role(&role).unwrap().value;
+            supplied_registers
+                .set_register_value_by_role(&role, value.unwrap())
+                .unwrap();
+        }
+        assert_ne!(supplied_registers, core_registers);
+
+        let supplied_stack_frames = debug_info
+            .unwind(
+                &mut adapter,
+                supplied_registers,
+                exception_handler.as_ref(),
+                Some(instruction_set),
+            )
+            .unwrap();
+
+        let frame_summary = |frames: &[StackFrame]| {
+            frames
+                .iter()
+                .map(|frame| (frame.function_name.clone(), frame.pc))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            frame_summary(&supplied_stack_frames),
+            frame_summary(&core_stack_frames[1..])
+        );
+    }
+
+    #[test_case("GNU C17 12.2.0 -mtune=generic -march=x86-64 -g -O2", true; "gcc 12")]
+    #[test_case("GNU C++14 8.3.1 20190703 (release)", true; "gcc 8")]
+    #[test_case("GNU C99 7.3.1 20180622 (release)", false; "gcc 7")]
+    #[test_case("GNU AS 2.40", false; "gnu assembler")]
+    #[test_case("clang version 17.0.6", false; "clang")]
+    #[test_case("clang LLVM (rustc version 1.79.0)", false; "rustc")]
+    fn producers_with_empty_statement_views(producer: &str, expected: bool) {
+        assert_eq!(emits_empty_statement_views(producer), expected);
+    }
+
+    #[test]
+    fn statements_of_gcc_location_views() {
+        let debug_info = load_test_elf_as_debug_info("c-optimized/c-optimized.elf");
+        let unit_info = &debug_info.unit_infos[0];
+        assert!(unit_info
+            .get_producer(&debug_info)
+            .unwrap()
+            .starts_with("GNU C17 12."));
+
+        // At the start of `partially_live`, the rows at 0x401010 are for line 13 (statement), line 14 (statement),
+        // and line 13 again, which describes the instruction at that address.
+        // Stepping should not halt at 0x401010 for line 14, before any of its code has run.
+        let statements = SourceStatements::new(&debug_info, unit_info, 0x40_1010)
+            .unwrap()
+            .statements
+            .iter()
+            .take_while(|statement| statement.low_pc() < 0x40_1030)
+            .map(|statement| {
+                (
+                    statement.line.map(std::num::NonZeroU64::get),
+                    statement.low_pc(),
+                    statement.is_stmt,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            statements,
+            vec![
+                (Some(13), 0x40_1010, true),
+                (Some(14), 0x40_1012, false),
+                (Some(15), 0x40_1018, true),
+                (Some(16), 0x40_101D, true),
+                (Some(17), 0x40_101F, false),
+                (Some(16), 0x40_1022, false),
+                (Some(18), 0x40_1027, true),
+            ]
+        );
+    }
+
+    #[test_case("c-types/c-types-dwarf4.elf"; "dwarf4")]
+    #[test_case("c-types/c-types-dwarf5.elf"; "dwarf5")]
+    fn next_line_address_in_loop(elf_file: &str) {
+        let debug_info = load_test_elf_as_debug_info(elf_file);
+
+        // The line table of the loop in `sum_values`:
+        //   81 @ 0x40102D  `for` initialization
+        //   81 @ 0x401034
+        //   82 @ 0x401036  loop body
+        //   82 @ 0x401046
+        //   81 @ 0x401049  `for` increment
+        //   81 @ 0x40104D  `for` condition
+        //   84 @ 0x401055  `return sum`
+
+        // From the initialization, skip the other row of the same line.
+        assert_eq!(debug_info.next_line_address(0x40102D), Some(0x401036));
+        // From the middle of the loop body, the next line is the increment.
+        assert_eq!(debug_info.next_line_address(0x40103A), Some(0x401049));
+        // From the jump back to the start of the endless loop in `_start`, which is on line 89,
+        // the next line is the one after the first run of line 89.
+        assert_eq!(debug_info.next_line_address(0x4010A1), Some(0x401087));
+        // Addresses without debug information.
+        assert_eq!(debug_info.next_line_address(0x0), None);
+    }
+
+    #[test_case("c-types/c-types-dwarf4.elf", 0x401049; "dwarf4 increment")]
+    #[test_case("c-types/c-types-dwarf4.elf", 0x40104D; "dwarf4 condition")]
+    #[test_case("c-types/c-types-dwarf5.elf", 0x401049; "dwarf5 increment")]
+    #[test_case("c-types/c-types-dwarf5.elf", 0x40104D; "dwarf5 condition")]
+    fn next_line_address_from_for_loop_increment(elf_file: &str, address: u64) {
+        let debug_info = load_test_elf_as_debug_info(elf_file);
+
+        // The increment and the condition of the loop in `sum_values` are on line 81, like the initialization,
+        // and the next line in the sequence is the loop body at 0x401036, not `return sum` at 0x401055,
+        // which only follows them in address order.
+        assert_eq!(debug_info.next_line_address(address), Some(0x401036));
+    }
+
+    #[test]
+    fn inline_chain_with_three_nested_inlines() {
+        let debug_info = load_test_elf_as_debug_info("c-optimized/c-optimized.elf");
+
+        // `outermost` -> `middle` -> `innermost` are all inlined into `nested_inlines`,
+        // and only `innermost` covers 0x401034.
+        let call_sites = debug_info
+            .inline_chain_at(0x40_1034)
+            .into_iter()
+            .map(|location| {
+                (
+                    location.file.unwrap(),
+                    location.line.unwrap(),
+                    location.column.unwrap(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            call_sites,
+            [
+                ("c-optimized.c".to_string(), 34, ColumnType::Column(5)),
+                ("c-optimized.c".to_string(), 30, ColumnType::Column(5)),
+                ("c-optimized.c".to_string(), 26, ColumnType::Column(5)),
+            ]
+        );
+
+        // `partially_live` has no inlined functions.
+        assert!(debug_info.inline_chain_at(0x40_1018).is_empty());
+    }
+
+    #[test]
+    fn source_range_of_function() {
+        let debug_info = load_test_elf_as_debug_info("c-optimized/c-optimized.elf");
+
+        // `return_u32` occupies 0x401040..0x40104A, from its opening line 38 to its closing brace on line 40.
+        let function_ranges = debug_info.function_source_ranges(0x40_1046).unwrap();
+        let [function_range] = function_ranges.as_slice() else {
+            panic!("Expected a single range, got {function_ranges:?}");
+        };
+        assert_eq!(
+            (function_range.low_pc, function_range.high_pc),
+            (0x40_1040, 0x40_104A)
+        );
+        assert_eq!(function_range.start.line, Some(38));
+        assert_eq!(function_range.end.line, Some(40));
+        assert_eq!(function_range.start.file.as_deref(), Some("c-optimized.c"));
+
+        // The compilation unit contains the function.
+        let unit_ranges = debug_info.unit_source_ranges(0x40_1046).unwrap();
+        assert!(unit_ranges.iter().any(|unit_range| {
+            unit_range.low_pc <= function_range.low_pc
+                && function_range.high_pc <= unit_range.high_pc
+        }));
+
+        assert!(debug_info.source_range(0x40_1040..0x40_1040).is_none());
+        assert!(debug_info.function_source_ranges(0x10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn line_table_rows_match_between_dwarf_versions() {
+        let dwarf4 = load_test_elf_as_debug_info("c-types/c-types-dwarf4.elf");
+        let dwarf5 = load_test_elf_as_debug_info("c-types/c-types-dwarf5.elf");
+
+        let rows = dwarf5.line_table_rows(0x40_1036).unwrap();
+        // The same source, compiled for both versions, results in the same rows,
+        // even though DWARF 5 numbers the files of the line program from 0 instead of 1.
+        assert_eq!(dwarf4.line_table_rows(0x40_1036).unwrap(), rows);
+
+        let compact_rows = rows
+            .iter()
+            .map(|row| {
+                assert_eq!(row.file.as_deref(), Some("c-types.c"));
+                (row.address, row.line.unwrap(), row.end_sequence)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(compact_rows.len(), 21);
+        assert_eq!(compact_rows[0], (0x40_1000, 73, false));
+        // The loop in `sum_values`.
+        assert_eq!(
+            compact_rows[6..12],
+            [
+                (0x40_102D, 81, false),
+                (0x40_1034, 81, false),
+                (0x40_1036, 82, false),
+                (0x40_1046, 82, false),
+                (0x40_1049, 81, false),
+                (0x40_104D, 81, false),
+            ]
+        );
+        assert_eq!(compact_rows.last(), Some(&(0x40_10A3, 89, true)));
+
+        // Addresses without debug information.
+        assert!(dwarf5.line_table_rows(0x0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn dwarf5_directory_and_file_index_0() {
+        let debug_info =
+            load_test_elf_as_debug_info("c-dwarf5-directories/c-dwarf5-directories.elf");
+        let unit = &debug_info.unit_infos[0].unit;
+        let header = unit.line_program.as_ref().unwrap().header();
+        // The unit has no `DW_AT_comp_dir`, so the compilation directory is only recorded as directory 0 of the line program.
+        assert!(unit.comp_dir.is_none());
+        let compilation_directory = "/root/crate/probe-rs/tests/c-dwarf5-directories";
+
+        let file_and_directory = |file_index: u64| {
+            let (file, directory) = debug_info
+                .find_file_and_directory(unit, header, header.file(file_index).unwrap())
+                .unwrap();
+            (
+                file.unwrap(),
+                directory.unwrap().to_string_lossy().into_owned(),
+            )
+        };
+        // File 0 is the primary source file, in directory 1, which is relative to directory 0.
+        assert_eq!(
+            file_and_directory(0),
+            ("main.c".to_string(), format!("{compilation_directory}/src"))
+        );
+        assert_eq!(file_and_directory(0), file_and_directory(2));
+        assert_eq!(
+            file_and_directory(1),
+            (
+                "scale.h".to_string(),
+                format!("{compilation_directory}/include")
+            )
+        );
+
+        // `_start` in the primary file, and the `scale` function from the header.
+        assert_eq!(
+            debug_info
+                .primary_source_file(0x40_1012)
+                .unwrap()
+                .to_string_lossy(),
+            format!("{compilation_directory}/src/main.c")
+        );
+        assert_eq!(
+            debug_info
+                .get_source_location(0x40_1004)
+                .and_then(|location| location.combined_typed_path())
+                .unwrap()
+                .to_string_lossy(),
+            format!("{compilation_directory}/include/scale.h")
+        );
+    }
+
+    #[test]
+    fn line_has_code() {
+        let debug_info = load_test_elf_as_debug_info("c-optimized/c-optimized.elf");
+
+        let source_path = debug_info
+            .get_source_location(0x40_1018)
+            .and_then(|location| location.combined_typed_path())
+            .unwrap();
+        let translation = SourcePathTranslation::default();
+
+        // The body of `consume`.
+        assert!(debug_info.line_has_code(&source_path, 7, &translation));
+        // The body of `innermost`, which only has code where it is inlined into `nested_inlines`.
+        assert!(debug_info.line_has_code(&source_path, 22, &translation));
+        // A comment, and the declaration of a struct member.
+        assert!(!debug_info.line_has_code(&source_path, 20, &translation));
+        assert!(!debug_info.line_has_code(&source_path, 47, &translation));
+        // Other files.
+        assert!(!debug_info.line_has_code(
+            &TypedPathBuf::from("/does/not/exist.c"),
+            7,
+            &translation
+        ));
+
+        // The source file at a translated path on the host.
+        let source_directory = debug_info
+            .get_source_location(0x40_1018)
+            .unwrap()
+            .directory
+            .unwrap();
+        let translation =
+            SourcePathTranslation::default().with_mapping(source_directory, "/home/user/firmware");
+        let host_path = TypedPathBuf::from("/home/user/firmware/c-optimized.c");
+        assert!(debug_info.line_has_code(&host_path, 7, &translation));
+        assert!(!debug_info.line_has_code(&host_path, 7, &SourcePathTranslation::default()));
+    }
+
+    #[test_case("Nested", &[("nested", 63)]; "qualified struct")]
+    #[test_case("uint32_t", &[("count", 79), ("sum", 80), ("index", 81), ("counter", 74)]; "typedef")]
+    #[test_case("unsigned int", &[("count", 79), ("sum", 80), ("index", 81), ("counter", 74)]; "typedef alias")]
+    #[test_case("uint8_t", &[]; "only pointers and members")]
+    fn variables_of_type(type_name: &str, expected_variables: &[(&str, u64)]) {
+        let debug_info = load_test_elf_as_debug_info("c-types/c-types-dwarf5.elf");
+
+        let variables = debug_info
+            .variables_of_type(type_name)
+            .into_iter()
+            .map(|variable| {
+                let source_location = variable.source_location.unwrap();
+                assert_eq!(source_location.file.as_deref(), Some("c-types.c"));
+                (variable.name.unwrap(), source_location.line.unwrap())
+            })
+            .collect::<Vec<_>>();
+        let expected_variables = expected_variables
+            .iter()
+            .map(|(name, line)| (name.to_string(), *line))
+            .collect::<Vec<_>>();
+        assert_eq!(variables, expected_variables);
+    }
+
+    #[test]
+    fn attribute_samples_to_lines() {
+        let debug_info = load_test_elf_as_debug_info("c-optimized/c-optimized.elf");
+
+        // Line 7 in `consume`, the instructions of line 14, and line 15 in `partially_live`,
+        // `innermost` at line 22, which is inlined into `nested_inlines`, and an address outside of the program.
+        let samples = [
+            0x40_1002, 0x40_1004, 0x40_1002, 0x40_1012, 0x40_1014, 0x40_1016, 0x40_1018, 0x40_1034,
+            0x40_103b, 0x10,
+        ];
+        let counts = debug_info.attribute_samples(&samples);
+
+        let mut line_counts = counts
+            .iter()
+            .map(|(location, count)| {
+                assert_eq!(location.file.as_deref(), Some("c-optimized.c"));
+                (location.line.unwrap(), *count)
+            })
+            .collect::<Vec<_>>();
+        line_counts.sort();
+        assert_eq!(line_counts, [(7, 3), (14, 3), (15, 1), (22, 2)]);
+    }
+
+    #[test]
+    fn resolve_breakpoint_with_remapped_path() {
+        let debug_info = load_test_elf_as_debug_info("c-optimized/c-optimized.elf");
+
+        let target_location = debug_info.get_source_location(0x40_1018).unwrap();
+        let target_path = target_location.combined_typed_path().unwrap();
+        let translation = SourcePathTranslation::default()
+            .with_mapping(target_location.directory.unwrap(), "/home/user/firmware");
+        let editor_path = TypedPathBuf::from("/home/user/firmware/c-optimized.c");
+
+        // The editor path is not in the debug information.
+        assert!(debug_info
+            .resolve_breakpoint(&editor_path, 15, None)
+            .is_err());
+
+        let resolution = debug_info
+            .resolve_breakpoint_with(&editor_path, 15, None, &translation)
+            .unwrap();
+        assert_eq!(
+            resolution.address,
+            debug_info
+                .resolve_breakpoint(&target_path, 15, None)
+                .unwrap()
+                .address
+        );
+        assert_eq!(
+            resolution.source_location.combined_typed_path(),
+            Some(target_path)
+        );
+    }
+
+    #[test]
+    fn resolve_breakpoint_batch() {
+        let debug_info = load_test_elf_as_debug_info("c-optimized/c-optimized.elf");
+
+        let source_path = debug_info
+            .get_source_location(0x40_1018)
+            .and_then(|location| location.combined_typed_path())
+            .unwrap();
+        let request = |path: &TypedPathBuf, line| BreakpointRequest {
+            path: path.clone(),
+            line,
+            column: None,
+        };
+        let resolved = debug_info.resolve_breakpoints(
+            &[
+                request(&source_path, 15),
+                request(&source_path, 10_000),
+                request(&TypedPathBuf::from("/does/not/exist.c"), 15),
+            ],
+            &SourcePathTranslation::default(),
+        );
+
+        assert_eq!(resolved.len(), 3);
+        let resolution = resolved[0].as_ref().unwrap();
+        assert_eq!(resolution.address, 0x40_1018);
+        assert_eq!(resolution.source_location.line, Some(15));
+
+        for unresolved in &resolved[1..] {
+            assert!(unresolved.is_err());
+        }
+    }
+
+    #[test_case(0x40_1008, "c-macros.c", 9; "macro invocation")]
+    #[test_case(0x40_101c, "registers.h", 8; "inside first expansion")]
+    #[test_case(0x40_1021, "c-macros.c", 10; "back at invocation")]
+    #[test_case(0x40_1040, "registers.h", 14; "inside second expansion")]
+    #[test_case(0x40_1070, "c-macros.c", 13; "invocation after expansion")]
+    fn source_location_in_macro_expansion(address: u64, file: &str, line: u64) {
+        let debug_info = load_test_elf_as_debug_info("c-macros/c-macros.elf");
+
+        let source_location = debug_info.get_source_location(address).unwrap();
+        assert_eq!(source_location.file.as_deref(), Some(file));
+        assert_eq!(source_location.line, Some(line));
+
+        let halt_location = debug_info.get_halt_location(address).unwrap();
+        assert_eq!(halt_location.file.as_deref(), Some(file));
+        assert_eq!(halt_location.line, Some(line));
+    }
+
+    #[test]
+    fn breakpoint_in_macro_expansion() {
+        let debug_info = load_test_elf_as_debug_info("c-macros/c-macros.elf");
+        let source_path = |address| {
+            debug_info
+                .get_source_location(address)
+                .and_then(|location| location.combined_typed_path())
+                .unwrap()
+        };
+        let invocation_path = source_path(0x40_1008);
+        let expansion_path = source_path(0x40_101c);
+
+        // Line 9 and 13 exist in both files, and must resolve to the rows of the requested file.
+        let breakpoint = debug_info
+            .resolve_breakpoint(&invocation_path, 9, None)
+            .unwrap();
+        assert_eq!(breakpoint.address, 0x40_1008);
+        assert_eq!(
+            breakpoint.source_location.file.as_deref(),
+            Some("c-macros.c")
+        );
+
+        let breakpoint = debug_info
+            .resolve_breakpoint(&invocation_path, 13, None)
+            .unwrap();
+        assert_eq!(breakpoint.address, 0x40_1070);
+        assert_eq!(
+            breakpoint.source_location.file.as_deref(),
+            Some("c-macros.c")
+        );
+
+        let breakpoint = debug_info
+            .resolve_breakpoint(&expansion_path, 13, None)
+            .unwrap();
+        assert_eq!(breakpoint.address, 0x40_1034);
+        assert_eq!(breakpoint.alternatives, vec![0x40_107e]);
+        assert_eq!(
+            breakpoint.source_location.file.as_deref(),
+            Some("registers.h")
+        );
+        assert_eq!(breakpoint.source_location.line, Some(13));
+    }
+
+    #[test]
+    fn halt_location_after_breakpoint() {
+        let debug_info = load_test_elf_as_debug_info("c-optimized/c-optimized.elf");
+
+        let source_path = debug_info
+            .get_source_location(0x40_1018)
+            .and_then(|location| location.combined_typed_path())
+            .unwrap();
+        let breakpoint = debug_info
+            .get_breakpoint_location(&source_path, 15, None, &SourcePathTranslation::default())
+            .unwrap();
+        assert_eq!(breakpoint.address, 0x40_1018);
+
+        // The core halts inside the statement, after the requested address.
+        let halt_location = debug_info.get_halt_location(0x40_101a).unwrap();
+        assert_eq!(halt_location.file.as_deref(), Some("c-optimized.c"));
+        assert_eq!(halt_location.line, Some(15));
+        assert_eq!(halt_location.low_pc, Some(0x40_1018));
+        assert_eq!(halt_location.high_pc, Some(0x40_101d));
+
+        // At the entry of `nested_inlines`, the innermost inlined line is reported,
+        // rather than the first row at the address.
+        assert_eq!(
+            debug_info.get_source_location(0x40_1030).unwrap().line,
+            Some(33)
+        );
+        let halt_location = debug_info.get_halt_location(0x40_1030).unwrap();
+        assert_eq!(halt_location.line, Some(22));
+        assert_eq!(halt_location.low_pc, Some(0x40_1030));
+        assert_eq!(halt_location.high_pc, Some(0x40_1034));
+    }
+
+    #[test]
+    fn set_next_statement_within_function() {
+        let debug_info = load_test_elf_as_debug_info("c-array-index/c-array-index.elf");
+
+        let source_path = debug_info
+            .get_source_location(0x40_103c)
+            .and_then(|location| location.combined_typed_path())
+            .unwrap();
+        let translation = SourcePathTranslation::default();
+        // Halted at `uint32_t i = selected;` in `select_values`, a jump back to the initialization of `values`.
+        assert_eq!(
+            debug_info.address_for_set_next_statement(&source_path, 12, 0x40_103c, &translation),
+            Some(0x40_1004)
+        );
+        // The call in `_start` is a statement boundary, but outside the current function.
+        assert_eq!(
+            debug_info.address_for_set_next_statement(&source_path, 20, 0x40_103c, &translation),
+            None
+        );
+        // The same line is a valid target while halted in `_start`.
+        assert_eq!(
+            debug_info.address_for_set_next_statement(&source_path, 20, 0x40_1057, &translation),
+            Some(0x40_105b)
+        );
+    }
+
+    #[test]
+    fn resolve_lvalue_with_computed_index() {
+        let debug_info = load_test_elf_as_debug_info("c-array-index/c-array-index.elf");
+
+        // On the return statement of `select_values`, with `rbp` at 0x2000_00F0, so that the CFA is 0x2000_0100,
+        // `points` at CFA-64, `values` at CFA-48, and `i` at CFA-20.
+        let program_counter = 0x40_1045;
+        let registers = x86_64_registers(|id| match id {
+            RBP => 0x2000_00F0,
+            RSP => 0x2000_00F0,
+            RIP => program_counter,
+            _ => 0,
+        });
+        let mut memory = MockMemory::new();
+        memory.add_word_range(
+            0x2000_00C0,
+            &[1, 2, 3, 4, 10, 20, 30, 40, 0, 0, 0, 1, 0, 0, 0, 0],
+        );
+        let mut frame = debug_info
+            .get_stackframe_info(
+                &mut memory,
+                program_counter,
+                &mut gimli::UnwindContext::new(),
+                &registers,
+            )
+            .unwrap()
+            .pop()
+            .unwrap();
+
+        for (expression, value) in [
+            ("values[i]", "20"),
+            ("points[i].y", "4"),
+            ("points[ i ].x", "3"),
+            ("values[3]", "40"),
+            ("values[points[0].x]", "20"),
+        ] {
+            let variable = debug_info
+                .resolve_lvalue(&mut memory, &mut frame, expression)
+                .unwrap();
+            let local_variables = frame.local_variables.as_ref().unwrap();
+            assert_eq!(variable.get_value(local_variables), value, "{expression}");
+        }
+
+        for (expression, error) in [
+            (
+                "values[4]",
+                "The index 4 is out of bounds for `values`, which has 4 elements at the end of `values[4]`.",
+            ),
+            (
+                "points[i].z",
+                "`points[1]` has no member `z` at the end of `points[i].z`.",
+            ),
+            ("i[0]", "`i` is not an array at the end of `i[0]`."),
+            // Only the variables in scope are resolved by name, not their members.
+            ("x", "`x` is not a variable in scope at the end of `x`."),
+            (
+                "count[i]",
+                "`count` is not a variable in scope in `count[i]`, at `[i]`.",
+            ),
+            ("values[i", "Expected `]` at the end of `values[i`."),
+        ] {
+            let resolved = debug_info.resolve_lvalue(&mut memory, &mut frame, expression);
+            assert_eq!(resolved.unwrap_err().to_string(), error, "{expression}");
+        }
+    }
+
+    #[test]
+    fn receiver_member_without_qualification() {
+        let debug_info = load_test_elf_as_debug_info("cpp-artificial/cpp-artificial.elf");
+
+        // The stack frame at the given program counter, with `rbp` at 0x2000_1000, so that the frame base is 0x2000_1010.
+        let stack_frame = |program_counter: u64, memory: &mut MockMemory| {
+            let registers = x86_64_registers(|id| match id {
+                RBP => 0x2000_1000,
+                RSP => 0x2000_0FB8,
+                RIP => program_counter,
+                _ => 0,
+            });
+            debug_info
+                .get_stackframe_info(
+                    memory,
+                    program_counter,
+                    &mut gimli::UnwindContext::new(),
+                    &registers,
+                )
+                .unwrap()
+                .pop()
+                .unwrap()
+        };
+
+        // Inside `Counter::sum`, where `this`, and the other pointers in its frame refer to `counter` at 0x2000_0000.
+        let mut memory = MockMemory::new();
+        memory.add_word_range(0x2000_0000, &[1, 2, 3]);
+        memory.add_range(0x2000_1010 - 72, 0x2000_0000_u64.to_le_bytes().repeat(9));
+        let mut frame = stack_frame(0x40_1049, &mut memory);
+
+        let values = debug_info
+            .receiver_member(&mut memory, &mut frame, "values")
+            .unwrap();
+        assert_eq!(
+            values.type_name,
+            VariableType::Array {
+                item_type_name: "int".to_string(),
+                count: 3
+            }
+        );
+        assert_eq!(
+            values.memory_location,
+            VariableLocation::Address(0x2000_0000)
+        );
+
+        let error = debug_info
+            .receiver_member(&mut memory, &mut frame, "count")
+            .unwrap_err();
+        assert!(error.to_string().ends_with("has no member `count`."));
+
+        // In an lvalue expression, a name that is not a local variable is a member of the receiver.
+        let element = debug_info
+            .resolve_lvalue(&mut memory, &mut frame, "values[1]")
+            .unwrap();
+        assert_eq!(element.type_name, VariableType::Base("int".to_string()));
+        assert_eq!(
+            element.memory_location,
+            VariableLocation::Address(0x2000_0004)
+        );
+        let error = debug_info
+            .resolve_lvalue(&mut memory, &mut frame, "count")
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "`count` is not a variable in scope at the end of `count`."
+        );
+
+        // `_start` is not a method.
+        let mut frame = stack_frame(0x40_1004, &mut memory);
+        let error = debug_info
+            .receiver_member(&mut memory, &mut frame, "values")
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Unable to resolve `values`, because `_start` is not a method with a `this` or `self` receiver."
+        );
+    }
+
+    #[test]
+    fn progress_is_reported_for_each_unit() {
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let recorded_events = events.clone();
+
+        let mut options = DebugInfoOptions::new();
+        options.progress = Some(DebugInfoProgress::new(move |processed, total| {
+            recorded_events.borrow_mut().push((processed, total));
+        }));
+
+        let debug_info = DebugInfo::from_file_with_options(
+            get_path_for_test_files("c-opaque-types/c-opaque-types.elf"),
+            options,
+        )
+        .unwrap();
+
+        let events = events.borrow();
+        let total_units = debug_info.unit_infos.len();
+        assert!(total_units > 1);
+        assert_eq!(
+            *events,
+            (1..=total_units)
+                .map(|processed| (processed, total_units))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test_case(0x40_1036, 0x40_103c, true; "same range")]
+    #[test_case(0x40_1036, 0x40_100f, true; "hot and cold ranges")]
+    #[test_case(0x40_1014, 0x40_1000, false; "cold range and callee")]
+    #[test_case(0x40_1030, 0x40_104d, false; "caller")]
+    #[test_case(0x40_1030, 0x50_0000, false; "outside of functions")]
+    fn same_function_across_cold_split(address: u64, other_address: u64, expected: bool) {
+        let debug_info = load_test_elf_as_debug_info("c-cold-split/c-cold-split.elf");
+
+        // `checked_double` covers 0x401030..0x401040, and its cold part `checked_double.cold` 0x40100f..0x401026.
+        assert_eq!(debug_info.same_function(address, other_address), expected);
+        assert_eq!(debug_info.same_function(other_address, address), expected);
+    }
+
+    #[test]
+    fn variable_at_interior_address() {
+        let elf_path = get_path_for_test_files("c-types/c-types-dwarf5.elf");
+        let debug_info = DebugInfo::from_file(&elf_path).unwrap();
+        let mut memory = SectionMemory::from_file(&elf_path).unwrap();
+        let registers = x86_64_registers(|id| if id == RIP { 0x40_1000 } else { 0 });
+
+        // `nested` is at 0x402000, with `origin.y` at offset 4.
+        let variable = debug_info
+            .variable_at_address(&mut memory, &registers, 0x40_2005, None)
+            .unwrap();
+        assert_eq!(variable.name, VariableName::Named("y".to_string()));
+        assert_eq!(variable.memory_range(), Some(0x40_2004..0x40_2006));
+        assert_eq!(variable.get_value(&VariableCache::new_svd_cache()), "3");
+
+        // The `crc` member of `tx_frame`, which follows its 256 byte `data` array.
+        let variable = debug_info
+            .variable_at_address(&mut memory, &registers, 0x40_2242, None)
+            .unwrap();
+        assert_eq!(variable.name, VariableName::Named("crc".to_string()));
+
+        // No variable is stored in the code.
+        assert!(debug_info
+            .variable_at_address(&mut memory, &registers, 0x40_1000, None)
+            .is_none());
+    }
 }
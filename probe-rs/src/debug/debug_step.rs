@@ -1,7 +1,9 @@
 use super::{
     debug_info::DebugInfo,
+    format_address,
+    return_value::float_return_registers,
     source_statement::SourceStatements,
-    {DebugError, SourceLocation},
+    {DebugError, DebugRegisters, SourceLocation, VariableCache},
 };
 use crate::{
     architecture::{arm::ArmError, riscv::communication_interface::RiscvError},
@@ -56,6 +58,7 @@ impl SteppingMode {
             }
         };
         let origin_program_counter = program_counter;
+        let address_size = core.program_counter().size_in_bytes() as u8;
         let mut return_address = core.read_core_reg(core.return_address().id())?.try_into()?;
 
         // Sometimes the target program_counter is at a location where the debug_info program row data does not contain valid statements for halt points.
@@ -94,8 +97,8 @@ impl SteppingMode {
                     } => {
                         // Step on target instruction, and then try again.
                         tracing::trace!(
-                            "Incomplete stepping information @{:#010X}: {}",
-                            pc_at_error,
+                            "Incomplete stepping information @{}: {}",
+                            format_address(pc_at_error, address_size),
                             message
                         );
                         program_counter = core.step()?.pc;
@@ -118,7 +121,7 @@ impl SteppingMode {
         (core_status, program_counter) = match target_address {
             Some(target_address) => {
                 tracing::debug!(
-                    "Preparing to step ({:20?}): \n\tfrom: {:?} @ {:#010X} \n\t  to: {:?} @ {:#010X}",
+                    "Preparing to step ({:20?}): \n\tfrom: {:?} @ {} \n\t  to: {:?} @ {}",
                     self,
                     debug_info
                         .get_source_location(program_counter)
@@ -127,7 +130,7 @@ impl SteppingMode {
                             source_location.line,
                             source_location.column
                         )),
-                    origin_program_counter,
+                    format_address(origin_program_counter, address_size),
                     debug_info
                         .get_source_location(target_address)
                         .map(|source_location| (
@@ -135,7 +138,7 @@ impl SteppingMode {
                             source_location.line,
                             source_location.column
                         )),
-                    target_address,
+                    format_address(target_address, address_size),
                 );
 
                 run_to_address(program_counter, target_address, core)?
@@ -151,6 +154,51 @@ impl SteppingMode {
         Ok((core_status, program_counter))
     }
 
+    /// Step out of the current function, like [`SteppingMode::OutOfStatement`],
+    /// and decode the value it returned with [`DebugInfo::function_return_value`].
+    ///
+    /// A value that is too large for the return registers is written to a buffer, of which the caller passes
+    /// the address as a hidden first argument. That address is only known when stepping out from the entry of the function,
+    /// where the argument is still in its register, or if the function also returns it, e.g. in `rax` on x86-64.
+    ///
+    /// Return the new CoreStatus and program_counter value, and the return value of the function,
+    /// if it has one and it could be decoded.
+    pub fn step_out_with_return_value(
+        core: &mut impl CoreInterface,
+        debug_info: &DebugInfo,
+    ) -> Result<(CoreStatus, u64, Option<VariableCache>), DebugError> {
+        let function_address: u64 = core
+            .read_core_reg(core.program_counter().id())?
+            .try_into()?;
+        let return_buffer_address = match core.registers().get_argument_register(0) {
+            Some(argument_register) if debug_info.is_function_entry(function_address) => {
+                Some(core.read_core_reg(argument_register.id())?.try_into()?)
+            }
+            _ => None,
+        };
+
+        let (core_status, program_counter) = SteppingMode::OutOfStatement.step(core, debug_info)?;
+
+        let mut registers = DebugRegisters::from_core(core);
+        registers
+            .0
+            .extend(float_return_registers(core, debug_info.return_value_abi));
+        let return_value = match debug_info.function_return_value(
+            function_address,
+            &registers,
+            return_buffer_address,
+            core,
+        ) {
+            Ok(return_value) => return_value,
+            Err(error) => {
+                tracing::warn!("Unable to decode the return value: {}", error);
+                None
+            }
+        };
+
+        Ok((core_status, program_counter, return_value))
+    }
+
     /// To understand how this method works, use the following framework:
     /// - Everything is calculated from a given machine instruction address, usually the current program counter.
     /// - To calculate where the user might step to (step-over, step-into, step-out), we start from the given instruction address/program counter, and work our way through all the rows in the sequence of instructions it is part of.
@@ -219,9 +267,9 @@ impl SteppingMode {
                                                 column: Some(source_statement.column.into()),
                                                 file,
                                                 directory,
-                                                low_pc: Some(source_statement.low_pc() as u32),
+                                                low_pc: Some(source_statement.low_pc()),
                                                 high_pc: Some(
-                                                    source_statement.instruction_range.end as u32,
+                                                    source_statement.instruction_range.end,
                                                 ),
                                             })
                                     })
@@ -384,6 +432,7 @@ fn run_to_address(
     target_address: u64,
     core: &mut impl CoreInterface,
 ) -> Result<(CoreStatus, u64), DebugError> {
+    let address_size = core.program_counter().size_in_bytes() as u8;
     Ok(if target_address < program_counter {
         // We are not able to calculate a step_out_address. Notify the user to try something else.
         return Err(DebugError::NoValidHaltLocation {
@@ -421,17 +470,17 @@ fn run_to_address(
                 ) {
                     // This is not a quick step and halt operation. Notify the user that we are not going to wait any longer, and then return the current program counter so that the debugger can show the user where the forced halt happened.
                     tracing::error!(
-                        "The core did not halt after stepping to {:#010X}. Forced a halt at {:#010X}. Long running operations between debug steps are not currently supported.",
-                        target_address,
-                        program_counter
+                        "The core did not halt after stepping to {}. Forced a halt at {}. Long running operations between debug steps are not currently supported.",
+                        format_address(target_address, address_size),
+                        format_address(program_counter, address_size)
                     );
                     (core.status()?, program_counter)
                 } else {
                     // Something else is wrong.
                     return Err(DebugError::Other(anyhow::anyhow!(
-                        "Unexpected error while waiting for the core to halt after stepping to {:#010X}. Forced a halt at {:#010X}. {:?}.",
-                        program_counter,
-                        target_address,
+                        "Unexpected error while waiting for the core to halt after stepping to {}. Forced a halt at {}. {:?}.",
+                        format_address(target_address, address_size),
+                        format_address(program_counter, address_size),
                         error
                     )));
                 }
@@ -480,7 +529,7 @@ fn step_to_address(
 }
 
 /// Find the compile unit at the current address.
-fn get_compile_unit_info(
+pub(crate) fn get_compile_unit_info(
     debug_info: &DebugInfo,
     program_counter: u64,
 ) -> Result<&super::unit_info::UnitInfo, DebugError> {
@@ -2,9 +2,10 @@ use crate::{debug::stack_frame::StackFrameInfo, MemoryInterface};
 
 use super::{
     debug_info, extract_file,
-    unit_info::{ExpressionResult, UnitInfo},
+    unit_info::{value_in_pieces, ExpressionResult, UnitInfo},
     ColumnType, DebugError, SourceLocation, VariableLocation,
 };
+use typed_path::TypedPathBuf;
 
 pub(crate) type Die<'abbrev, 'unit> =
     gimli::DebuggingInformationEntry<'abbrev, 'unit, debug_info::GimliReader, usize>;
@@ -90,6 +91,17 @@ impl<'debugunit, 'abbrev, 'unit: 'debugunit, 'unit_info> FunctionDie<'abbrev, 'u
         }
     }
 
+    /// Get the source file that declares this function, from its `DW_AT_decl_file`.
+    ///
+    /// For an inlined function, this is the file of the inlined function, not of its caller.
+    pub(crate) fn decl_file(&self, debug_info: &super::DebugInfo) -> Option<TypedPathBuf> {
+        let file_name_attr = self.attribute(gimli::DW_AT_decl_file)?;
+
+        let (directory, file) =
+            extract_file(debug_info, &self.unit_info.unit, file_name_attr.value())?;
+        Some(directory.join(file))
+    }
+
     /// Get the call site of an inlined function.
     ///
     /// If this function is not inlined (`is_inline()` returns false),
@@ -121,8 +133,8 @@ impl<'debugunit, 'abbrev, 'unit: 'debugunit, 'unit_info> FunctionDie<'abbrev, 'u
             column,
             file: Some(file),
             directory: Some(directory),
-            low_pc: Some(self.low_pc as u32),
-            high_pc: Some(self.high_pc as u32),
+            low_pc: Some(self.low_pc),
+            high_pc: Some(self.high_pc),
         })
     }
 
@@ -187,10 +199,40 @@ impl<'debugunit, 'abbrev, 'unit: 'debugunit, 'unit_info> FunctionDie<'abbrev, 'u
             &self.function_die,
             &VariableLocation::Unknown,
             memory,
-            frame_info,
+            // The frame base is calculated from the registers, and is the address of the frame in memory,
+            // even if only the variables that are in registers should be resolved.
+            StackFrameInfo {
+                registers_only: false,
+                ..frame_info
+            },
         )? {
             ExpressionResult::Location(VariableLocation::Address(address)) => Ok(Some(address)),
+            // A frame base in a register, e.g. `DW_OP_reg6`, is the address in that register.
+            ExpressionResult::Location(VariableLocation::Pieces(pieces)) => Ok(value_in_pieces(
+                &pieces,
+                0,
+                self.unit_info.unit.header.address_size() as u64,
+                debug_info.endianness,
+            )
+            .filter(|&address| memory.supports_native_64bit_access() || address < u32::MAX as u64)),
             _ => Ok(None),
         }
     }
+
+    /// Check if the frame base of this function is relative to a register, e.g. the stack pointer or frame pointer,
+    /// rather than to the canonical frame address (`DW_OP_call_frame_cfa`).
+    pub(crate) fn has_register_frame_base(&self) -> Result<bool, DebugError> {
+        let Some(gimli::AttributeValue::Exprloc(expression)) =
+            self.function_die.attr_value(gimli::DW_AT_frame_base)?
+        else {
+            return Ok(false);
+        };
+
+        self.unit_info.has_operation(&expression, |operation| {
+            matches!(
+                operation,
+                gimli::Operation::Register { .. } | gimli::Operation::RegisterOffset { .. }
+            )
+        })
+    }
 }
@@ -32,18 +32,26 @@ impl ProgrammingLanguage for C {
                     VariableValue::Valid,
                 ),
 
-                "unsigned char" | "unsigned int" | "short unsigned int" | "long unsigned int" => {
-                    read_unsigned_int(variable, memory).map_or_else(
-                        |err| VariableValue::Error(format!("{err:?}")),
-                        VariableValue::Valid,
-                    )
-                }
-                "signed char" | "int" | "short int" | "long int" | "signed int"
-                | "short signed int" | "long signed int" => read_signed_int(variable, memory)
-                    .map_or_else(
-                        |err| VariableValue::Error(format!("{err:?}")),
-                        VariableValue::Valid,
-                    ),
+                "unsigned char"
+                | "unsigned int"
+                | "short unsigned int"
+                | "long unsigned int"
+                | "long long unsigned int" => read_unsigned_int(variable, memory).map_or_else(
+                    |err| VariableValue::Error(format!("{err:?}")),
+                    VariableValue::Valid,
+                ),
+                "signed char"
+                | "int"
+                | "short int"
+                | "long int"
+                | "long long int"
+                | "signed int"
+                | "short signed int"
+                | "long signed int"
+                | "long long signed int" => read_signed_int(variable, memory).map_or_else(
+                    |err| VariableValue::Error(format!("{err:?}")),
+                    VariableValue::Valid,
+                ),
 
                 "float" => match variable.byte_size {
                     Some(4) | None => read_f32(variable, memory).map_or_else(
@@ -126,12 +134,50 @@ fn print_arbitrary_length(is_signed: bool, num: &mut [u8]) -> String {
     out
 }
 
+/// Read the little endian bytes of an integer variable, which may be a bitfield.
+fn read_int_bytes(
+    variable: &Variable,
+    memory: &mut dyn MemoryInterface,
+    is_signed: bool,
+) -> Result<Vec<u8>, DebugError> {
+    let byte_size = variable.byte_size.unwrap_or(1) as usize;
+    let address = variable.memory_location.memory_address()?;
+
+    let Some(bit_field) = variable.bit_field else {
+        let mut buff = vec![0u8; byte_size];
+        memory.read(address, &mut buff)?;
+        return Ok(buff);
+    };
+
+    // The bits of the field may span more bytes than the type of the field, e.g. a 64-bit field
+    // that doesn't start at a byte boundary, so we assemble them in a 128-bit integer.
+    let bit_end = bit_field.bit_offset + bit_field.bit_size;
+    if bit_field.bit_size == 0 || bit_field.bit_size > 64 || bit_end > 128 {
+        return Err(DebugError::Other(anyhow::anyhow!(
+            "Unsupported bitfield of {} bits at bit offset {}",
+            bit_field.bit_size,
+            bit_field.bit_offset
+        )));
+    }
+    let mut buff = [0u8; 16];
+    memory.read(address, &mut buff[..bit_end.div_ceil(8) as usize])?;
+
+    let unused_bits = 128 - bit_field.bit_size as u32;
+    let value = u128::from_le_bytes(buff) << (unused_bits - bit_field.bit_offset as u32);
+    let value = if is_signed {
+        ((value as i128) >> unused_bits) as u128
+    } else {
+        value >> unused_bits
+    };
+
+    Ok(value.to_le_bytes()[..byte_size.clamp(1, 16)].to_vec())
+}
+
 fn read_unsigned_int(
     variable: &Variable,
     memory: &mut dyn MemoryInterface,
 ) -> Result<String, DebugError> {
-    let mut buff = vec![0u8; variable.byte_size.unwrap_or(1) as usize];
-    memory.read(variable.memory_location.memory_address()?, &mut buff)?;
+    let mut buff = read_int_bytes(variable, memory, false)?;
 
     Ok(print_arbitrary_length(false, &mut buff))
 }
@@ -140,8 +186,7 @@ fn read_signed_int(
     variable: &Variable,
     memory: &mut dyn MemoryInterface,
 ) -> Result<String, DebugError> {
-    let mut buff = vec![0u8; variable.byte_size.unwrap_or(1) as usize];
-    memory.read(variable.memory_location.memory_address()?, &mut buff)?;
+    let mut buff = read_int_bytes(variable, memory, true)?;
 
     Ok(print_arbitrary_length(true, &mut buff))
 }
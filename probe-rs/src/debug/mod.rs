@@ -15,10 +15,16 @@ pub mod function_die;
 pub(crate) mod language;
 /// Target Register definitions, expanded from [`crate::core::registers::CoreRegister`] to include unwind specific information.
 pub mod registers;
+/// Decoding the value returned by a function from the return registers.
+pub(crate) mod return_value;
+/// Target memory from the allocated sections of an ELF file, to inspect statics without a target.
+pub mod section_memory;
 /// The source statement information used while identifying haltpoints for debug stepping and breakpoints.
 pub(crate) mod source_statement;
 /// The stack frame information used while unwinding the stack from a specific program counter.
 pub mod stack_frame;
+/// Type information, e.g. the members of a type, that can be inspected without reading target memory.
+pub mod type_info;
 /// Information about a Unit in the debug information.
 pub mod unit_info;
 /// Variable information used during debug.
@@ -27,7 +33,13 @@ pub mod variable;
 pub mod variable_cache;
 
 pub use self::{
-    debug_info::*, debug_step::SteppingMode, registers::*, stack_frame::StackFrame, variable::*,
+    debug_info::*,
+    debug_step::SteppingMode,
+    registers::*,
+    section_memory::SectionMemory,
+    stack_frame::{FoldedStackOrder, StackFrame},
+    type_info::{BitFieldLayout, MemberInfo, TypeInfo},
+    variable::*,
     variable_cache::VariableCache,
 };
 use crate::{core::Core, MemoryInterface};
@@ -73,7 +85,7 @@ pub enum DebugError {
     IntConversion(#[from] std::num::TryFromIntError),
     /// Errors encountered while determining valid halt locations for breakpoints and stepping.
     /// These are distinct from other errors because they terminate the current step, and result in a user message, but they do not interrupt the rest of the debug session.
-    #[error("{message}  @program_counter={:#010X}.", pc_at_error)]
+    #[error("{message}  @program_counter={}.", format_address(*.pc_at_error, 4))]
     NoValidHaltLocation {
         /// A message that can be displayed to the user to help them make an informed recovery choice.
         message: String,
@@ -94,7 +106,7 @@ pub enum DebugError {
 }
 
 /// A copy of [`gimli::ColumnType`] which uses [`u64`] instead of [`NonZeroU64`](std::num::NonZeroU64).
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize)]
 pub enum ColumnType {
     /// The `LeftEdge` means that the statement begins at the start of the new line.
     LeftEdge,
@@ -171,6 +183,12 @@ pub fn get_object_reference() -> ObjectRef {
     ObjectRef::Valid(NonZeroU32::new(key).unwrap())
 }
 
+/// Format `address` in hexadecimal, zero padded to the width of an address of `address_size` bytes on the target,
+/// e.g. `0x20000100` for a 32-bit target, and `0x0000000020000100` for a 64-bit target.
+pub fn format_address(address: u64, address_size: u8) -> String {
+    format!("{address:#0width$X}", width = address_size as usize * 2 + 2)
+}
+
 fn serialize_typed_path<S>(path: &Option<TypedPathBuf>, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: serde::Serializer,
@@ -194,18 +212,43 @@ pub struct SourceLocation {
     #[serde(serialize_with = "serialize_typed_path")]
     pub directory: Option<TypedPathBuf>,
     /// The address of the first instruction associated with the source code
-    pub low_pc: Option<u32>,
+    pub low_pc: Option<u64>,
     /// The address of the first location past the last instruction associated with the source code
-    pub high_pc: Option<u32>,
+    pub high_pc: Option<u64>,
+}
+
+// `TypedPathBuf` does not implement `Hash`, so hash the bytes of the directory instead.
+impl std::hash::Hash for SourceLocation {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.line.hash(state);
+        self.column.hash(state);
+        self.file.hash(state);
+        self.directory
+            .as_ref()
+            .map(|path| path.as_bytes())
+            .hash(state);
+        self.low_pc.hash(state);
+        self.high_pc.hash(state);
+    }
 }
 
 impl SourceLocation {
     /// The full path of the source file, combining the `directory` and `file` fields.
     /// If the path does not resolve to an existing file, an error is returned.
     pub fn combined_path(&self) -> Result<PathBuf, DebugError> {
+        self.combined_path_with(&SourcePathTranslation::default())
+    }
+
+    /// The full path of the source file on the host, combining the `directory` and `file` fields,
+    /// and translating the resulting target path with `translation`.
+    /// If the path does not resolve to an existing file, an error is returned.
+    pub fn combined_path_with(
+        &self,
+        translation: &SourcePathTranslation,
+    ) -> Result<PathBuf, DebugError> {
         let combined_path = self.combined_typed_path();
 
-        if let Some(native_path) = combined_path.and_then(|p| PathBuf::try_from(p).ok()) {
+        if let Some(native_path) = combined_path.and_then(|p| translation.to_host_path(&p)) {
             if native_path.exists() {
                 return Ok(native_path);
             }
@@ -227,6 +270,150 @@ impl SourceLocation {
 
         combined_path
     }
+
+    /// Test if `other` refers to the same source statement as `self`.
+    ///
+    /// The rules are:
+    /// - The `directory` and `file` must be equal.
+    /// - The `line` must be equal, and known (`Some`) for both locations.
+    /// - The `column` is only compared when it is known (`Some`) for both locations.
+    ///   [`ColumnType::LeftEdge`] is a known column, and is different from any [`ColumnType::Column`].
+    /// - The instruction addresses (`low_pc` and `high_pc`) are ignored,
+    ///   because the instructions of a single statement are not necessarily contiguous.
+    pub fn same_statement_as(&self, other: &SourceLocation) -> bool {
+        let same_column = match (self.column, other.column) {
+            (Some(column), Some(other_column)) => column == other_column,
+            _ => true,
+        };
+
+        self.line.is_some()
+            && self.line == other.line
+            && same_column
+            && self.file == other.file
+            && self.directory == other.directory
+    }
+}
+
+/// A range of instruction addresses, with the source locations of its first and last instruction,
+/// e.g. to highlight the source code of a function.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SourceRange {
+    /// The address of the first instruction in the range.
+    pub low_pc: u64,
+    /// The address of the first location past the last instruction in the range.
+    pub high_pc: u64,
+    /// The source location of the first instruction in the range.
+    pub start: SourceLocation,
+    /// The source location of the last instruction in the range.
+    pub end: SourceLocation,
+}
+
+/// A row of the line number program of a compilation unit, as decoded from the debug information,
+/// e.g. to diagnose source locations that don't match the instructions.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct LineRow {
+    /// The address of the instruction.
+    pub address: u64,
+    /// The file name of the source file.
+    pub file: Option<String>,
+    /// The directory of the source file.
+    #[serde(serialize_with = "serialize_typed_path")]
+    pub directory: Option<TypedPathBuf>,
+    /// The line number in the source file, or `None` if the instruction is not attributed to a line.
+    pub line: Option<u64>,
+    /// The column number in the source file.
+    pub column: ColumnType,
+    /// The instruction is a recommended breakpoint location (`is_stmt`).
+    pub is_stmt: bool,
+    /// The row marks the first address past the end of a sequence of instructions.
+    pub end_sequence: bool,
+}
+
+/// Translates source file paths, as recorded in the debug information, to paths on the host.
+///
+/// The debug information keeps the path style of the machine that built the binary,
+/// which is not necessarily the same as the host running the debugger, e.g.
+/// `C:\work\firmware\src\main.c` for a binary built on Windows.
+/// Paths are first matched against the configured prefix mappings, and otherwise
+/// converted component by component to the host path style.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SourcePathTranslation {
+    mappings: Vec<(TypedPathBuf, PathBuf)>,
+}
+
+impl SourcePathTranslation {
+    /// Map target paths starting with `target_prefix` to the same relative path below `host_prefix`.
+    ///
+    /// Mappings are tried in the order they were added.
+    pub fn with_mapping(
+        mut self,
+        target_prefix: impl Into<TypedPathBuf>,
+        host_prefix: impl Into<PathBuf>,
+    ) -> Self {
+        self.mappings
+            .push((target_prefix.into(), host_prefix.into()));
+        self
+    }
+
+    /// Translate a target path to a host path.
+    ///
+    /// Returns `None` if the path cannot be represented on the host, e.g. because it is not valid UTF-8.
+    pub fn to_host_path(&self, target_path: &TypedPathBuf) -> Option<PathBuf> {
+        for (target_prefix, host_prefix) in &self.mappings {
+            // Compare in the path style of the target path, so that `C:\work` matches `C:/work`.
+            let target_prefix = if target_path.is_windows() {
+                target_prefix.with_windows_encoding()
+            } else {
+                target_prefix.with_unix_encoding()
+            };
+
+            if let Ok(relative_path) = target_path.strip_prefix(target_prefix.as_bytes()) {
+                return append_components(host_prefix.clone(), &relative_path.to_path_buf());
+            }
+        }
+
+        append_components(PathBuf::new(), target_path)
+    }
+
+    /// Translate a host path, e.g. of a source file that is open in an editor, back to the target path
+    /// in the debug information, by applying the first mapping with a matching host prefix in reverse.
+    ///
+    /// Returns `None` if none of the mappings apply.
+    pub fn to_target_path(&self, host_path: &TypedPathBuf) -> Option<TypedPathBuf> {
+        for (target_prefix, host_prefix) in &self.mappings {
+            let Some(host_prefix) = host_prefix.to_str() else {
+                continue;
+            };
+
+            if let Ok(relative_path) = host_path.strip_prefix(host_prefix) {
+                // Add the components one by one, to use the path separator of the target.
+                let mut target_path = target_prefix.clone();
+                for component in relative_path.components() {
+                    target_path.push(component.as_bytes());
+                }
+                return Some(target_path);
+            }
+        }
+
+        None
+    }
+}
+
+/// Append the components of `target_path` to `host_path`, using the host path separator.
+///
+/// Windows drive prefixes can not be represented on other hosts, and are dropped there,
+/// so that `C:\work\main.c` becomes `/work/main.c` on a Unix host.
+fn append_components(mut host_path: PathBuf, target_path: &TypedPathBuf) -> Option<PathBuf> {
+    for component in target_path.components() {
+        let component = std::str::from_utf8(component.as_bytes()).ok()?;
+        if component == "/" || component == "\\" {
+            host_path.push(std::path::MAIN_SEPARATOR_STR);
+        } else if cfg!(windows) || !component.ends_with(':') {
+            host_path.push(component);
+        }
+    }
+
+    Some(host_path)
 }
 
 /// If file information is available, it returns `Some(directory:PathBuf, file_name:String)`, otherwise `None`.
@@ -268,10 +455,13 @@ fn extract_file(
 fn extract_byte_size(node_die: &DebuggingInformationEntry<GimliReader>) -> Option<u64> {
     match node_die.attr(gimli::DW_AT_byte_size) {
         Ok(optional_byte_size_attr) => match optional_byte_size_attr {
-            Some(byte_size_attr) => match byte_size_attr.value() {
-                gimli::AttributeValue::Udata(byte_size) => Some(byte_size),
-                other => {
-                    tracing::warn!("Unimplemented: DW_AT_byte_size value: {:?} ", other);
+            Some(byte_size_attr) => match extract_constant(&byte_size_attr.value()) {
+                Some(byte_size) => Some(byte_size),
+                None => {
+                    tracing::warn!(
+                        "Unimplemented: DW_AT_byte_size value: {:?} ",
+                        byte_size_attr.value()
+                    );
                     None
                 }
             },
@@ -289,10 +479,25 @@ fn extract_byte_size(node_die: &DebuggingInformationEntry<GimliReader>) -> Optio
 }
 
 fn extract_line(attribute_value: gimli::AttributeValue<GimliReader>) -> Option<u64> {
-    match attribute_value {
-        gimli::AttributeValue::Udata(line) => Some(line),
-        _ => None,
-    }
+    extract_constant(&attribute_value)
+}
+
+/// Extract an unsigned constant from any of the constant attribute forms.
+///
+/// This includes the `DW_FORM_data<n>` forms, as well as `DW_FORM_udata`, `DW_FORM_sdata`,
+/// and the DWARF 5 `DW_FORM_implicit_const`, where the value is stored in the abbreviation instead of the DIE
+/// (gimli reports these as [`gimli::AttributeValue::Sdata`]).
+/// Returns `None` for other forms, or for negative values.
+fn extract_constant(attribute_value: &gimli::AttributeValue<GimliReader>) -> Option<u64> {
+    attribute_value.udata_value()
+}
+
+/// Returns `true` if the DIE only declares an entity (`DW_AT_declaration`), which is defined elsewhere.
+fn is_declaration(node_die: &DebuggingInformationEntry<GimliReader>) -> bool {
+    matches!(
+        node_die.attr_value(gimli::DW_AT_declaration),
+        Ok(Some(gimli::AttributeValue::Flag(true)))
+    )
 }
 
 fn extract_name(
@@ -433,3 +638,143 @@ pub(crate) fn _print_all_attributes(
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{ColumnType, SourceLocation, SourcePathTranslation};
+    use std::path::PathBuf;
+    use test_case::test_case;
+    use typed_path::TypedPathBuf;
+
+    fn location(line: Option<u64>, column: Option<ColumnType>) -> SourceLocation {
+        SourceLocation {
+            line,
+            column,
+            file: Some("main.rs".to_string()),
+            directory: Some(TypedPathBuf::from("/home/user/project/src")),
+            low_pc: None,
+            high_pc: None,
+        }
+    }
+
+    #[test_case(Some(ColumnType::Column(5)), Some(ColumnType::Column(5)), true; "same column")]
+    #[test_case(Some(ColumnType::Column(5)), Some(ColumnType::Column(9)), false; "different columns")]
+    #[test_case(Some(ColumnType::LeftEdge), Some(ColumnType::Column(1)), false; "left edge and column")]
+    #[test_case(Some(ColumnType::Column(5)), None, true; "one column unknown")]
+    #[test_case(None, None, true; "both columns unknown")]
+    fn same_statement_on_same_line(
+        column: Option<ColumnType>,
+        other_column: Option<ColumnType>,
+        expected: bool,
+    ) {
+        let first = location(Some(10), column);
+        let second = location(Some(10), other_column);
+        assert_eq!(first.same_statement_as(&second), expected);
+        assert_eq!(second.same_statement_as(&first), expected);
+    }
+
+    #[test]
+    fn same_statement_ignores_addresses() {
+        let first = SourceLocation {
+            low_pc: Some(0x1000),
+            high_pc: Some(0x1004),
+            ..location(Some(10), Some(ColumnType::Column(5)))
+        };
+        let second = SourceLocation {
+            low_pc: Some(0x1010),
+            high_pc: Some(0x1014),
+            ..location(Some(10), Some(ColumnType::Column(5)))
+        };
+        assert!(first.same_statement_as(&second));
+    }
+
+    #[test]
+    fn different_statements() {
+        let first = location(Some(10), Some(ColumnType::Column(5)));
+
+        assert!(!first.same_statement_as(&location(Some(11), Some(ColumnType::Column(5)))));
+        assert!(!first.same_statement_as(&location(None, Some(ColumnType::Column(5)))));
+        assert!(!location(None, None).same_statement_as(&location(None, None)));
+        assert!(!first.same_statement_as(&SourceLocation {
+            file: Some("lib.rs".to_string()),
+            ..first.clone()
+        }));
+        assert!(!first.same_statement_as(&SourceLocation {
+            directory: Some(TypedPathBuf::from("/home/user/other/src")),
+            ..first.clone()
+        }));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn windows_path_without_mapping_on_unix_host() {
+        let translation = SourcePathTranslation::default();
+        let target_path = TypedPathBuf::from("C:\\work\\firmware\\src\\main.c");
+
+        assert_eq!(
+            translation.to_host_path(&target_path),
+            Some(PathBuf::from("/work/firmware/src/main.c"))
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn windows_path_with_mapping_on_unix_host() {
+        let translation = SourcePathTranslation::default()
+            .with_mapping("D:\\other", "/unused")
+            .with_mapping("C:/work/firmware", "/home/user/firmware");
+        let target_path = TypedPathBuf::from("C:\\work\\firmware\\src\\main.c");
+
+        assert_eq!(
+            translation.to_host_path(&target_path),
+            Some(PathBuf::from("/home/user/firmware/src/main.c"))
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn windows_source_location_resolves_on_unix_host() {
+        let host_directory = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests");
+        let translation =
+            SourcePathTranslation::default().with_mapping("C:\\work\\probe-rs", &host_directory);
+        let source_location = SourceLocation {
+            file: Some("c-types.c".to_string()),
+            directory: Some(TypedPathBuf::from("C:\\work\\probe-rs\\c-types")),
+            ..location(Some(10), None)
+        };
+
+        assert!(source_location.combined_path().is_err());
+        assert_eq!(
+            source_location.combined_path_with(&translation).unwrap(),
+            host_directory.join("c-types").join("c-types.c")
+        );
+    }
+
+    #[test]
+    fn host_path_with_mapping_to_windows_target() {
+        let translation = SourcePathTranslation::default()
+            .with_mapping("D:\\other", "/unused")
+            .with_mapping("C:\\work\\firmware", "/home/user/firmware");
+        let host_path = TypedPathBuf::from("/home/user/firmware/src/main.c");
+
+        assert_eq!(
+            translation.to_target_path(&host_path),
+            Some(TypedPathBuf::from("C:\\work\\firmware\\src\\main.c"))
+        );
+        assert_eq!(
+            translation.to_target_path(&TypedPathBuf::from("/home/user/other/main.c")),
+            None
+        );
+    }
+
+    #[test]
+    fn unix_path_is_unchanged_without_mapping() {
+        let translation = SourcePathTranslation::default();
+        let target_path = TypedPathBuf::from("/home/user/project/src/main.rs");
+
+        assert_eq!(
+            translation.to_host_path(&target_path),
+            Some(PathBuf::from("/home/user/project/src/main.rs"))
+        );
+    }
+}
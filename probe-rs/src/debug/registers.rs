@@ -14,6 +14,12 @@ pub struct DebugRegister {
     pub dwarf_id: Option<u16>,
     /// The value of the register is read from the target memory and updated as needed.
     pub value: Option<RegisterValue>,
+    /// How the value of the register was determined while unwinding the stack into the frame of these registers.
+    ///
+    /// This is `None` for the registers of the frame where the unwinding started, which were read from the core,
+    /// and for the registers of frames that were unwound without unwind rules, e.g. exception handlers.
+    #[serde(skip_serializing)]
+    pub origin: Option<RegisterOrigin>,
 }
 
 impl DebugRegister {
@@ -80,6 +86,20 @@ impl DebugRegister {
     }
 }
 
+/// How the value of a register of a calling frame was determined while unwinding the stack,
+/// e.g. to diagnose why a frame has unexpected register values.
+/// See [`DebugRegister::origin`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum RegisterOrigin {
+    /// The value was recovered with the unwind rules of the called frame, e.g. read from where it was saved on the stack,
+    /// or computed from the canonical frame address.
+    Recovered,
+    /// The value was passed through unchanged from the called frame.
+    SameAsCallee,
+    /// The value could not be recovered.
+    Undefined,
+}
+
 /// All the registers required for debug related operations.
 #[derive(Debug, Clone, Default, PartialEq, Serialize)]
 pub struct DebugRegisters(pub Vec<DebugRegister>);
@@ -112,6 +132,7 @@ impl DebugRegisters {
                             None
                         }
                     },
+                    origin: None,
                 });
             } else {
                 tracing::trace!(
@@ -123,6 +144,13 @@ impl DebugRegisters {
         DebugRegisters(debug_registers)
     }
 
+    /// Forget how the registers were unwound, e.g. when they are replaced by the registers saved by an exception handler.
+    pub(crate) fn clear_origins(&mut self) {
+        for register in &mut self.0 {
+            register.origin = None;
+        }
+    }
+
     /// Gets the address size for this target, in bytes
     pub fn get_address_size_bytes(&self) -> usize {
         self.get_program_counter().map_or_else(
@@ -254,6 +282,23 @@ impl DebugRegisters {
             .try_into()
     }
 
+    /// Replace the value of a register by searching against an exact match of the [`RegisterRole`].
+    ///
+    /// This allows an unwind to start from a saved context, e.g. the registers that were stacked when a fault occurred,
+    /// instead of the current registers of the core:
+    /// - Read the current registers with [`DebugRegisters::from_core`].
+    /// - Replace the values of the top-of-stack registers, e.g. [`RegisterRole::ProgramCounter`], [`RegisterRole::StackPointer`],
+    ///   [`RegisterRole::FramePointer`] and [`RegisterRole::ReturnAddress`].
+    /// - Pass the registers to [`crate::debug::DebugInfo::unwind`].
+    pub fn set_register_value_by_role(
+        &mut self,
+        register_role: &RegisterRole,
+        value: RegisterValue,
+    ) -> Result<(), Error> {
+        self.get_register_mut_by_role(register_role)?.value = Some(value);
+        Ok(())
+    }
+
     /// Retrieve a mutable reference to a register by searching against an exact match of the [`RegisterRole`].
     pub fn get_register_mut_by_role(
         &mut self,
@@ -0,0 +1,475 @@
+use super::{
+    extract_byte_size, type_info::resolve_type_modifiers, unit_info::UnitInfo, DebugError,
+    DebugRegister, DebugRegisters, UnavailableReason, VariableLocation, VariablePiece,
+};
+use crate::{core::RegisterRole, CoreInterface};
+use object::{elf, Architecture, FileFlags, Object};
+
+/// How functions return their values, according to the ABI of the ELF file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct ReturnValueAbi {
+    /// The DWARF registers in which a `float` is returned.
+    /// If this is empty, the value is returned in the integer return registers, e.g. with a soft-float ABI.
+    pub(crate) f32_registers: &'static [u16],
+    /// The DWARF registers in which a `double` is returned, like `f32_registers`.
+    pub(crate) f64_registers: &'static [u16],
+    /// Whether a function also returns the address of the buffer for a value that is returned in memory,
+    /// in its first return register, e.g. `rax` on x86-64.
+    pub(crate) returns_buffer_address: bool,
+}
+
+impl ReturnValueAbi {
+    /// The return value ABI of the `object` file, from its architecture and ELF header flags.
+    pub(crate) fn from_object(object: &object::File) -> Self {
+        let e_flags = match object.flags() {
+            FileFlags::Elf { e_flags, .. } => e_flags,
+            _ => 0,
+        };
+        match object.architecture() {
+            // `s0`, and `d0`, which is `s0:s1`.
+            Architecture::Arm if e_flags & elf::EF_ARM_ABI_FLOAT_HARD != 0 => Self {
+                f32_registers: &[64],
+                f64_registers: &[64, 65],
+                returns_buffer_address: false,
+            },
+            // `fa0`, for the floating point types that fit in the floating point registers.
+            Architecture::Riscv32 | Architecture::Riscv64 => {
+                match e_flags & elf::EF_RISCV_FLOAT_ABI {
+                    elf::EF_RISCV_FLOAT_ABI_SINGLE => Self {
+                        f32_registers: &[42],
+                        ..Self::default()
+                    },
+                    elf::EF_RISCV_FLOAT_ABI_DOUBLE | elf::EF_RISCV_FLOAT_ABI_QUAD => Self {
+                        f32_registers: &[42],
+                        f64_registers: &[42],
+                        returns_buffer_address: false,
+                    },
+                    _ => Self::default(),
+                }
+            }
+            // `xmm0`.
+            Architecture::X86_64 => Self {
+                f32_registers: &[17],
+                f64_registers: &[17],
+                returns_buffer_address: true,
+            },
+            _ => Self::default(),
+        }
+    }
+}
+
+/// The location of a value of the type at `type_offset`, that was returned by a function,
+/// with the `registers` immediately after the function returned.
+///
+/// Values that fit in the return registers are located in the pieces of these registers.
+/// Larger values are located in the buffer at `return_buffer_address`, if it is known.
+pub(crate) fn return_value_location(
+    abi: ReturnValueAbi,
+    unit_info: &UnitInfo,
+    type_offset: gimli::UnitOffset,
+    registers: &DebugRegisters,
+    return_buffer_address: Option<u64>,
+) -> Result<VariableLocation, DebugError> {
+    let Some(type_offset) = resolve_type_modifiers(unit_info, type_offset)? else {
+        return Ok(VariableLocation::Unavailable(UnavailableReason::NoLocation));
+    };
+    let type_entry = unit_info.unit.entry(type_offset)?;
+    let byte_size = extract_byte_size(&type_entry);
+
+    let is_float = type_entry.tag() == gimli::DW_TAG_base_type
+        && type_entry.attr_value(gimli::DW_AT_encoding)?
+            == Some(gimli::AttributeValue::Encoding(gimli::DW_ATE_float));
+    let float_registers = match byte_size {
+        Some(4) if is_float => abi.f32_registers,
+        Some(8) if is_float => abi.f64_registers,
+        _ => &[],
+    };
+    let return_registers = if float_registers.is_empty() {
+        registers
+            .0
+            .iter()
+            .filter(|register| {
+                register
+                    .core_register
+                    .roles
+                    .iter()
+                    .any(|role| matches!(role, RegisterRole::Return(_)))
+            })
+            .collect::<Vec<_>>()
+    } else {
+        float_registers
+            .iter()
+            .map(|&dwarf_id| {
+                registers.get_register_by_dwarf_id(dwarf_id).ok_or_else(|| {
+                    DebugError::UnwindIncompleteResults {
+                        message: format!(
+                            "The floating point return register with DWARF number {dwarf_id} is not available"
+                        ),
+                    }
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?
+    };
+    let pieces = register_pieces(&return_registers)?;
+    let register_bytes = pieces.iter().map(|piece| piece.byte_size).sum::<u64>();
+
+    match byte_size {
+        Some(byte_size) if byte_size <= register_bytes => Ok(VariableLocation::Pieces(pieces)),
+        _ => {
+            let return_buffer_address = return_buffer_address.or_else(|| {
+                abi.returns_buffer_address
+                    .then(|| pieces.first().and_then(|piece| piece.value))
+                    .flatten()
+            });
+            Ok(match return_buffer_address {
+                Some(address) => VariableLocation::Address(address),
+                None => VariableLocation::Error(
+                    "The return value is stored in memory, at an unknown address.".to_string(),
+                ),
+            })
+        }
+    }
+}
+
+/// The floating point registers of `core` in which values are returned with the `abi`,
+/// which are not included in [`DebugRegisters::from_core`], with their DWARF register numbers.
+///
+/// Only the `s` registers of ARM cores are supported, because the crate does not define
+/// the floating point registers of the other architectures.
+pub(crate) fn float_return_registers(
+    core: &mut impl CoreInterface,
+    abi: ReturnValueAbi,
+) -> Vec<DebugRegister> {
+    let mut dwarf_ids = abi
+        .f32_registers
+        .iter()
+        .chain(abi.f64_registers)
+        .copied()
+        .collect::<Vec<_>>();
+    dwarf_ids.sort_unstable();
+    dwarf_ids.dedup();
+
+    let mut registers = Vec::new();
+    for dwarf_id in dwarf_ids {
+        // `s0` to `s31` are the DWARF registers 64 to 95.
+        let Some(register_name) = dwarf_id
+            .checked_sub(64)
+            .filter(|index| *index < 32)
+            .map(|index| format!("S{index}"))
+        else {
+            continue;
+        };
+        let Some(core_register) = core.registers().all_registers().find(|core_register| {
+            core_register
+                .roles
+                .iter()
+                .any(|role| matches!(role, RegisterRole::Core(name) if *name == register_name))
+        }) else {
+            continue;
+        };
+        registers.push(DebugRegister {
+            core_register,
+            dwarf_id: Some(dwarf_id),
+            value: core.read_core_reg(core_register.id()).ok(),
+            origin: None,
+        });
+    }
+    registers
+}
+
+/// The pieces of a value that is stored in `registers`, in the order of the registers, e.g. `r0:r1` on ARM.
+fn register_pieces(registers: &[&DebugRegister]) -> Result<Vec<VariablePiece>, DebugError> {
+    let mut pieces = Vec::with_capacity(registers.len());
+    let mut byte_offset = 0;
+    for register in registers {
+        let value: u64 = register
+            .value
+            .ok_or_else(|| DebugError::UnwindIncompleteResults {
+                message: format!(
+                    "No value for return register {}",
+                    register.get_register_name()
+                ),
+            })?
+            .try_into()?;
+        let byte_size = register.core_register.size_in_bytes().min(8) as u64;
+        pieces.push(VariablePiece {
+            byte_offset,
+            byte_size,
+            location: VariableLocation::Value,
+            value: Some(value),
+        });
+        byte_offset += byte_size;
+    }
+    Ok(pieces)
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use super::{float_return_registers, return_value_location};
+    use crate::{
+        architecture::arm::core::registers::cortex_m::CORTEX_M_WITH_FP_CORE_REGISTERS,
+        debug::{
+            extract_name, unit_info::value_in_pieces, DebugRegisters, SteppingMode, VariableCache,
+            VariableLocation,
+        },
+        test::{
+            cortex_m_registers, load_test_elf_as_debug_info,
+            x86_64::{RAX, RDI, RIP, XMM0},
+            x86_64_registers, MockCore, MockMemory,
+        },
+    };
+    use test_case::test_case;
+
+    /// The values of the members of the returned value, or the value itself if it has no members.
+    fn return_values(return_value: &VariableCache) -> Vec<String> {
+        let root_variable = return_value.root_variable();
+        let children = return_value
+            .get_children(root_variable.variable_key())
+            .unwrap();
+        if children.is_empty() {
+            return vec![root_variable.get_value(return_value)];
+        }
+        children
+            .iter()
+            .flat_map(|child| {
+                let members = return_value.get_children(child.variable_key()).unwrap();
+                if members.is_empty() {
+                    vec![format!(
+                        "{} = {}",
+                        child.name,
+                        child.get_value(return_value)
+                    )]
+                } else {
+                    members
+                        .iter()
+                        .map(|member| {
+                            format!("{} = {}", member.name, member.get_value(return_value))
+                        })
+                        .collect()
+                }
+            })
+            .collect()
+    }
+
+    #[test_case(0x40_1040, "305419896"; "u32 in rax")]
+    #[test_case(0x40_1050, "11150031900141442680"; "u64 in rax")]
+    fn function_return_value_from_registers(function_address: u64, expected_value: &str) {
+        let debug_info = load_test_elf_as_debug_info("c-optimized/c-optimized.elf");
+
+        // A `u64` is returned in `rax`.
+        let registers = x86_64_registers(|id| if id == RAX { 0x9ABC_DEF0_1234_5678 } else { 0 });
+
+        let return_value = debug_info
+            .function_return_value(function_address, &registers, None, &mut MockMemory::new())
+            .unwrap()
+            .expect("The function returns a value");
+
+        let variable = return_value.root_variable();
+        assert_eq!(variable.get_value(&return_value), expected_value);
+    }
+
+    #[test]
+    fn function_return_value_from_register_pair() {
+        let debug_info = load_test_elf_as_debug_info("debug-unwind-tests/nRF52833_xxAA.elf");
+
+        // `assoc_arg<i32>` returns an `i64`, with its low word in `r0`, and its high word in `r1`.
+        let registers = cortex_m_registers(|id| match id {
+            0 => 0x8765_4321,
+            1 => 0x1234_5678,
+            _ => 0,
+        });
+
+        let return_value = debug_info
+            .function_return_value(0x1de4, &registers, None, &mut MockMemory::new())
+            .unwrap()
+            .expect("The function returns a value");
+
+        assert_eq!(
+            return_values(&return_value),
+            [0x1234_5678_8765_4321_i64.to_string()]
+        );
+    }
+
+    #[test]
+    fn double_returned_in_hard_float_registers() {
+        let debug_info = load_test_elf_as_debug_info("debug-unwind-tests/nRF52833_xxAA.elf");
+        let (unit_info, f64_offset) = debug_info
+            .unit_infos
+            .iter()
+            .find_map(|unit_info| {
+                let mut entries = unit_info.unit.entries();
+                while let Ok(Some((_, entry))) = entries.next_dfs() {
+                    if entry.tag() == gimli::DW_TAG_base_type
+                        && entry
+                            .attr_value(gimli::DW_AT_name)
+                            .ok()
+                            .flatten()
+                            .is_some_and(|name| extract_name(&debug_info, name) == "f64")
+                    {
+                        return Some((unit_info, entry.offset()));
+                    }
+                }
+                None
+            })
+            .unwrap();
+
+        // With the hard-float ABI, a `double` is returned in `d0`, which is `s0:s1`.
+        let value = 2.25f64.to_bits();
+        let mut core = MockCore::new(
+            HashMap::from([
+                (64, value & 0xFFFF_FFFF),
+                (65, value >> 32),
+                (0, 0xDEAD_BEEF),
+            ]),
+            MockMemory::new(),
+        );
+        core.core_registers = &CORTEX_M_WITH_FP_CORE_REGISTERS;
+        let registers = DebugRegisters(float_return_registers(
+            &mut core,
+            debug_info.return_value_abi,
+        ));
+
+        let location = return_value_location(
+            debug_info.return_value_abi,
+            unit_info,
+            f64_offset,
+            &registers,
+            None,
+        )
+        .unwrap();
+        let VariableLocation::Pieces(pieces) = location else {
+            panic!("The value is not in registers: {location:?}");
+        };
+        let bits = value_in_pieces(&pieces, 0, 8, gimli::RunTimeEndian::Little).unwrap();
+        assert_eq!(f64::from_bits(bits), 2.25);
+    }
+
+    #[test_case(0x40_1000, 1.5f32.to_bits() as u64, "1.5"; "float")]
+    #[test_case(0x40_1020, 2.25f64.to_bits(), "2.25"; "double")]
+    fn function_return_value_from_float_registers(
+        function_address: u64,
+        xmm0: u64,
+        expected_value: &str,
+    ) {
+        let debug_info = load_test_elf_as_debug_info("c-return-values/c-return-values.elf");
+
+        // Floating point values are returned in `xmm0`, not in `rax`.
+        let registers = x86_64_registers(|id| match id {
+            RAX => 0xFFFF_FFFF_FFFF_FFFF,
+            XMM0 => xmm0,
+            _ => 0,
+        });
+
+        let return_value = debug_info
+            .function_return_value(function_address, &registers, None, &mut MockMemory::new())
+            .unwrap()
+            .expect("The function returns a value");
+
+        assert_eq!(return_values(&return_value), [expected_value]);
+    }
+
+    #[test]
+    fn function_return_value_from_struct_in_registers() {
+        let debug_info = load_test_elf_as_debug_info("c-return-values/c-return-values.elf");
+
+        // `return_small` returns `left` in the low, and `right` in the high half of `rax`.
+        let registers = x86_64_registers(|id| if id == RAX { 0xFFFF_FFFE_0000_0007 } else { 0 });
+
+        let return_value = debug_info
+            .function_return_value(0x40_1040, &registers, None, &mut MockMemory::new())
+            .unwrap()
+            .expect("The function returns a value");
+
+        assert_eq!(return_values(&return_value), ["left = 7", "right = -2"]);
+    }
+
+    #[test_case(Some(0x2000_0000), 0xDEAD_BEEF; "buffer address from the caller")]
+    #[test_case(None, 0x2000_0000; "buffer address returned in rax")]
+    fn function_return_value_in_buffer(return_buffer_address: Option<u64>, rax: u64) {
+        let debug_info = load_test_elf_as_debug_info("c-return-values/c-return-values.elf");
+        let registers = x86_64_registers(|id| if id == RAX { rax } else { 0 });
+
+        // `return_large` writes its value to the buffer provided by the caller.
+        let mut memory = MockMemory::new();
+        memory.add_word_range(0x2000_0000, &[10, 11, 12, 13, 14, 15, 16, 17]);
+
+        let return_value = debug_info
+            .function_return_value(0x40_1060, &registers, return_buffer_address, &mut memory)
+            .unwrap()
+            .expect("The function returns a value");
+
+        assert_eq!(
+            return_values(&return_value),
+            (0..8)
+                .map(|index| format!("__{index} = {}", 10 + index))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn step_out_with_return_value() {
+        let debug_info = load_test_elf_as_debug_info("c-return-values/c-return-values.elf");
+
+        // Halted in `return_small`, which returns to `_start`.
+        let mut core = MockCore::new(
+            HashMap::from([(RIP, 0x40_1046), (MockCore::RETURN_ADDRESS, 0x40_10CA)]),
+            MockMemory::new(),
+        );
+        core.registers_at_breakpoint = HashMap::from([(RAX, 0x0000_0002_0000_0001)]);
+
+        let (_, program_counter, return_value) =
+            SteppingMode::step_out_with_return_value(&mut core, &debug_info).unwrap();
+
+        assert_eq!(program_counter, 0x40_10CA);
+        let return_value = return_value.expect("The function returns a value");
+        assert_eq!(return_values(&return_value), ["left = 1", "right = 2"]);
+    }
+
+    #[test]
+    fn step_out_with_return_value_from_function_entry() {
+        let debug_info = load_test_elf_as_debug_info("c-return-values/c-return-values.elf");
+
+        // At the entry of `return_large`, the address of the buffer is still in `rdi`.
+        let mut memory = MockMemory::new();
+        memory.add_word_range(0x2000_0000, &[10, 11, 12, 13, 14, 15, 16, 17]);
+        let mut core = MockCore::new(
+            HashMap::from([
+                (RIP, 0x40_1060),
+                (RDI, 0x2000_0000),
+                (MockCore::RETURN_ADDRESS, 0x40_10D9),
+            ]),
+            memory,
+        );
+        core.registers_at_breakpoint = HashMap::from([(RAX, 0xDEAD_BEEF), (RDI, 0)]);
+
+        let (_, program_counter, return_value) =
+            SteppingMode::step_out_with_return_value(&mut core, &debug_info).unwrap();
+
+        assert_eq!(program_counter, 0x40_10D9);
+        let return_value = return_value.expect("The function returns a value");
+        assert_eq!(
+            return_values(&return_value),
+            (0..8)
+                .map(|index| format!("__{index} = {}", 10 + index))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn function_without_return_value() {
+        let debug_info = load_test_elf_as_debug_info("c-optimized/c-optimized.elf");
+
+        // `nested_inlines` returns `void`.
+        let return_value = debug_info
+            .function_return_value(
+                0x40_1030,
+                &DebugRegisters(Vec::new()),
+                None,
+                &mut MockMemory::new(),
+            )
+            .unwrap();
+        assert!(return_value.is_none());
+    }
+}
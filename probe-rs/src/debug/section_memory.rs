@@ -0,0 +1,267 @@
+use super::DebugError;
+use crate::{Error, MemoryInterface};
+use object::read::{Object, ObjectSection};
+use object::{SectionFlags, SectionKind};
+use std::{ops::Range, path::Path};
+
+/// The contents of one allocated section of an ELF file.
+#[derive(Debug, Clone)]
+enum SectionContents {
+    /// The initial bytes of the section, e.g. `.data` or `.rodata`.
+    Initialized(Vec<u8>),
+    /// A section without bytes in the file, e.g. `.bss`, which is zero-initialized at startup.
+    Zeroed,
+}
+
+/// Read-only target memory that contains the initial values of the allocated sections of an ELF file,
+/// so that e.g. statics can be inspected without a target.
+///
+/// Initialized sections (`.data`, `.rodata`, ...) are read from the file, at their run-time address,
+/// and reads from zero-initialized sections (`.bss`) return zeros.
+/// Reads of any other memory fail.
+#[derive(Debug, Clone)]
+pub struct SectionMemory {
+    sections: Vec<(Range<u64>, SectionContents)>,
+    supports_native_64bit_access: bool,
+}
+
+impl SectionMemory {
+    /// Read the allocated sections from the ELF file at `path`.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, DebugError> {
+        let data = std::fs::read(path)?;
+
+        SectionMemory::from_raw(&data)
+    }
+
+    /// Read the allocated sections from the contents of an ELF file.
+    pub fn from_raw(data: &[u8]) -> Result<Self, DebugError> {
+        let object = object::File::parse(data)?;
+
+        let mut sections = Vec::new();
+        for section in object.sections() {
+            let is_allocated = matches!(
+                section.flags(),
+                SectionFlags::Elf { sh_flags } if sh_flags & u64::from(object::elf::SHF_ALLOC) != 0
+            );
+            // Thread local sections are only templates, and do not occupy their address at run-time.
+            if !is_allocated
+                || section.size() == 0
+                || matches!(
+                    section.kind(),
+                    SectionKind::Tls | SectionKind::UninitializedTls
+                )
+            {
+                continue;
+            }
+
+            let contents = if section.kind() == SectionKind::UninitializedData {
+                SectionContents::Zeroed
+            } else {
+                SectionContents::Initialized(section.data()?.to_vec())
+            };
+            sections.push((
+                section.address()..section.address() + section.size(),
+                contents,
+            ));
+        }
+
+        Ok(SectionMemory {
+            sections,
+            supports_native_64bit_access: object.is_64(),
+        })
+    }
+
+    /// Fill `data` with the bytes at `address`, which must be completely inside one section.
+    fn read_bytes(&self, address: u64, data: &mut [u8]) -> Result<(), Error> {
+        let end_address = address.checked_add(data.len() as u64);
+        let Some((range, contents)) = self.sections.iter().find(|(range, _)| {
+            range.start <= address
+                && end_address.is_some_and(|end_address| end_address <= range.end)
+        }) else {
+            return Err(Error::Other(anyhow::anyhow!(
+                "The ELF file does not include the memory for address {address:#x} of size {:#x}",
+                data.len()
+            )));
+        };
+
+        match contents {
+            SectionContents::Initialized(bytes) => {
+                let offset = (address - range.start) as usize;
+                // The file may contain less data than the size of the section.
+                let available = bytes.get(offset..).unwrap_or_default();
+                let copied = available.len().min(data.len());
+                data[..copied].copy_from_slice(&available[..copied]);
+                data[copied..].fill(0);
+            }
+            SectionContents::Zeroed => data.fill(0),
+        }
+        Ok(())
+    }
+
+    /// Read the `N` bytes at `address`, e.g. to convert them to a word.
+    fn read_array<const N: usize>(&self, address: u64) -> Result<[u8; N], Error> {
+        let mut bytes = [0u8; N];
+        self.read_bytes(address, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Fill `data` with the words of `N` bytes at `address`, converted with `from_bytes`.
+    fn read_words<const N: usize, T>(
+        &self,
+        address: u64,
+        data: &mut [T],
+        from_bytes: fn([u8; N]) -> T,
+    ) -> Result<(), Error> {
+        let mut bytes = vec![0u8; data.len() * N];
+        self.read_bytes(address, &mut bytes)?;
+        for (word, word_bytes) in data.iter_mut().zip(bytes.chunks_exact(N)) {
+            // `chunks_exact` only yields slices of `N` bytes.
+            *word = from_bytes(word_bytes.try_into().unwrap());
+        }
+        Ok(())
+    }
+}
+
+impl MemoryInterface for SectionMemory {
+    fn supports_native_64bit_access(&mut self) -> bool {
+        self.supports_native_64bit_access
+    }
+
+    fn read_word_64(&mut self, address: u64) -> Result<u64, Error> {
+        Ok(u64::from_le_bytes(self.read_array(address)?))
+    }
+
+    fn read_word_32(&mut self, address: u64) -> Result<u32, Error> {
+        Ok(u32::from_le_bytes(self.read_array(address)?))
+    }
+
+    fn read_word_16(&mut self, address: u64) -> Result<u16, Error> {
+        Ok(u16::from_le_bytes(self.read_array(address)?))
+    }
+
+    fn read_word_8(&mut self, address: u64) -> Result<u8, Error> {
+        let [byte] = self.read_array(address)?;
+        Ok(byte)
+    }
+
+    fn read_64(&mut self, address: u64, data: &mut [u64]) -> Result<(), Error> {
+        self.read_words(address, data, u64::from_le_bytes)
+    }
+
+    fn read_32(&mut self, address: u64, data: &mut [u32]) -> Result<(), Error> {
+        self.read_words(address, data, u32::from_le_bytes)
+    }
+
+    fn read_16(&mut self, address: u64, data: &mut [u16]) -> Result<(), Error> {
+        self.read_words(address, data, u16::from_le_bytes)
+    }
+
+    fn read_8(&mut self, address: u64, data: &mut [u8]) -> Result<(), Error> {
+        self.read_bytes(address, data)
+    }
+
+    fn write_word_64(&mut self, _address: u64, _data: u64) -> Result<(), Error> {
+        Err(read_only_error())
+    }
+
+    fn write_word_32(&mut self, _address: u64, _data: u32) -> Result<(), Error> {
+        Err(read_only_error())
+    }
+
+    fn write_word_16(&mut self, _address: u64, _data: u16) -> Result<(), Error> {
+        Err(read_only_error())
+    }
+
+    fn write_word_8(&mut self, _address: u64, _data: u8) -> Result<(), Error> {
+        Err(read_only_error())
+    }
+
+    fn write_64(&mut self, _address: u64, _data: &[u64]) -> Result<(), Error> {
+        Err(read_only_error())
+    }
+
+    fn write_32(&mut self, _address: u64, _data: &[u32]) -> Result<(), Error> {
+        Err(read_only_error())
+    }
+
+    fn write_16(&mut self, _address: u64, _data: &[u16]) -> Result<(), Error> {
+        Err(read_only_error())
+    }
+
+    fn write_8(&mut self, _address: u64, _data: &[u8]) -> Result<(), Error> {
+        Err(read_only_error())
+    }
+
+    fn supports_8bit_transfers(&self) -> Result<bool, Error> {
+        Ok(true)
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+fn read_only_error() -> Error {
+    Error::Other(anyhow::anyhow!(
+        "The memory of an ELF file can not be modified."
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::SectionMemory;
+    use crate::{
+        debug::{stack_frame::StackFrameInfo, DebugInfo, VariableName},
+        test::{get_path_for_test_files, x86_64::RIP, x86_64_registers},
+        MemoryInterface,
+    };
+
+    #[test]
+    fn statics_from_section_memory() {
+        let elf_path = get_path_for_test_files("c-types/c-types-dwarf5.elf");
+        let debug_info = DebugInfo::from_file(&elf_path).unwrap();
+        let mut memory = SectionMemory::from_file(&elf_path).unwrap();
+
+        // Resolving variables requires a program counter, which we set to `_start`.
+        let registers = x86_64_registers(|id| if id == RIP { 0x40_1000 } else { 0 });
+        let unit_info = &debug_info.unit_infos[0];
+        let mut static_variables = debug_info.create_static_scope_cache(unit_info).unwrap();
+        static_variables.recurse_deferred_variables(
+            &debug_info,
+            &mut memory,
+            None,
+            10,
+            0,
+            StackFrameInfo {
+                registers: &registers,
+                frame_base: None,
+                canonical_frame_address: None,
+                thread_local_storage_base: None,
+                registers_only: false,
+                frame_torn_down: false,
+            },
+        );
+        let member_value = |variable_name: &str, member_name: &str| {
+            let variable = static_variables
+                .get_variable_by_name(&VariableName::Named(variable_name.to_string()))
+                .unwrap();
+            static_variables
+                .get_children(variable.variable_key())
+                .unwrap()
+                .into_iter()
+                .find(|member| member.name.to_string() == member_name)
+                .unwrap()
+                .get_value(&static_variables)
+        };
+
+        // `nested` is initialized in `.data`.
+        assert_eq!(member_value("nested", "id"), "1");
+        assert_eq!(member_value("nested", "mode"), "Mode::MODE_AUTO");
+        // `rx_frame` is zero-initialized in `.bss`.
+        assert_eq!(member_value("rx_frame", "crc"), "0");
+
+        // Memory outside of the sections can not be read.
+        assert!(memory.read_word_32(0x1000_0000).is_err());
+        assert!(memory.read_word_32(u64::MAX - 1).is_err());
+    }
+}
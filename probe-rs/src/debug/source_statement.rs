@@ -36,10 +36,21 @@ impl SourceStatements {
         let (complete_line_program, active_sequence) =
             get_program_info_at_pc(debug_info, program_unit, program_counter)?;
         let mut sequence_rows = complete_line_program.resume_from(&active_sequence);
+        let mut rows = Vec::new();
+        while let Ok(Some((_, row))) = sequence_rows.next_row() {
+            rows.push((*row, row.is_stmt()));
+        }
+        if program_unit
+            .get_producer(debug_info)
+            .is_some_and(|producer| emits_empty_statement_views(&producer))
+        {
+            rows = merge_location_views(rows);
+        }
         let program_language = program_unit.get_language();
         let mut prologue_completed = false;
         let mut source_statement: Option<SourceStatement> = None;
-        while let Ok(Some((_, row))) = sequence_rows.next_row() {
+        for (row, is_stmt) in &rows {
+            let is_stmt = *is_stmt;
             if let Some(source_row) = source_statement.as_mut() {
                 if source_row.line.is_none()
                     && row.line().is_some()
@@ -51,7 +62,7 @@ impl SourceStatements {
                 }
             } else {
                 // Start tracking the source statement using this row.
-                source_statement = Some(SourceStatement::from(row));
+                source_statement = Some(SourceStatement::from_row(row, is_stmt));
             }
 
             // Don't do anything until we are at least at the prologue_end() of a function.
@@ -69,7 +80,7 @@ impl SourceStatements {
             {
                 if let Some(source_row) = source_statement.as_mut() {
                     if row.end_sequence()
-                        || (row.is_stmt()
+                        || (is_stmt
                             && (row.file_index() == source_row.file_index
                                 && (row.line() != source_row.line || row.line().is_none())))
                     {
@@ -96,7 +107,7 @@ impl SourceStatements {
                 source_row.instruction_range = source_row.low_pc()..row.address();
 
                 if row.end_sequence()
-                    || (row.is_stmt() && row.address() > source_row.low_pc())
+                    || (is_stmt && row.address() > source_row.low_pc())
                     || !(row.file_index() == source_row.file_index
                         && (row.line() == source_row.line || row.line().is_none())
                         && row.column() == source_row.column)
@@ -111,7 +122,7 @@ impl SourceStatements {
                         // If we hit the end of the sequence, we can get out of here.
                         break;
                     }
-                    source_statement = Some(SourceStatement::from(row));
+                    source_statement = Some(SourceStatement::from_row(row, is_stmt));
                 } else if row.address() == program_counter {
                     // If we encounter the program_counter after the prologue, then we need to use this address as the low_pc, or else we run the risk of setting a breakpoint before the current program counter.
                     source_row.instruction_range = row.address()..row.address();
@@ -212,10 +223,12 @@ impl SourceStatement {
     }
 }
 
-impl From<&gimli::LineRow> for SourceStatement {
-    fn from(line_row: &gimli::LineRow) -> Self {
+impl SourceStatement {
+    /// Start a source statement at `line_row`, where `is_stmt` replaces the flag of the row,
+    /// see [`merge_location_views`].
+    fn from_row(line_row: &gimli::LineRow, is_stmt: bool) -> Self {
         SourceStatement {
-            is_stmt: line_row.is_stmt(),
+            is_stmt,
             file_index: line_row.file_index(),
             line: line_row.line(),
             column: line_row.column(),
@@ -225,10 +238,52 @@ impl From<&gimli::LineRow> for SourceStatement {
     }
 }
 
-// Overriding clippy, as this is a private helper function.
+/// Check if the `producer` of a compilation unit emits location views, where a statement can be marked at an address,
+/// before a row for the same address that describes the instruction.
+///
+/// Since version 8, GCC emits location views for optimized code, i.e. several rows for the same address,
+/// of which only the last describes the instruction at that address.
+/// The earlier rows are often marked as statements (`is_stmt`) for the next line,
+/// while the instruction still belongs to the previous line, so stepping would halt on a line before any of its code has run.
+/// Other compilers, and the GNU assembler, which creates the line program for hand written assembly, are not affected.
+pub(crate) fn emits_empty_statement_views(producer: &str) -> bool {
+    let mut words = producer.split_whitespace();
+    if words.next() != Some("GNU") || matches!(words.next(), None | Some("AS")) {
+        return false;
+    }
+    words
+        .next()
+        .and_then(|version| version.split('.').next()?.parse::<u32>().ok())
+        .is_some_and(|major_version| major_version >= 8)
+}
+
+/// Merge the `rows` of a sequence, with their `is_stmt` flag, that have the same address into the last of these rows,
+/// which describes the instruction at that address.
+///
+/// The merged row is a statement, if any of the rows at that address was a statement,
+/// so the address remains a valid halt location, but for the line of its instruction.
+fn merge_location_views(rows: Vec<(gimli::LineRow, bool)>) -> Vec<(gimli::LineRow, bool)> {
+    let mut merged_rows: Vec<(gimli::LineRow, bool)> = Vec::with_capacity(rows.len());
+    for (row, is_stmt) in rows {
+        match merged_rows.last_mut() {
+            Some((previous_row, previous_is_stmt))
+                if previous_row.address() == row.address()
+                    && !previous_row.end_sequence()
+                    && !row.end_sequence() =>
+            {
+                *previous_row = row;
+                *previous_is_stmt |= is_stmt;
+            }
+            _ => merged_rows.push((row, is_stmt)),
+        }
+    }
+    merged_rows
+}
+
+// Overriding clippy, as this is an internal helper function.
 #[allow(clippy::type_complexity)]
 /// Resolve the relevant program row data for the given program counter.
-fn get_program_info_at_pc(
+pub(crate) fn get_program_info_at_pc(
     debug_info: &DebugInfo,
     program_unit: &UnitInfo,
     program_counter: u64,
@@ -19,6 +19,30 @@ pub struct StackFrameInfo<'a> {
 
     /// The value of the stack pointer just before the CALL instruction in the parent function.
     pub canonical_frame_address: Option<u64>,
+
+    /// The base address of the thread local storage (TLS) block, used to resolve `DW_OP_form_tls_address` location expressions.
+    /// The debug info does not describe where to find it, so it has to be supplied by the caller, e.g. from an RTOS task control block.
+    /// Together with the `registers` of a suspended task, this allows resolving the variables of tasks other than the currently running one.
+    pub thread_local_storage_base: Option<u64>,
+
+    /// Only resolve the variables that are available from the `registers`, without reading the target memory,
+    /// e.g. to inspect the registers that were saved after a fault, when the memory may not be accessible.
+    /// Variables that are stored in memory are reported as [`UnavailableReason::NeedsMemory`](crate::debug::UnavailableReason::NeedsMemory).
+    pub registers_only: bool,
+
+    /// The `frame_base` is no longer valid, because the program counter is in the epilogue of the function,
+    /// and the frame base is relative to a register (e.g. the stack pointer or frame pointer) that may already
+    /// have been restored for the caller. See [`StackFrame::frame_torn_down`].
+    pub frame_torn_down: bool,
+}
+
+/// The order of the function names in a folded stack, see [`StackFrame::folded_stack`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FoldedStackOrder {
+    /// The outermost function first, and the innermost function last, as expected by flamegraph tools.
+    RootFirst,
+    /// The innermost function first, in the same order as the unwound stack frames.
+    LeafFirst,
 }
 
 /// A full stack frame with all its information contained.
@@ -40,6 +64,12 @@ pub struct StackFrame {
     /// However, some isa's (e.g. RISC-V) uses a default of `-C force-frame-pointers off` and will then use the stack pointer as the frame base address.
     /// We store the frame_base of the relevant non-inlined parent function, to ensure correct calculation of the [`Variable::memory_location`] values.
     pub frame_base: Option<u64>,
+    /// The program counter is in the epilogue of the function, and the `frame_base` is relative to a register,
+    /// so that the variables relative to the frame base are reported as
+    /// [`UnavailableReason::FrameTornDown`](crate::debug::UnavailableReason::FrameTornDown).
+    /// A frame base that is relative to the canonical frame address stays valid in the epilogue.
+    #[serde(skip_serializing)]
+    pub frame_torn_down: bool,
     /// Indicate if this stack frame belongs to an inlined function.
     pub is_inlined: bool,
     /// A cache of 'static' scoped variables for this stackframe
@@ -50,6 +80,80 @@ pub struct StackFrame {
     pub local_variables: Option<VariableCache>,
     /// The value of the stack pointer just before the CALL instruction in the parent function.
     pub canonical_frame_address: Option<u64>,
+    /// The source location in the calling frame where execution continues when this frame returns,
+    /// as determined during unwinding. See [`StackFrame::return_location`].
+    #[serde(skip_serializing)]
+    pub(crate) return_location: Option<SourceLocation>,
+}
+
+impl StackFrame {
+    /// The source location in the calling function that this frame will return to,
+    /// e.g. to show where a "step out" will land.
+    ///
+    /// For inlined functions, this is the call site in the containing function.
+    /// Otherwise, it is the location of the instruction before the return address,
+    /// which attributes the return to the line of the call in the caller, rather than to the line that follows it.
+    ///
+    /// Returns `None` for the bottom frame of the stack, which has no caller.
+    pub fn return_location(&self) -> Option<SourceLocation> {
+        self.return_location.clone()
+    }
+
+    /// Format the unwound `stack_frames` of a sample as a folded stack, i.e. the function names joined with `;`,
+    /// which can be counted per sample to draw a flamegraph.
+    ///
+    /// Inlined functions are only included as separate entries if `include_inlined` is set,
+    /// otherwise only the functions they are inlined into are included.
+    ///
+    /// The `;` in a function name is replaced with `:`, and trailing whitespace is removed,
+    /// so that the name is not split into two functions, or mistaken for the separator of the sample count.
+    pub fn folded_stack(
+        stack_frames: &[StackFrame],
+        order: FoldedStackOrder,
+        include_inlined: bool,
+    ) -> String {
+        let function_names = stack_frames
+            .iter()
+            .filter(|frame| include_inlined || !frame.is_inlined)
+            .map(|frame| frame.function_name.trim_end().replace(';', ":"));
+
+        match order {
+            FoldedStackOrder::RootFirst => function_names.rev().collect::<Vec<_>>().join(";"),
+            FoldedStackOrder::LeafFirst => function_names.collect::<Vec<_>>().join(";"),
+        }
+    }
+
+    /// The local variables of this frame whose values differ from those in `previous`,
+    /// the local variables of the same frame at the previous halt, e.g. to highlight what the last step changed.
+    ///
+    /// The keys of the variables are not kept between halts, so the variables are matched by their debug information entry.
+    /// The value of a variable includes the values of its members, so a change to a member reports the whole variable.
+    /// Variables that are not in `previous`, e.g. because they just came into scope, are included.
+    /// Only the variables that were already resolved, e.g. with [`DebugInfo::cache_deferred_variables`], are compared.
+    /// Returns an empty list if the local variables of this frame were not resolved.
+    pub fn changed_since(&self, previous: &VariableCache) -> Vec<Variable> {
+        let Some(local_variables) = &self.local_variables else {
+            return Vec::new();
+        };
+        let previous_variables = previous
+            .get_children(previous.root_variable().variable_key())
+            .unwrap_or_default();
+
+        local_variables
+            .get_children(local_variables.root_variable().variable_key())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|variable| {
+                let value = variable.get_value(local_variables);
+                !previous_variables.iter().any(|previous_variable| {
+                    previous_variable.unit_header_offset == variable.unit_header_offset
+                        && previous_variable.variable_unit_offset == variable.variable_unit_offset
+                        && previous_variable.name == variable.name
+                        && previous_variable.get_value(previous) == value
+                })
+            })
+            .collect()
+    }
 }
 
 impl std::fmt::Display for StackFrame {
@@ -87,6 +191,14 @@ impl std::fmt::Display for StackFrame {
 #[cfg(test)]
 mod test {
     use super::StackFrame;
+    use crate::{
+        debug::stack_frame::StackFrameInfo,
+        test::{
+            load_test_elf_as_debug_info,
+            x86_64::{RBP, RIP, RSP},
+            x86_64_registers, MockMemory,
+        },
+    };
 
     /// Helper struct used to format a StackFrame for testing.
     pub struct TestFormatter<'s>(pub &'s StackFrame);
@@ -115,4 +227,79 @@ mod test {
             Ok(())
         }
     }
+
+    #[test]
+    fn locals_changed_by_step() {
+        let debug_info = load_test_elf_as_debug_info("c-array-index/c-array-index.elf");
+
+        // `select_values`, with `rbp` at 0x2000_00F0, so that `points` is at 0x2000_00C0, `values` at 0x2000_00D0,
+        // and `i` at 0x2000_00EC.
+        let frame_at = |program_counter: u64, i: u32| {
+            let registers = x86_64_registers(|id| match id {
+                RBP => 0x2000_00F0,
+                RSP => 0x2000_00F0,
+                RIP => program_counter,
+                _ => 0,
+            });
+            let mut memory = MockMemory::new();
+            memory.add_word_range(
+                0x2000_00C0,
+                &[1, 2, 3, 4, 10, 20, 30, 40, 0, 0, 0, i, 0, 0, 0, 0],
+            );
+            let mut frame = debug_info
+                .get_stackframe_info(
+                    &mut memory,
+                    program_counter,
+                    &mut gimli::UnwindContext::new(),
+                    &registers,
+                )
+                .unwrap()
+                .pop()
+                .unwrap();
+
+            // Resolve the locals, as a debugger does when it shows them.
+            let frame_info = StackFrameInfo {
+                registers: &frame.registers,
+                frame_base: frame.frame_base,
+                canonical_frame_address: frame.canonical_frame_address,
+                thread_local_storage_base: None,
+                registers_only: false,
+                frame_torn_down: frame.frame_torn_down,
+            };
+            let local_variables = frame.local_variables.as_mut().unwrap();
+            let mut root_variable = local_variables.root_variable();
+            debug_info
+                .cache_deferred_variables(
+                    local_variables,
+                    &mut memory,
+                    &mut root_variable,
+                    frame_info,
+                )
+                .unwrap();
+            frame
+        };
+
+        // Step over `uint32_t i = selected;`, from line 14 to line 15.
+        let previous_frame = frame_at(0x40_103c, 0);
+        let frame = frame_at(0x40_1045, 1);
+
+        let previous_variables = previous_frame.local_variables.as_ref().unwrap();
+        let changed = frame.changed_since(previous_variables);
+        assert_eq!(
+            changed
+                .iter()
+                .map(|variable| variable.name.to_string())
+                .collect::<Vec<_>>(),
+            vec!["i"]
+        );
+        assert_eq!(
+            changed[0].get_value(frame.local_variables.as_ref().unwrap()),
+            "1"
+        );
+
+        // Nothing changed since the same halt.
+        assert!(frame
+            .changed_since(frame.local_variables.as_ref().unwrap())
+            .is_empty());
+    }
 }
@@ -0,0 +1,478 @@
+use super::{
+    debug_info::GimliReader, extract_byte_size, extract_constant, extract_name,
+    unit_info::UnitInfo, DebugError, DebugInfo,
+};
+use gimli::{DebugInfoOffset, EvaluationResult, Location, UnitOffset};
+
+/// The type information of a DWARF type, which can be inspected without reading any target memory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeInfo {
+    /// The name of the type, if it has one.
+    pub name: Option<String>,
+    /// The size of the type in bytes, if known.
+    pub byte_size: Option<u64>,
+    /// The offset of the compilation unit that contains the type.
+    pub unit_header_offset: DebugInfoOffset,
+    /// The offset of the type DIE in the compilation unit.
+    pub type_unit_offset: UnitOffset,
+}
+
+/// The layout of a bitfield member, as described by `DW_AT_bit_size` and `DW_AT_data_bit_offset` (or the older `DW_AT_bit_offset`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitFieldLayout {
+    /// The offset, in bits, of the first bit of the member, from the start of the containing type.
+    pub bit_offset: u64,
+    /// The number of bits occupied by the member.
+    pub bit_size: u64,
+}
+
+/// A member of a structured type (`struct`, `union` or `class`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemberInfo {
+    /// The name of the member, or `None` for anonymous members.
+    pub name: Option<String>,
+    /// The type of the member, if it could be resolved.
+    pub type_info: Option<TypeInfo>,
+    /// The offset, in bytes, of the member from the start of the containing type.
+    /// For bitfields, this is the offset of the byte that contains the first bit of the member.
+    pub byte_offset: u64,
+    /// The size of the member in bytes, if known.
+    pub byte_size: Option<u64>,
+    /// The bit layout, if this member is a bitfield.
+    pub bit_field: Option<BitFieldLayout>,
+}
+
+impl TypeInfo {
+    /// Create the [`TypeInfo`] for the type DIE at `type_unit_offset` in the unit at `unit_header_offset`.
+    pub fn new(
+        debug_info: &DebugInfo,
+        unit_header_offset: DebugInfoOffset,
+        type_unit_offset: UnitOffset,
+    ) -> Result<Self, DebugError> {
+        let unit_info = unit_info_from_offset(debug_info, unit_header_offset)?;
+        let type_die = unit_info.unit.entry(type_unit_offset)?;
+        Self::from_die(debug_info, &unit_info, &type_die)
+    }
+
+    /// Find the first named type with the given `type_name` (e.g. the name of a `struct`, `enum` or `typedef`).
+    pub fn from_name(debug_info: &DebugInfo, type_name: &str) -> Result<Option<Self>, DebugError> {
+        for unit_info in &debug_info.unit_infos {
+            let mut entries = unit_info.unit.entries();
+            while let Some((_, entry)) = entries.next_dfs()? {
+                if !is_type_tag(entry.tag()) {
+                    continue;
+                }
+                let Some(name) = entry.attr_value(gimli::DW_AT_name)? else {
+                    continue;
+                };
+                if extract_name(debug_info, name) == type_name {
+                    return Self::from_die(debug_info, unit_info, entry).map(Some);
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// List the members of this type, including anonymous and bitfield members, in declaration order.
+    ///
+    /// Type modifiers and typedefs (e.g. `const`, `volatile`) are resolved to the underlying type.
+    /// Types without members (e.g. base types or pointers) return an empty list.
+    pub fn members(&self, debug_info: &DebugInfo) -> Result<Vec<MemberInfo>, DebugError> {
+        let unit_info = unit_info_from_offset(debug_info, self.unit_header_offset)?;
+
+        let Some(structure_offset) = resolve_type_modifiers(&unit_info, self.type_unit_offset)?
+        else {
+            return Ok(Vec::new());
+        };
+
+        let mut members = Vec::new();
+        let mut tree = unit_info.unit.entries_tree(Some(structure_offset))?;
+        let mut children = tree.root()?.children();
+        while let Some(child) = children.next()? {
+            let member_die = child.entry();
+            if member_die.tag() != gimli::DW_TAG_member {
+                continue;
+            }
+            members.push(member_info(debug_info, &unit_info, member_die)?);
+        }
+        Ok(members)
+    }
+
+    /// Create the [`TypeInfo`] from a type DIE.
+    fn from_die(
+        debug_info: &DebugInfo,
+        unit_info: &UnitInfo,
+        type_die: &gimli::DebuggingInformationEntry<GimliReader>,
+    ) -> Result<Self, DebugError> {
+        let name = type_die
+            .attr_value(gimli::DW_AT_name)
+            .ok()
+            .flatten()
+            .map(|name| extract_name(debug_info, name));
+
+        let byte_size = extract_byte_size(type_die).or_else(|| {
+            // Typedefs and type modifiers don't have a size, so we use the size of the type they refer to.
+            let referenced_type = resolve_type_modifiers(unit_info, type_die.offset()).ok()??;
+            extract_byte_size(&unit_info.unit.entry(referenced_type).ok()?)
+        });
+
+        Ok(Self {
+            name,
+            byte_size,
+            unit_header_offset: unit_info.debug_info_offset()?,
+            type_unit_offset: type_die.offset(),
+        })
+    }
+}
+
+/// Resolve the `DebugInfoOffset` of a unit, to a `UnitInfo`.
+fn unit_info_from_offset(
+    debug_info: &DebugInfo,
+    unit_header_offset: DebugInfoOffset,
+) -> Result<UnitInfo, DebugError> {
+    let unit_header = debug_info
+        .dwarf
+        .debug_info
+        .header_from_offset(unit_header_offset)?;
+    Ok(UnitInfo::new(gimli::Unit::new(
+        &debug_info.dwarf,
+        unit_header,
+    )?))
+}
+
+/// Follow typedefs and type modifiers to the underlying type.
+/// Returns `None` if the chain ends without a type, e.g. for `void`, or a `const void`.
+pub(crate) fn resolve_type_modifiers(
+    unit_info: &UnitInfo,
+    mut type_offset: UnitOffset,
+) -> Result<Option<UnitOffset>, DebugError> {
+    loop {
+        let type_die = unit_info.unit.entry(type_offset)?;
+        if !matches!(
+            type_die.tag(),
+            gimli::DW_TAG_typedef
+                | gimli::DW_TAG_const_type
+                | gimli::DW_TAG_volatile_type
+                | gimli::DW_TAG_restrict_type
+                | gimli::DW_TAG_atomic_type
+        ) {
+            return Ok(Some(type_offset));
+        }
+        match type_die.attr_value(gimli::DW_AT_type)? {
+            Some(gimli::AttributeValue::UnitRef(referenced_offset)) => {
+                type_offset = referenced_offset;
+            }
+            _ => return Ok(None),
+        }
+    }
+}
+
+/// Check if the type at `type_offset` is named `type_name`, or is a typedef or type modifier of such a type,
+/// e.g. a `uint32_t` for `unsigned int`, or a `volatile struct Nested` for `Nested`.
+pub(crate) fn type_has_name(
+    debug_info: &DebugInfo,
+    unit_info: &UnitInfo,
+    mut type_offset: UnitOffset,
+    type_name: &str,
+) -> Result<bool, DebugError> {
+    loop {
+        let type_die = unit_info.unit.entry(type_offset)?;
+        if type_die
+            .attr_value(gimli::DW_AT_name)?
+            .is_some_and(|name| extract_name(debug_info, name) == type_name)
+        {
+            return Ok(true);
+        }
+        if !matches!(
+            type_die.tag(),
+            gimli::DW_TAG_typedef
+                | gimli::DW_TAG_const_type
+                | gimli::DW_TAG_volatile_type
+                | gimli::DW_TAG_restrict_type
+                | gimli::DW_TAG_atomic_type
+        ) {
+            return Ok(false);
+        }
+        match type_die.attr_value(gimli::DW_AT_type)? {
+            Some(gimli::AttributeValue::UnitRef(referenced_offset)) => {
+                type_offset = referenced_offset;
+            }
+            _ => return Ok(false),
+        }
+    }
+}
+
+/// Check if the tag is one that describes a type.
+fn is_type_tag(tag: gimli::DwTag) -> bool {
+    matches!(
+        tag,
+        gimli::DW_TAG_base_type
+            | gimli::DW_TAG_structure_type
+            | gimli::DW_TAG_union_type
+            | gimli::DW_TAG_class_type
+            | gimli::DW_TAG_enumeration_type
+            | gimli::DW_TAG_typedef
+            | gimli::DW_TAG_array_type
+            | gimli::DW_TAG_pointer_type
+            | gimli::DW_TAG_subroutine_type
+    )
+}
+
+/// Extract the [`MemberInfo`] from a `DW_TAG_member` DIE.
+fn member_info(
+    debug_info: &DebugInfo,
+    unit_info: &UnitInfo,
+    member_die: &gimli::DebuggingInformationEntry<GimliReader>,
+) -> Result<MemberInfo, DebugError> {
+    let name = member_die
+        .attr_value(gimli::DW_AT_name)?
+        .map(|name| extract_name(debug_info, name));
+
+    let type_info = match member_die.attr_value(gimli::DW_AT_type)? {
+        Some(gimli::AttributeValue::UnitRef(type_offset)) => Some(TypeInfo::from_die(
+            debug_info,
+            unit_info,
+            &unit_info.unit.entry(type_offset)?,
+        )?),
+        _ => None,
+    };
+
+    // Members of a union, and DWARF 5 bitfields, don't have a `DW_AT_data_member_location`.
+    let data_member_location = match member_die.attr(gimli::DW_AT_data_member_location)? {
+        Some(attribute) => match attribute.value() {
+            gimli::AttributeValue::Exprloc(expression) => {
+                Some(member_location_from_expression(unit_info, expression)?)
+            }
+            _ => extract_constant(&attribute.value()),
+        },
+        None => None,
+    };
+
+    let byte_size = member_die
+        .attr(gimli::DW_AT_byte_size)?
+        .and_then(|attribute| extract_constant(&attribute.value()))
+        .or_else(|| type_info.as_ref().and_then(|type_info| type_info.byte_size));
+
+    let bit_field = bit_field_layout(member_die, data_member_location, byte_size)?;
+
+    let byte_offset = match bit_field {
+        Some(bit_field) => bit_field.bit_offset / 8,
+        None => data_member_location.unwrap_or_default(),
+    };
+
+    Ok(MemberInfo {
+        name,
+        type_info,
+        byte_offset,
+        byte_size: if bit_field.is_some() { None } else { byte_size },
+        bit_field,
+    })
+}
+
+/// The bit layout of the bitfield `member_die`, relative to the start of the containing type,
+/// or `None` if the member is not a bitfield.
+///
+/// The `data_member_location` and the `storage_size` (the `DW_AT_byte_size` of the member, or of its type)
+/// are only needed for the DWARF 2/3 `DW_AT_bit_offset`.
+pub(crate) fn bit_field_layout(
+    member_die: &gimli::DebuggingInformationEntry<GimliReader>,
+    data_member_location: Option<u64>,
+    storage_size: Option<u64>,
+) -> Result<Option<BitFieldLayout>, DebugError> {
+    let Some(bit_size) = member_die
+        .attr(gimli::DW_AT_bit_size)?
+        .and_then(|attribute| extract_constant(&attribute.value()))
+    else {
+        return Ok(None);
+    };
+
+    let bit_offset = if let Some(data_bit_offset) = member_die
+        .attr(gimli::DW_AT_data_bit_offset)?
+        .and_then(|attribute| extract_constant(&attribute.value()))
+    {
+        Some(data_bit_offset)
+    } else if let Some(legacy_bit_offset) = member_die
+        .attr(gimli::DW_AT_bit_offset)?
+        .and_then(|attribute| extract_constant(&attribute.value()))
+    {
+        // DWARF 2/3: The `DW_AT_bit_offset` is counted from the most significant bit of the storage unit,
+        // that starts at `DW_AT_data_member_location`, and is `DW_AT_byte_size` bytes long.
+        // We only support little endian targets, so we convert it to an offset from the start of the containing type.
+        storage_size.map(|storage_size| {
+            (data_member_location.unwrap_or_default() + storage_size) * 8
+                - legacy_bit_offset
+                - bit_size
+        })
+    } else {
+        data_member_location.map(|location| location * 8)
+    };
+
+    Ok(bit_offset.map(|bit_offset| BitFieldLayout {
+        bit_offset,
+        bit_size,
+    }))
+}
+
+/// Evaluate a `DW_AT_data_member_location` expression, e.g. `DW_OP_plus_uconst`, relative to a start address of zero.
+fn member_location_from_expression(
+    unit_info: &UnitInfo,
+    expression: gimli::Expression<GimliReader>,
+) -> Result<u64, DebugError> {
+    let mut evaluation = expression.evaluation(unit_info.unit.encoding());
+    evaluation.set_initial_value(0);
+    if let EvaluationResult::Complete = evaluation.evaluate()? {
+        if let [gimli::Piece {
+            location: Location::Address { address },
+            ..
+        }] = evaluation.result().as_slice()
+        {
+            return Ok(*address);
+        }
+    }
+    Err(DebugError::Other(anyhow::anyhow!(
+        "Unimplemented: Unable to evaluate DW_AT_data_member_location expression."
+    )))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// Load one of the `c-types` test binaries, e.g. `c-types-dwarf4.elf`.
+    fn load_c_types_debug_info(elf_file: &str) -> DebugInfo {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("tests/c-types");
+        path.push(elf_file);
+        DebugInfo::from_file(path).unwrap()
+    }
+
+    /// The name, type name, byte offset and bitfield (offset, size) of a member.
+    type MemberDescription<'a> = (Option<&'a str>, Option<&'a str>, u64, Option<(u64, u64)>);
+
+    /// A compact representation of a member, to keep the expected values readable.
+    fn describe(member: &MemberInfo) -> MemberDescription<'_> {
+        (
+            member.name.as_deref(),
+            member
+                .type_info
+                .as_ref()
+                .and_then(|type_info| type_info.name.as_deref()),
+            member.byte_offset,
+            member
+                .bit_field
+                .map(|bit_field| (bit_field.bit_offset, bit_field.bit_size)),
+        )
+    }
+
+    #[test]
+    fn nested_struct_members() {
+        let debug_info = load_c_types_debug_info("c-types-dwarf4.elf");
+
+        let nested = TypeInfo::from_name(&debug_info, "Nested")
+            .unwrap()
+            .expect("The test binary should contain `struct Nested`");
+        assert_eq!(nested.byte_size, Some(32));
+
+        let members = nested.members(&debug_info).unwrap();
+        assert_eq!(
+            members.iter().map(describe).collect::<Vec<_>>(),
+            vec![
+                (Some("id"), Some("uint8_t"), 0, None),
+                (Some("origin"), Some("Point"), 2, None),
+                (Some("flags"), Some("Flags"), 8, None),
+                (None, None, 12, None),
+                (Some("pair"), None, 16, None),
+                (Some("mode"), Some("Mode"), 20, None),
+                (Some("next"), None, 24, None),
+            ]
+        );
+
+        // The member types can be inspected in turn.
+        let flags = nested_member_type(&members, Some("flags"));
+        assert_eq!(
+            flags
+                .members(&debug_info)
+                .unwrap()
+                .iter()
+                .map(describe)
+                .collect::<Vec<_>>(),
+            vec![
+                (Some("enabled"), Some("uint32_t"), 0, Some((0, 1))),
+                (Some("mode"), Some("uint32_t"), 0, Some((1, 2))),
+                (Some("reserved"), Some("uint32_t"), 0, Some((3, 5))),
+                (Some("count"), Some("uint32_t"), 1, Some((8, 12))),
+            ]
+        );
+
+        // The typedef `Point` resolves to the anonymous struct it names.
+        let origin = nested_member_type(&members, Some("origin"));
+        assert_eq!(origin.byte_size, Some(4));
+        assert_eq!(
+            origin
+                .members(&debug_info)
+                .unwrap()
+                .iter()
+                .map(describe)
+                .collect::<Vec<_>>(),
+            vec![
+                (Some("x"), Some("uint16_t"), 0, None),
+                (Some("y"), Some("uint16_t"), 2, None),
+            ]
+        );
+
+        // Anonymous unions have members at offset zero.
+        let anonymous_union = nested_member_type(&members, None);
+        assert_eq!(
+            anonymous_union
+                .members(&debug_info)
+                .unwrap()
+                .iter()
+                .map(describe)
+                .collect::<Vec<_>>(),
+            vec![
+                (Some("raw"), Some("uint32_t"), 0, None),
+                (Some("value"), Some("float"), 0, None),
+            ]
+        );
+
+        // Pointers and base types have no members.
+        let next = nested_member_type(&members, Some("next"));
+        assert!(next.members(&debug_info).unwrap().is_empty());
+    }
+
+    fn nested_member_type(members: &[MemberInfo], name: Option<&str>) -> TypeInfo {
+        members
+            .iter()
+            .find(|member| member.name.as_deref() == name)
+            .and_then(|member| member.type_info.clone())
+            .unwrap()
+    }
+
+    #[test]
+    fn implicit_const_member_offsets() {
+        let debug_info = load_c_types_debug_info("c-types-dwarf5.elf");
+
+        for frame_type in ["RxFrame", "TxFrame"] {
+            let frame = TypeInfo::from_name(&debug_info, frame_type)
+                .unwrap()
+                .unwrap_or_else(|| panic!("The test binary should contain `struct {frame_type}`"));
+            // `DW_AT_byte_size` is encoded as `DW_FORM_implicit_const`.
+            assert_eq!(frame.byte_size, Some(264));
+
+            // The `DW_AT_data_member_location` of `crc` and `baud` are encoded as `DW_FORM_implicit_const`.
+            let members = frame.members(&debug_info).unwrap();
+            assert_eq!(
+                members
+                    .iter()
+                    .map(|member| (member.name.as_deref(), member.byte_offset, member.byte_size))
+                    .collect::<Vec<_>>(),
+                vec![
+                    (Some("data"), 0, None),
+                    (Some("crc"), 256, Some(4)),
+                    (Some("baud"), 260, Some(4)),
+                ]
+            );
+        }
+    }
+}
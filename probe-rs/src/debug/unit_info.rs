@@ -1,13 +1,18 @@
 use super::{
-    debug_info::*, extract_byte_size, extract_file, extract_line, extract_name,
-    function_die::FunctionDie, variable::*, DebugError, DebugRegisters, EndianReader,
-    SourceLocation, VariableCache,
+    debug_info::*,
+    extract_byte_size, extract_constant, extract_file, extract_line, extract_name, format_address,
+    function_die::FunctionDie,
+    is_declaration,
+    type_info::{bit_field_layout, resolve_type_modifiers, BitFieldLayout},
+    variable::*,
+    DebugError, DebugRegisters, EndianReader, SourceLocation, VariableCache,
 };
 use crate::{core::RegisterValue, debug::stack_frame::StackFrameInfo, Error, MemoryInterface};
-use gimli::{AttributeValue::Language, EvaluationResult, Location, UnitOffset};
+use gimli::{AttributeValue::Language, EvaluationResult, Location, Reader, UnitOffset};
 use num_traits::Zero;
 
 /// The result of `UnitInfo::evaluate_expression()` can be the value of a variable, or a memory location.
+#[derive(Debug)]
 pub(crate) enum ExpressionResult {
     Value(VariableValue),
     Location(VariableLocation),
@@ -18,12 +23,35 @@ pub struct UnitInfo {
     pub(crate) unit: gimli::Unit<GimliReader, usize>,
 }
 
+/// The GNU extension to push the value of another variable, e.g. the length of a variable length array.
+/// gimli does not support this operation, see [`UnitInfo::resolve_variable_values`].
+const DW_OP_GNU_VARIABLE_VALUE: gimli::DwOp = gimli::DwOp(0xfd);
+
+/// The maximum nesting of `DW_OP_GNU_variable_value` operations, to avoid endless recursion for cyclic references.
+const MAX_VARIABLE_VALUE_DEPTH: usize = 8;
+
 impl UnitInfo {
     /// Create a new `UnitInfo` from a `gimli::Unit`.
     pub fn new(unit: gimli::Unit<GimliReader, usize>) -> Self {
         Self { unit }
     }
 
+    /// The offset of the unit header in the `.debug_info` section.
+    ///
+    /// Units in `.debug_types` don't have such an offset, and are reported as an error.
+    pub(crate) fn debug_info_offset(&self) -> Result<gimli::DebugInfoOffset, DebugError> {
+        self.unit
+            .header
+            .offset()
+            .as_debug_info_offset()
+            .ok_or_else(|| {
+                DebugError::Other(anyhow::anyhow!(
+                    "The unit at {:?} is not in the `.debug_info` section.",
+                    self.unit.header.offset()
+                ))
+            })
+    }
+
     /// Retrieve the value of the `DW_AT_language` attribute of the compilation unit.
     ///
     /// In the unlikely event that we are unable to retrieve the language, we assume Rust.
@@ -40,6 +68,24 @@ impl UnitInfo {
         unit_language
     }
 
+    /// Retrieve the value of the `DW_AT_producer` attribute of the compilation unit,
+    /// i.e. the name and version of the compiler, which often includes the options it was called with.
+    pub(crate) fn get_producer(&self, debug_info: &super::DebugInfo) -> Option<String> {
+        let mut tree = self
+            .unit
+            .header
+            .entries_tree(&self.unit.abbreviations, None)
+            .ok()?;
+        let producer = tree
+            .root()
+            .ok()?
+            .entry()
+            .attr_value(gimli::DW_AT_producer)
+            .ok()??;
+        let producer = debug_info.dwarf.attr_string(&self.unit, producer).ok()?;
+        producer.to_string().ok().map(String::from)
+    }
+
     /// Get the DIEs for the function containing the given address.
     ///
     /// If `find_inlined` is `false`, then the result will contain a single [`FunctionDie`]
@@ -186,14 +232,28 @@ impl UnitInfo {
     ) -> Result<Variable, DebugError> {
         // Identify the parent.
         child_variable.parent_key = parent_variable.variable_key;
+        if tree_node.entry().tag() == gimli::DW_TAG_member {
+            // The members of a `volatile` struct are also `volatile`.
+            child_variable.is_volatile = parent_variable.is_volatile;
+        }
+        // The parameters of an inlined function instance only describe the location,
+        // and take their name and type from the parameters of the abstract origin below.
+        child_variable.is_parameter = tree_node.entry().tag() == gimli::DW_TAG_formal_parameter;
 
-        // We need to determine if we are working with a 'abstract` location, and use that node for the attributes we need
-        let attributes_entry = if let Ok(Some(abstract_origin)) =
-            tree_node.entry().attr(gimli::DW_AT_abstract_origin)
-        {
-            match abstract_origin.value() {
+        // We need to determine if we are working with a 'abstract` location, or the definition of a declaration
+        // (e.g. a C++ static member), and use that node for the attributes we need
+        let origin_attribute = match tree_node.entry().attr(gimli::DW_AT_abstract_origin) {
+            Ok(Some(abstract_origin)) => Some(abstract_origin),
+            _ => tree_node
+                .entry()
+                .attr(gimli::DW_AT_specification)
+                .ok()
+                .flatten(),
+        };
+        let attributes_entry = if let Some(origin) = origin_attribute {
+            match origin.value() {
                 gimli::AttributeValue::UnitRef(unit_ref) => {
-                    // The abstract origin is a reference to another DIE, so we need to resolve that,
+                    // The origin is a reference to another DIE, so we need to resolve that,
                     // but first we need to process the (optional) memory location using the current DIE.
                     self.process_memory_location(
                         debug_info,
@@ -214,7 +274,8 @@ impl UnitInfo {
                 }
                 other_attribute_value => {
                     child_variable.set_value(VariableValue::Error(format!(
-                        "Unimplemented: Attribute Value for DW_AT_abstract_origin {other_attribute_value:?}"
+                        "Unimplemented: Attribute Value for {} {other_attribute_value:?}",
+                        origin.name()
                     )));
                     None
                 }
@@ -234,8 +295,23 @@ impl UnitInfo {
             child_variable.name = VariableName::Named(extract_name(debug_info, name));
         }
 
+        // The declaration of a static member, or of a namespaced global that is defined outside of its namespace,
+        // is nested in the types and namespaces that qualify its name.
+        if let (Ok(Some(_)), Some(declaration), VariableName::Named(name)) = (
+            tree_node.entry().attr(gimli::DW_AT_specification),
+            attributes_entry.as_ref(),
+            &child_variable.name,
+        ) {
+            child_variable.name =
+                VariableName::Named(self.qualified_name(debug_info, declaration.offset(), name)?);
+        }
+
         if let Some(attributes_entry) = attributes_entry {
             let mut variable_attributes = attributes_entry.attrs();
+            let mut is_artificial = false;
+            // `DW_AT_count` is relative to the `DW_AT_lower_bound`, which may follow it,
+            // so the end of the range of a subrange is known after all its attributes are read.
+            let mut subrange_end = None;
 
             // Now loop through all the unit attributes to extract the remainder of the `Variable` definition.
             while let Ok(Some(attr)) = variable_attributes.next() {
@@ -372,21 +448,28 @@ impl UnitInfo {
                         }
                     },
                     gimli::DW_AT_const_value => match attr.value() {
-                        gimli::AttributeValue::Udata(const_value) => {
+                        // Signed constants, including DWARF 5 `DW_FORM_implicit_const` values, which may be negative.
+                        gimli::AttributeValue::Sdata(const_value) => {
                             child_variable.set_value(VariableValue::Valid(const_value.to_string()));
                         }
-                        other_attribute_value => {
-                            child_variable.set_value(VariableValue::Error(format!(
-                                "Unimplemented: Attribute Value for DW_AT_const_value: {other_attribute_value:?}"
-                            )));
-                        }
+                        other_attribute_value => match extract_constant(&other_attribute_value) {
+                            Some(const_value) => {
+                                child_variable
+                                    .set_value(VariableValue::Valid(const_value.to_string()));
+                            }
+                            None => {
+                                child_variable.set_value(VariableValue::Error(format!(
+                                    "Unimplemented: Attribute Value for DW_AT_const_value: {other_attribute_value:?}"
+                                )));
+                            }
+                        },
                     },
                     gimli::DW_AT_alignment => {
                         // TODO: Figure out when (if at all) we need to do anything with DW_AT_alignment for the purposes of decoding data values.
                     }
                     gimli::DW_AT_artificial => {
-                        // These are references for entries like discriminant values of `VariantParts`.
-                        child_variable.name = VariableName::Artifical;
+                        // Processed after all the other attributes, which still need the name of the variable.
+                        is_artificial = true;
                     }
                     gimli::DW_AT_discr => match attr.value() {
                         // This calculates the active discriminant value for the `VariantPart`.
@@ -410,16 +493,25 @@ impl UnitInfo {
                                 cache,
                                 frame_info,
                             )?;
-                            if !discriminant_variable.is_valid() {
-                                parent_variable.role = VariantRole::VariantPart(u64::MAX);
+                            parent_variable.discriminant_value = if discriminant_variable.is_valid()
+                            {
+                                discriminant_bits(
+                                    &discriminant_variable.get_value(cache),
+                                    discriminant_variable.byte_size,
+                                )
                             } else {
-                                parent_variable.role = VariantRole::VariantPart(
-                                    discriminant_variable
-                                        .get_value(cache)
-                                        .parse()
-                                        .unwrap_or(u64::MAX),
-                                );
-                            }
+                                None
+                            };
+                            parent_variable.role = match parent_variable.discriminant_value {
+                                Some(discriminant) => {
+                                    VariantRole::VariantPart(self.active_variant_discriminant(
+                                        tree_node.entry().offset(),
+                                        discriminant,
+                                        discriminant_variable.byte_size,
+                                    )?)
+                                }
+                                None => VariantRole::VariantPart(None),
+                            };
                             cache.remove_cache_entry(discriminant_variable.variable_key)?;
                         }
                         other_attribute_value => {
@@ -440,13 +532,12 @@ impl UnitInfo {
                     },
                     // Property of variables that are of DW_TAG_subrange_type.
                     gimli::DW_AT_upper_bound | gimli::DW_AT_count => {
-                        match attr.value().udata_value() {
-                            Some(upper_bound) => {
-                                child_variable.range_upper_bound = upper_bound as i64
-                            }
-                            None => {
+                        match self.array_bound(debug_info, attr.value(), memory, frame_info) {
+                            Ok(bound) => subrange_end = Some((attr.name(), bound)),
+                            Err(error) => {
                                 child_variable.set_value(VariableValue::Error(format!(
-                                    "Unimplemented: Attribute Value for DW_AT_upper_bound: {:?}",
+                                    "Unable to resolve {} {:?}: {error}",
+                                    attr.name(),
                                     attr.value()
                                 )));
                             }
@@ -471,7 +562,12 @@ impl UnitInfo {
                     gimli::DW_AT_byte_size => {
                         // Processed by `extract_byte_size()`.
                     }
-                    gimli::DW_AT_abstract_origin => {
+                    gimli::DW_AT_bit_size
+                    | gimli::DW_AT_data_bit_offset
+                    | gimli::DW_AT_bit_offset => {
+                        // Processed by `process_memory_location()`.
+                    }
+                    gimli::DW_AT_abstract_origin | gimli::DW_AT_specification => {
                         // Processed before looping through all attributes
                     }
                     gimli::DW_AT_linkage_name => {
@@ -480,6 +576,10 @@ impl UnitInfo {
                     gimli::DW_AT_address_class => {
                         // Processed by `extract_type()`
                     }
+                    gimli::DW_AT_GNU_locviews => {
+                        // GCC's view numbers for the location list entries, which only matter
+                        // when several entries apply to the same address.
+                    }
                     other_attribute => {
                         #[allow(clippy::format_in_format_args)]
                         // This follows the examples of the "format!" documenation as the way to limit string length of a {:?} parameter.
@@ -492,6 +592,35 @@ impl UnitInfo {
                     }
                 }
             }
+
+            match subrange_end {
+                // The upper bound is inclusive, while the range of members excludes it.
+                Some((gimli::DW_AT_upper_bound, upper_bound)) => {
+                    child_variable.range_upper_bound = upper_bound as i64 + 1
+                }
+                Some((_, count)) => {
+                    child_variable.range_upper_bound =
+                        child_variable.range_lower_bound + count as i64
+                }
+                None => {}
+            }
+
+            if is_artificial {
+                match attributes_entry.tag() {
+                    gimli::DW_TAG_variable | gimli::DW_TAG_formal_parameter => {
+                        // Compiler generated variables, like the `this` pointer of a C++ method.
+                        if !debug_info.artificial_variables.shows(&child_variable.name) {
+                            child_variable.name = VariableName::Artifical;
+                        } else if debug_info.artificial_variables == ArtificialVariables::Mark {
+                            child_variable.is_artificial = true;
+                        }
+                    }
+                    _ => {
+                        // These are references for entries like discriminant values of `VariantParts`.
+                        child_variable.name = VariableName::Artifical;
+                    }
+                }
+            }
         }
         cache.update_variable_and_value(&mut child_variable, memory)?;
 
@@ -546,12 +675,15 @@ impl UnitInfo {
                         VariableName::Namespace(extract_name(debug_info, attr.value()))
                     } else { VariableName::AnonymousNamespace };
                     namespace_variable.type_name = VariableType::Namespace;
-                    namespace_variable.memory_location = VariableLocation::Unavailable;
+                    namespace_variable.memory_location = VariableLocation::Unavailable(UnavailableReason::NoLocation);
                     cache.add_variable(parent_variable.variable_key, &mut namespace_variable)?;
 
                     let mut namespace_children_nodes = child_node.children();
                     while let Some(mut namespace_child_node) = namespace_children_nodes.next()? {
                         match namespace_child_node.entry().tag() {
+                            gimli::DW_TAG_variable if is_declaration(namespace_child_node.entry()) => {
+                                // The definition refers to this declaration with `DW_AT_specification`.
+                            }
                             gimli::DW_TAG_variable => {
                                 // We only want the TOP level variables of the namespace (statics).
                                 let static_child_variable = cache.create_variable(
@@ -578,7 +710,7 @@ impl UnitInfo {
 
                                 } else { VariableName::AnonymousNamespace};
                                 namespace_child_variable.type_name = VariableType::Namespace;
-                                namespace_child_variable.memory_location = VariableLocation::Unavailable;
+                                namespace_child_variable.memory_location = VariableLocation::Unavailable(UnavailableReason::NoLocation);
                                 cache.add_variable(namespace_variable.variable_key, &mut namespace_child_variable)?;
                                 namespace_child_variable = self.process_tree(debug_info, namespace_child_node, namespace_child_variable, memory, cache, frame_info)?;
                                 if !cache.has_children(&namespace_child_variable)? {
@@ -630,11 +762,11 @@ impl UnitInfo {
                         Some(self),
                     )?;
                     // To determine the discriminant, we use the following rules:
-                    // - If there is no DW_AT_discr, then there will be a single DW_TAG_variant, and this will be the matching value. In the code here, we assign a default value of u64::MAX to both, so that they will be matched as belonging together (https://dwarfstd.org/ShowIssue.php?issue=180517.2)
+                    // - If there is no DW_AT_discr, then there will be a single DW_TAG_variant, and this will be the matching value. In the code here, we assign no discriminant (`None`) to both, so that they will be matched as belonging together (https://dwarfstd.org/ShowIssue.php?issue=180517.2)
                     // - TODO: The [DWARF] standard, 5.7.10, allows for a case where there is no DW_AT_discr attribute, but a DW_AT_type to represent the tag. I have not seen that generated from RUST yet.
                     // - If there is a DW_AT_discr that has a value, then this is a reference to the member entry for the discriminant. This value will be resolved to match against the appropriate DW_TAG_variant.
                     // - TODO: The [DWARF] standard, 5.7.10, allows for a DW_AT_discr_list, but I have not seen that generated from RUST yet. 
-                    parent_variable.role = VariantRole::VariantPart(u64::MAX);
+                    parent_variable.role = VariantRole::VariantPart(None);
                     child_variable = self.process_tree_node_attributes(debug_info, &mut child_node, &mut parent_variable, child_variable, memory, cache, frame_info)?;
                     // At this point we have everything we need (It has updated the parent's `role`) from the child_variable, so elimnate it before we continue ...
                     cache.remove_cache_entry(child_variable.variable_key)?;
@@ -653,12 +785,17 @@ impl UnitInfo {
                         child_variable = self.process_tree_node_attributes(debug_info, &mut child_node, &mut parent_variable, child_variable, memory, cache, frame_info)?;
                         if child_variable.is_valid() {
                             if let VariantRole::Variant(discriminant) = child_variable.role {
-                                // Only process the discriminant variants or when we eventually   encounter the default 
-                                if parent_variable.role == VariantRole::VariantPart(discriminant) || discriminant == u64::MAX {
+                                // Only process the active variant, which is the default variant (`None`) if no other variant matches the discriminant.
+                                if parent_variable.role == VariantRole::VariantPart(discriminant) {
                                     self.process_memory_location(debug_info, child_node.entry(), &parent_variable, &mut child_variable, memory, frame_info)?;
                                     // Recursively process each relevant child node.
                                     child_variable = self.process_tree(debug_info, child_node, child_variable, memory, cache, frame_info)?;
                                     if child_variable.is_valid() {
+                                        // The member of the active `DW_TAG_variant` is named after the variant, e.g. `Err`.
+                                        parent_variable.active_variant_name = cache
+                                            .get_children(child_variable.variable_key)?
+                                            .first()
+                                            .map(|variant| variant.name.to_string());
                                         // Eliminate intermediate DWARF nodes, but keep their children
                                         cache.adopt_grand_children(&parent_variable, &child_variable)?;
                                     }
@@ -702,8 +839,7 @@ impl UnitInfo {
                             = child_node.entry().attr(gimli::DW_AT_high_pc) {
                                 match high_pc_attr.value() {
                                     gimli::AttributeValue::Addr(addr) => addr,
-                                    gimli::AttributeValue::Udata(unsigned_offset) => low_pc + unsigned_offset,
-                                    _other => 0_u64,
+                                    other => extract_constant(&other).map_or(0_u64, |unsigned_offset| low_pc + unsigned_offset),
                                 }
                         } else { 0_u64};
                         if low_pc == u64::MAX || high_pc == 0_u64 {
@@ -763,6 +899,7 @@ impl UnitInfo {
                         gimli::DW_TAG_base_type |
                         gimli::DW_TAG_pointer_type |
                         gimli::DW_TAG_structure_type |
+                        gimli::DW_TAG_class_type |
                         gimli::DW_TAG_enumeration_type |
                         gimli::DW_TAG_array_type |
                         gimli::DW_TAG_subroutine_type |
@@ -793,20 +930,23 @@ impl UnitInfo {
             variable.role = match node.entry().attr(gimli::DW_AT_discr_value) {
                 Ok(optional_discr_value_attr) => {
                     match optional_discr_value_attr {
-                        Some(discr_attr) => {
-                            match discr_attr.value() {
-                                gimli::AttributeValue::Data1(const_value) => {
-                                    VariantRole::Variant(const_value as u64)
-                                }
-                                other_attribute_value => {
-                                    variable.set_value(VariableValue::Error(format!("Unimplemented: Attribute Value for DW_AT_discr_value: {:.100}", format!("{other_attribute_value:?}"))));
-                                    VariantRole::Variant(u64::MAX)
+                        Some(discr_attr) => match discr_attr.value() {
+                            gimli::AttributeValue::Sdata(const_value) => {
+                                VariantRole::Variant(Some(const_value as u64))
+                            }
+                            other_attribute_value => {
+                                match extract_constant(&other_attribute_value) {
+                                    Some(const_value) => VariantRole::Variant(Some(const_value)),
+                                    None => {
+                                        variable.set_value(VariableValue::Error(format!("Unimplemented: Attribute Value for DW_AT_discr_value: {:.100}", format!("{other_attribute_value:?}"))));
+                                        VariantRole::Variant(None)
+                                    }
                                 }
                             }
-                        }
+                        },
                         None => {
                             // In the case where the variable is a DW_TAG_variant, but has NO DW_AT_discr_value, then this is the "default" to be used.
-                            VariantRole::Variant(u64::MAX)
+                            VariantRole::Variant(None)
                         }
                     }
                 }
@@ -821,6 +961,46 @@ impl UnitInfo {
         Ok(())
     }
 
+    /// Select the `DW_TAG_variant` child of the `DW_TAG_variant_part` at `variant_part_offset` that matches
+    /// the `discriminant` value, which was read from the `DW_AT_discr` member of the variant part.
+    ///
+    /// The discriminant and the `DW_AT_discr_value` of the variants are compared in the `byte_size` of the discriminant,
+    /// so that signed discriminants (e.g. `-1_i8`) match their (unsigned) encoding in the debug information.
+    /// Returns the discriminant of the matching variant, as it is assigned by [`Self::extract_variant_discriminant`],
+    /// or `None` to select the default variant, if no other variant matches.
+    fn active_variant_discriminant(
+        &self,
+        variant_part_offset: UnitOffset,
+        discriminant: u64,
+        byte_size: Option<u64>,
+    ) -> Result<Option<u64>, DebugError> {
+        let mask = discriminant_mask(byte_size);
+
+        let mut tree = self
+            .unit
+            .header
+            .entries_tree(&self.unit.abbreviations, Some(variant_part_offset))?;
+        let mut variants = tree.root()?.children();
+        while let Some(variant) = variants.next()? {
+            if variant.entry().tag() != gimli::DW_TAG_variant {
+                continue;
+            }
+            let discr_value = match variant.entry().attr_value(gimli::DW_AT_discr_value)? {
+                Some(gimli::AttributeValue::Sdata(value)) => value as u64,
+                Some(other_attribute_value) => match extract_constant(&other_attribute_value) {
+                    Some(value) => value,
+                    None => continue,
+                },
+                None => continue,
+            };
+            if discr_value & mask == discriminant {
+                return Ok(Some(discr_value));
+            }
+        }
+
+        Ok(None)
+    }
+
     /// Compute the type (base to complex) of a variable. Only base types have values.
     /// Complex types are references to node trees, that require traversal in similar ways to other DIE's like functions.
     /// This means both [`get_function_variables()`] and [`extract_type()`] will call the recursive [`process_tree()`] method to build an integrated `tree` of variables with types and values.
@@ -854,6 +1034,38 @@ impl UnitInfo {
             return Ok(child_variable);
         }
 
+        let is_incomplete_type = is_declaration(node.entry());
+        if is_incomplete_type {
+            // The type is only declared in this unit (e.g. an opaque `struct`), so we look for the definition in the other units.
+            if let Some((definition_unit_info, definition_offset)) = match &type_name {
+                Some(type_name) => {
+                    debug_info.find_type_definition(node.entry().tag(), type_name)?
+                }
+                None => None,
+            } {
+                let mut type_tree = definition_unit_info.unit.header.entries_tree(
+                    &definition_unit_info.unit.abbreviations,
+                    Some(definition_offset),
+                )?;
+                let definition_node = type_tree.root()?;
+                // Children of the type will be resolved from the unit that contains the definition.
+                child_variable.unit_header_offset = definition_unit_info
+                    .unit
+                    .header
+                    .offset()
+                    .as_debug_info_offset();
+                return definition_unit_info.extract_type(
+                    debug_info,
+                    definition_node,
+                    parent_variable,
+                    child_variable,
+                    memory,
+                    cache,
+                    frame_info,
+                );
+            }
+        }
+
         match node.entry().tag() {
             gimli::DW_TAG_base_type => {
                 child_variable.type_name =
@@ -866,6 +1078,19 @@ impl UnitInfo {
                     memory,
                     frame_info,
                 )?;
+
+                if let VariableLocation::Pieces(pieces) = &child_variable.memory_location {
+                    // The value is stored in a register, rather than in memory.
+                    match self.base_type_value_in_pieces(debug_info, node.entry(), pieces, 0)? {
+                        ExpressionResult::Value(value) => {
+                            child_variable.memory_location = VariableLocation::Value;
+                            child_variable.set_value(value);
+                        }
+                        ExpressionResult::Location(location) => {
+                            child_variable.set_value(VariableValue::Error(location.to_string()));
+                        }
+                    }
+                }
             }
             gimli::DW_TAG_pointer_type => {
                 child_variable.type_name = VariableType::Pointer(type_name);
@@ -889,13 +1114,12 @@ impl UnitInfo {
                                 if let VariableType::Pointer(optional_name) =
                                     &child_variable.type_name
                                 {
-                                    #[allow(clippy::unwrap_used)]
-                                    // Use of `unwrap` below is safe because we first check for `is_none()`.
                                     if optional_name.is_none()
-                                        || optional_name.as_ref().unwrap().starts_with("*const")
-                                        || optional_name.as_ref().unwrap().starts_with("*mut")
+                                        || child_variable.type_name.is_raw_pointer()
+                                        || child_variable.type_name.is_reference()
                                     {
-                                        // Resolve the children of this variable, because they contain essential information required to resolve the value
+                                        // Resolve the children of this variable, because they contain essential information required to resolve the value,
+                                        // e.g. the referenced variable, because references are automatically dereferenced when displaying their value.
                                         debug_info.cache_deferred_variables(
                                             cache,
                                             memory,
@@ -935,7 +1159,8 @@ impl UnitInfo {
                     }
                 }
             }
-            gimli::DW_TAG_structure_type => {
+            // C++ classes only differ from structs in the default accessibility of their members.
+            gimli::DW_TAG_structure_type | gimli::DW_TAG_class_type => {
                 child_variable.type_name =
                     VariableType::Struct(type_name.unwrap_or_else(|| "<unnamed>".to_string()));
                 self.process_memory_location(
@@ -947,7 +1172,10 @@ impl UnitInfo {
                     frame_info,
                 )?;
 
-                if child_variable.memory_location != VariableLocation::Unavailable {
+                if !matches!(
+                    child_variable.memory_location,
+                    VariableLocation::Unavailable(_)
+                ) {
                     if let VariableType::Struct(name) = &child_variable.type_name {
                         // The default behaviour is to defer the processing of child types.
                         child_variable.variable_node_type =
@@ -996,16 +1224,27 @@ impl UnitInfo {
                 if parent_variable.is_valid() && child_variable.is_valid() {
                     let enumerator_values = cache.get_children(child_variable.variable_key)?;
 
-                    let value = if let VariableLocation::Address(address) =
-                        child_variable.memory_location
-                    {
-                        // NOTE: hard-coding value of variable.byte_size to 1 ... replace with code if necessary.
-                        let mut buff = 0u8;
-                        memory.read(address, std::slice::from_mut(&mut buff))?;
-                        let this_enum_const_value = buff.to_string();
+                    // Enumerator values, e.g. `DW_FORM_implicit_const` baud rates, don't always fit in a byte.
+                    let byte_size = child_variable.byte_size.unwrap_or(1).clamp(1, 8) as usize;
+                    let unsigned_value = match &child_variable.memory_location {
+                        VariableLocation::Address(address) => {
+                            let mut buff = [0u8; 8];
+                            memory.read(*address, &mut buff[..byte_size])?;
+                            Some(u64::from_le_bytes(buff))
+                        }
+                        VariableLocation::Pieces(pieces) => {
+                            value_in_pieces(pieces, 0, byte_size as u64, debug_info.endianness)
+                        }
+                        _ => None,
+                    };
+                    let value = if let Some(unsigned_value) = unsigned_value {
+                        let sign_shift = 64 - 8 * byte_size as u32;
+                        let signed_value = ((unsigned_value << sign_shift) as i64) >> sign_shift;
+                        let this_enum_const_values =
+                            [unsigned_value.to_string(), signed_value.to_string()];
 
                         let is_this_value = |enumerator_variable: &Variable| {
-                            enumerator_variable.get_value(cache) == this_enum_const_value
+                            this_enum_const_values.contains(&enumerator_variable.get_value(cache))
                         };
 
                         let enumumerator_value =
@@ -1103,7 +1342,11 @@ impl UnitInfo {
                                 } else {
                                     // - Next, process this DW_TAG_array_type's DW_AT_type full tree.
                                     // - We have to do this repeatedly, for every array member in the range.
-                                    for array_member_index in child_variable.subrange_bounds() {
+                                    // - Only up to `max_array_elements` members are resolved, because the length may be corrupt.
+                                    for array_member_index in child_variable
+                                        .subrange_bounds()
+                                        .take(debug_info.max_array_elements)
+                                    {
                                         self.expand_array_member(
                                             debug_info,
                                             unit_ref,
@@ -1114,6 +1357,22 @@ impl UnitInfo {
                                             frame_info,
                                         )?;
                                     }
+                                    let omitted_members = (child_variable.range_upper_bound
+                                        - child_variable.range_lower_bound)
+                                        .saturating_sub(debug_info.max_array_elements as i64);
+                                    if omitted_members > 0 {
+                                        let mut truncation_marker = cache.create_variable(
+                                            child_variable.variable_key,
+                                            None,
+                                            Some(self),
+                                        )?;
+                                        truncation_marker.name =
+                                            VariableName::Named("...".to_string());
+                                        truncation_marker.set_value(VariableValue::Valid(format!(
+                                            "<{omitted_members} more elements>"
+                                        )));
+                                        cache.update_variable(&truncation_marker)?;
+                                    }
                                 }
                             }
                             other_attribute_value => {
@@ -1213,6 +1472,9 @@ impl UnitInfo {
             | gimli::DW_TAG_volatile_type) => match node.entry().attr(gimli::DW_AT_type) {
                 Ok(Some(data_type_attribute)) => match data_type_attribute.value() {
                     gimli::AttributeValue::UnitRef(unit_ref) => {
+                        if other == gimli::DW_TAG_volatile_type {
+                            child_variable.is_volatile = true;
+                        }
                         child_variable = self.expand_indirect_type(
                             debug_info,
                             unit_ref,
@@ -1251,6 +1513,15 @@ impl UnitInfo {
             }
         }
 
+        if is_incomplete_type && child_variable.is_valid() {
+            // There is no definition of this type anywhere, so we cannot show its members.
+            child_variable.set_value(VariableValue::Valid(format!(
+                "<incomplete type {}>",
+                child_variable.type_name
+            )));
+            child_variable.variable_node_type = VariableNodeType::DoNotRecurse;
+        }
+
         cache.update_variable_and_value(&mut child_variable, memory)?;
 
         Ok(child_variable)
@@ -1282,6 +1553,7 @@ impl UnitInfo {
         // Override the calculated member name with a more 'array-like' name.
         array_member_variable.name = VariableName::Named(format!("__{array_member_index}"));
         array_member_variable.source_location = child_variable.source_location.clone();
+        array_member_variable.is_volatile = child_variable.is_volatile;
         self.process_memory_location(
             debug_info,
             array_member_type_node.entry(),
@@ -1314,6 +1586,7 @@ impl UnitInfo {
             child_variable.set_value(VariableValue::Empty);
         }
         self.handle_memory_location_special_cases(
+            debug_info,
             unit_ref,
             &mut array_member_variable,
             child_variable,
@@ -1364,6 +1637,7 @@ impl UnitInfo {
         )?;
 
         self.handle_memory_location_special_cases(
+            debug_info,
             unit_ref,
             &mut child_variable,
             parent_variable,
@@ -1386,9 +1660,9 @@ impl UnitInfo {
     ) -> Result<(), DebugError> {
         // The `byte_size` is used for arrays, etc. to offset the memory location of the next element.
         // For nested types, the `byte_size` may need to be calculated as the product of the `byte_size` and array upper bound.
-        child_variable.byte_size = child_variable
-            .byte_size
-            .or_else(|| extract_byte_size(node_die))
+        // A `byte_size` on the DIE itself always takes precedence over an inherited value.
+        child_variable.byte_size = extract_byte_size(node_die)
+            .or(child_variable.byte_size)
             .or_else(|| {
                 parent_variable.byte_size.map(|byte_size| {
                     let array_member_count = parent_variable.subrange_bounds().count() as u64;
@@ -1432,10 +1706,9 @@ impl UnitInfo {
                     child_variable.set_value(value_from_expression);
                 }
 
-                ExpressionResult::Location(VariableLocation::Unavailable) => {
-                    child_variable.set_value(VariableValue::Error(
-                        "<value optimized away by compiler, out of scope, or dropped>".to_string(),
-                    ));
+                ExpressionResult::Location(VariableLocation::Unavailable(reason)) => {
+                    child_variable.set_value(VariableValue::Error(reason.to_string()));
+                    child_variable.memory_location = VariableLocation::Unavailable(reason);
                 }
 
                 ExpressionResult::Location(
@@ -1453,7 +1726,25 @@ impl UnitInfo {
             }
         }
 
+        // Bitfield members are located at a bit offset from the start of the containing variable.
+        let data_member_location = node_die
+            .attr(gimli::DW_AT_data_member_location)?
+            .and_then(|attribute| extract_constant(&attribute.value()));
+        if let Some(bit_field) =
+            bit_field_layout(node_die, data_member_location, extract_byte_size(node_die))?
+        {
+            if let VariableLocation::Address(address) = parent_variable.memory_location {
+                child_variable.memory_location =
+                    VariableLocation::Address(address + bit_field.bit_offset / 8);
+                child_variable.bit_field = Some(BitFieldLayout {
+                    bit_offset: bit_field.bit_offset % 8,
+                    bit_size: bit_field.bit_size,
+                });
+            }
+        }
+
         self.handle_memory_location_special_cases(
+            debug_info,
             node_die.offset(),
             child_variable,
             parent_variable,
@@ -1487,7 +1778,9 @@ impl UnitInfo {
                     Ok(result) => Ok(result),
                     Err(DebugError::UnwindIncompleteResults { message }) => {
                         tracing::warn!("UnwindIncompleteResults: {:?}", message);
-                        Ok(ExpressionResult::Location(VariableLocation::Unavailable))
+                        Ok(ExpressionResult::Location(VariableLocation::Unavailable(
+                            UnavailableReason::OptimizedOut,
+                        )))
                     }
                     e => e,
                 }
@@ -1500,12 +1793,41 @@ impl UnitInfo {
                 gimli::DW_AT_location
                 | gimli::DW_AT_frame_base
                 | gimli::DW_AT_data_member_location => match attr.value() {
+                    gimli::AttributeValue::Exprloc(expression)
+                        if attr.name() == gimli::DW_AT_data_member_location =>
+                    {
+                        // The expression starts with the address of the containing object on the stack,
+                        // which is also the object address for `DW_OP_push_object_address`.
+                        match parent_location {
+                            VariableLocation::Address(address) => self
+                                .evaluate_expression(
+                                    debug_info,
+                                    memory,
+                                    expression,
+                                    Some(*address),
+                                    frame_info,
+                                )
+                                .convert_incomplete()?,
+                            other => ExpressionResult::Location(other.clone()),
+                        }
+                    }
                     gimli::AttributeValue::Exprloc(expression) => self
-                        .evaluate_expression(memory, expression, frame_info)
+                        .evaluate_expression(debug_info, memory, expression, None, frame_info)
                         .convert_incomplete()?,
 
-                    gimli::AttributeValue::Udata(offset_from_location) => {
-                        let location = match parent_location {
+                    ref offset_value @ (gimli::AttributeValue::Udata(_)
+                    | gimli::AttributeValue::Sdata(_)
+                    | gimli::AttributeValue::Data1(_)
+                    | gimli::AttributeValue::Data2(_)
+                    | gimli::AttributeValue::Data4(_)
+                    | gimli::AttributeValue::Data8(_)) => {
+                        // A constant offset, which may be stored as a DWARF 5 `DW_FORM_implicit_const`.
+                        let Some(offset_from_location) = extract_constant(offset_value) else {
+                            return Err(DebugError::UnwindIncompleteResults {
+                                message: format!("Invalid negative offset {offset_value:?} for variable location"),
+                            });
+                        };
+                        match parent_location {
                             VariableLocation::Address(address) => {
                                 let (location, has_overflowed) =
                                     address.overflowing_add(offset_from_location);
@@ -1516,12 +1838,16 @@ impl UnitInfo {
                                     });
                                 }
 
-                                VariableLocation::Address(location)
+                                ExpressionResult::Location(VariableLocation::Address(location))
                             }
-                            other => other.clone(),
-                        };
-
-                        ExpressionResult::Location(location)
+                            VariableLocation::Pieces(pieces) => self.member_location_in_pieces(
+                                debug_info,
+                                node_die,
+                                pieces,
+                                offset_from_location,
+                            )?,
+                            other => ExpressionResult::Location(other.clone()),
+                        }
                     }
 
                     gimli::AttributeValue::LocationListsRef(location_list_offset) => self
@@ -1583,6 +1909,22 @@ impl UnitInfo {
         frame_info: StackFrameInfo<'_>,
         memory: &mut dyn MemoryInterface,
     ) -> Result<ExpressionResult, DebugError> {
+        match self.location_list_expression(debug_info, location_list_offset, frame_info) {
+            Ok(expression) => {
+                self.evaluate_expression(debug_info, memory, expression, None, frame_info)
+            }
+            Err(location) => Ok(ExpressionResult::Location(location)),
+        }
+    }
+
+    /// Find the expression of the location list entry that covers the program counter of `frame_info`,
+    /// or the [`VariableLocation`] that explains why there is none.
+    fn location_list_expression(
+        &self,
+        debug_info: &DebugInfo,
+        location_list_offset: gimli::LocationListsOffset,
+        frame_info: StackFrameInfo<'_>,
+    ) -> Result<gimli::Expression<GimliReader>, VariableLocation> {
         let mut locations = match debug_info.locations_section.locations(
             location_list_offset,
             self.unit.header.encoding(),
@@ -1592,8 +1934,9 @@ impl UnitInfo {
         ) {
             Ok(locations) => locations,
             Err(error) => {
-                return Ok(ExpressionResult::Location(VariableLocation::Error(
-                    format!("Error: Resolving variable Location: {:?}", error),
+                return Err(VariableLocation::Error(format!(
+                    "Error: Resolving variable Location: {:?}",
+                    error
                 )))
             }
         };
@@ -1602,9 +1945,9 @@ impl UnitInfo {
             .get_program_counter()
             .and_then(|reg| reg.value)
         else {
-            return Ok(ExpressionResult::Location(VariableLocation::Error(
+            return Err(VariableLocation::Error(
                 "Cannot determine variable location without a valid program counter.".to_string(),
-            )));
+            ));
         };
 
         let mut expression = None;
@@ -1613,8 +1956,8 @@ impl UnitInfo {
                 Ok(Some(location_lists_entry)) => location_lists_entry,
                 Ok(None) => break 'find_range,
                 Err(error) => {
-                    return Ok(ExpressionResult::Location(VariableLocation::Error(
-                        format!("Error while iterating LocationLists for this variable: {error:?}"),
+                    return Err(VariableLocation::Error(format!(
+                        "Error while iterating LocationLists for this variable: {error:?}"
                     )));
                 }
             };
@@ -1627,11 +1970,166 @@ impl UnitInfo {
             }
         }
 
-        let Some(valid_expression) = expression else {
-            return Ok(ExpressionResult::Location(VariableLocation::Unavailable));
+        // If the location list does not cover the program counter,
+        // the variable is not live here, even if it is in scope.
+        expression.ok_or(VariableLocation::Unavailable(
+            UnavailableReason::NotLiveHere,
+        ))
+    }
+
+    /// Get the address ranges where the variable at `variable_offset` has a valid location.
+    ///
+    /// - For a location list, this is one range per entry that describes a location,
+    ///   so a variable that moves between registers has a range for each register.
+    /// - For a single location, or a constant value, this is the scope of the variable.
+    /// - Without a location, the variable is never live, and the list is empty.
+    pub(crate) fn variable_liveness_ranges(
+        &self,
+        debug_info: &DebugInfo,
+        variable_offset: UnitOffset,
+    ) -> Result<Vec<std::ops::Range<u64>>, DebugError> {
+        let variable_die = self.unit.entry(variable_offset)?;
+
+        let location = variable_die.attr_value(gimli::DW_AT_location)?;
+        let location_list_offset = match location {
+            Some(gimli::AttributeValue::LocationListsRef(location_list_offset)) => {
+                Some(location_list_offset)
+            }
+            // `DW_FORM_loclistx`, an index into the offsets of the location lists of the unit.
+            Some(gimli::AttributeValue::DebugLocListsIndex(index)) => {
+                Some(debug_info.dwarf.locations_offset(&self.unit, index)?)
+            }
+            _ => None,
         };
+        if let Some(location_list_offset) = location_list_offset {
+            let mut locations = debug_info.locations_section.locations(
+                location_list_offset,
+                self.unit.header.encoding(),
+                self.unit.low_pc,
+                &debug_info.address_section,
+                self.unit.addr_base,
+            )?;
+            let mut ranges = Vec::new();
+            while let Some(location) = locations.next()? {
+                // An empty expression means the variable has no location in this range.
+                if !location.data.0.is_empty() {
+                    ranges.push(location.range.begin..location.range.end);
+                }
+            }
+            return Ok(ranges);
+        }
+
+        if location.is_none() && variable_die.attr(gimli::DW_AT_const_value)?.is_none() {
+            return Ok(Vec::new());
+        }
+        self.scope_ranges(debug_info, variable_offset)
+    }
+
+    /// Get the address ranges of the innermost scope (function, inlined function, lexical block,
+    /// or the unit itself) that contains the DIE at `offset`.
+    fn scope_ranges(
+        &self,
+        debug_info: &DebugInfo,
+        offset: UnitOffset,
+    ) -> Result<Vec<std::ops::Range<u64>>, DebugError> {
+        for ancestor_offset in self.ancestors(offset)?.into_iter().rev() {
+            let ancestor = self.unit.entry(ancestor_offset)?;
+            if !matches!(
+                ancestor.tag(),
+                gimli::DW_TAG_compile_unit
+                    | gimli::DW_TAG_subprogram
+                    | gimli::DW_TAG_inlined_subroutine
+                    | gimli::DW_TAG_lexical_block
+            ) {
+                continue;
+            }
+
+            let mut ranges = Vec::new();
+            let mut die_ranges = debug_info.dwarf.die_ranges(&self.unit, &ancestor)?;
+            while let Some(range) = die_ranges.next()? {
+                ranges.push(range.begin..range.end);
+            }
+            // Scopes without addresses, e.g. abstract instances of inlined functions, are ignored.
+            if !ranges.is_empty() {
+                return Ok(ranges);
+            }
+        }
+        Ok(Vec::new())
+    }
+
+    /// Qualify `name` with the types and namespaces that enclose the DIE at `offset`, e.g. `MyClass::instance_count`.
+    pub(crate) fn qualified_name(
+        &self,
+        debug_info: &DebugInfo,
+        offset: UnitOffset,
+        name: &str,
+    ) -> Result<String, DebugError> {
+        let mut qualified_name = String::new();
+        for ancestor_offset in self.ancestors(offset)? {
+            let ancestor = self.unit.entry(ancestor_offset)?;
+            if !matches!(
+                ancestor.tag(),
+                gimli::DW_TAG_namespace
+                    | gimli::DW_TAG_structure_type
+                    | gimli::DW_TAG_class_type
+                    | gimli::DW_TAG_union_type
+            ) {
+                continue;
+            }
+            match ancestor.attr_value(gimli::DW_AT_name)? {
+                Some(ancestor_name) => {
+                    qualified_name.push_str(&extract_name(debug_info, ancestor_name))
+                }
+                None if ancestor.tag() == gimli::DW_TAG_namespace => {
+                    qualified_name.push_str("(anonymous namespace)")
+                }
+                None => qualified_name.push_str("<unnamed>"),
+            }
+            qualified_name.push_str("::");
+        }
+        qualified_name.push_str(name);
+        Ok(qualified_name)
+    }
+
+    /// Check if the DIE at `offset` is nested in a function, e.g. a local variable or a parameter,
+    /// rather than a static.
+    pub(crate) fn is_in_function(&self, offset: UnitOffset) -> Result<bool, DebugError> {
+        for ancestor_offset in self.ancestors(offset)? {
+            if matches!(
+                self.unit.entry(ancestor_offset)?.tag(),
+                gimli::DW_TAG_subprogram | gimli::DW_TAG_inlined_subroutine
+            ) {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Get the offsets of the DIEs that enclose the DIE at `offset`, starting with the unit DIE.
+    /// Returns an empty list if there is no DIE at `offset`.
+    fn ancestors(&self, offset: UnitOffset) -> Result<Vec<UnitOffset>, DebugError> {
+        // The offsets of the DIEs that enclose the current entry, with their depth in the tree.
+        let mut ancestors: Vec<(isize, UnitOffset)> = Vec::new();
+        let mut depth = 0;
+        let mut entries = self.unit.entries();
+        while let Some((depth_delta, entry)) = entries.next_dfs()? {
+            depth += depth_delta;
+            while ancestors
+                .last()
+                .is_some_and(|(ancestor_depth, _)| *ancestor_depth >= depth)
+            {
+                ancestors.pop();
+            }
 
-        self.evaluate_expression(memory, valid_expression, frame_info)
+            if entry.offset() == offset {
+                return Ok(ancestors
+                    .into_iter()
+                    .map(|(_, ancestor_offset)| ancestor_offset)
+                    .collect());
+            }
+            ancestors.push((depth, entry.offset()));
+        }
+        Ok(Vec::new())
     }
 
     /// Evaluate a gimli::Expression as a valid memory location.
@@ -1639,26 +2137,64 @@ impl UnitInfo {
     /// - Result<_, DebugError>: This happens when we encounter an error we did not expect, and will propagate upwards until the debugger request is failed. NOT GRACEFUL, and should be avoided.
     /// - Result<ExpressionResult::Value(),_>:  The value is statically stored in the binary, and can be returned, and has no relevant memory location.
     /// - Result<ExpressionResult::Location(),_>:  One of the variants of VariableLocation, and needs to be interpreted for handling the 'expected' errors we encounter during evaluation.
+    ///
+    /// The `object_address` is the address of the object that the expression describes, e.g. an array descriptor or
+    /// the struct that contains a member. It is the initial value of the stack, and is used by `DW_OP_push_object_address`.
     pub(crate) fn evaluate_expression(
         &self,
+        debug_info: &DebugInfo,
         memory: &mut dyn MemoryInterface,
         expression: gimli::Expression<GimliReader>,
+        object_address: Option<u64>,
         frame_info: StackFrameInfo<'_>,
     ) -> Result<ExpressionResult, DebugError> {
-        let pieces = self.expression_to_piece(memory, expression, frame_info)?;
+        // In the epilogue, the frame base may already have been restored to the value of the caller.
+        if frame_info.frame_torn_down
+            && self.has_operation(&expression, |operation| {
+                matches!(operation, gimli::Operation::FrameOffset { .. })
+            })?
+        {
+            return Ok(ExpressionResult::Location(VariableLocation::Unavailable(
+                UnavailableReason::FrameTornDown,
+            )));
+        }
+
+        if frame_info.registers_only {
+            // Don't evaluate expressions that read from memory, e.g. to follow a pointer.
+            if self.has_operation(&expression, |operation| {
+                matches!(operation, gimli::Operation::Deref { .. })
+            })? {
+                return Ok(ExpressionResult::Location(VariableLocation::Unavailable(
+                    UnavailableReason::NeedsMemory,
+                )));
+            }
+            return self.registers_only_location(
+                debug_info,
+                memory,
+                expression,
+                object_address,
+                frame_info,
+            );
+        }
+
+        let pieces =
+            self.expression_to_piece(debug_info, memory, expression, object_address, frame_info)?;
         if pieces.is_empty() {
             Ok(ExpressionResult::Location(VariableLocation::Error(
                 format!("Error: expr_to_piece() returned 0 results: {pieces:?}"),
             )))
         } else if pieces.len() > 1 {
-            Ok(ExpressionResult::Location(VariableLocation::Error(
-                "<unsupported memory implementation>".to_string(),
+            Ok(ExpressionResult::Location(pieces_location(
+                &pieces,
+                frame_info.registers,
             )))
         } else {
             match &pieces[0].location {
                 Location::Empty => {
                     // This means the value was optimized away.
-                    Ok(ExpressionResult::Location(VariableLocation::Unavailable))
+                    Ok(ExpressionResult::Location(VariableLocation::Unavailable(
+                        UnavailableReason::OptimizedOut,
+                    )))
                 }
                 Location::Address { address } => {
                     if address.is_zero() {
@@ -1669,7 +2205,7 @@ impl UnitInfo {
                                 *address,
                             )))
                         } else {
-                            Ok(ExpressionResult::Location(VariableLocation::Error(format!("The memory location for this variable value ({address:#010X}) is invalid. Please report this as a bug."))))
+                            Ok(ExpressionResult::Location(VariableLocation::Error(format!("The memory location for this variable value ({}) is invalid. Please report this as a bug.", format_address(*address, self.unit.header.address_size())))))
                         }
                     } else {
                         Ok(ExpressionResult::Location(VariableLocation::Address(
@@ -1713,38 +2249,21 @@ impl UnitInfo {
                     ))),
                 },
                 Location::Register { register } => {
-                    if let Some(address) = frame_info
+                    // The register holds the value of the variable, which is decoded with its type.
+                    let (location, value) = register_piece(*register, frame_info.registers);
+                    let byte_size = frame_info
                         .registers
                         .get_register_by_dwarf_id(register.0)
-                        .and_then(|register| register.value)
-                    {
-                        match address.try_into() {
-                            Ok(location) => {
-                                if !memory.supports_native_64bit_access() {
-                                    if location < u32::MAX as u64 {
-                                        Ok(ExpressionResult::Location(VariableLocation::Address(
-                                            location,
-                                        )))
-                                    } else {
-                                Ok(ExpressionResult::Location(VariableLocation::Error(format!("The memory location for this variable value ({location:#010X}) is invalid. Please report this as a bug."))))
-                                    }
-                                } else {
-                                    Ok(ExpressionResult::Location(VariableLocation::Address(
-                                        location,
-                                    )))
-                                }
-                            },
-                            Err(error) => Ok(ExpressionResult::Location(
-                                VariableLocation::Error(format!(
-                                    "Error: Cannot convert register value to location address: {error:?}"
-                                )),
-                            )),
-                        }
-                    } else {
-                        Ok(ExpressionResult::Location(VariableLocation::Error(
-                            format!("Error: Cannot resolve register: {register:?}"),
-                        )))
-                    }
+                        .map_or(0, |register| register.core_register.size_in_bytes() as u64);
+                    Ok(ExpressionResult::Location(match location {
+                        VariableLocation::Value => VariableLocation::Pieces(vec![VariablePiece {
+                            byte_offset: 0,
+                            byte_size,
+                            location,
+                            value,
+                        }]),
+                        other => other,
+                    }))
                 }
                 l => Ok(ExpressionResult::Location(VariableLocation::Error(
                     format!(
@@ -1756,64 +2275,508 @@ impl UnitInfo {
         }
     }
 
-    /// Tries to get the result of a DWARF expression in the form of a Piece.
-    pub(crate) fn expression_to_piece(
+    /// The location of a member at `byte_offset` in a variable with a [`VariableLocation::Pieces`] location.
+    ///
+    /// Members in parts that were optimized out are unavailable, and members in parts that are stored in registers
+    /// are decoded from the register value, while the other members are still shown.
+    fn member_location_in_pieces(
         &self,
-        memory: &mut dyn MemoryInterface,
-        expression: gimli::Expression<GimliReader>,
-        frame_info: StackFrameInfo<'_>,
-    ) -> Result<Vec<gimli::Piece<GimliReader, usize>>, DebugError> {
-        let mut evaluation = expression.evaluation(self.unit.encoding());
-        let mut result = evaluation.evaluate()?;
+        debug_info: &DebugInfo,
+        member_die: &gimli::DebuggingInformationEntry<GimliReader>,
+        pieces: &[VariablePiece],
+        byte_offset: u64,
+    ) -> Result<ExpressionResult, DebugError> {
+        let Some(piece) = pieces.iter().find(|piece| {
+            piece.byte_offset <= byte_offset && byte_offset < piece.byte_offset + piece.byte_size
+        }) else {
+            // None of the parts of the variable describes this member.
+            return Ok(ExpressionResult::Location(VariableLocation::Unavailable(
+                UnavailableReason::OptimizedOut,
+            )));
+        };
+        let offset_in_piece = byte_offset - piece.byte_offset;
 
-        loop {
-            result = match result {
-                EvaluationResult::Complete => return Ok(evaluation.result()),
-                EvaluationResult::RequiresMemory { address, size, .. } => {
-                    read_memory(size, memory, address, &mut evaluation)?
-                }
-                EvaluationResult::RequiresFrameBase => {
-                    match provide_frame_base(frame_info.frame_base, &mut evaluation) {
-                        Ok(value) => value,
-                        Err(value) => return Err(value),
-                    }
-                }
-                EvaluationResult::RequiresRegister {
-                    register,
-                    base_type,
-                } => provide_register(frame_info.registers, register, base_type, &mut evaluation)?,
-                EvaluationResult::RequiresRelocatedAddress(address_index) => {
-                    // The address_index as an offset from 0, so just pass it into the next step.
-                    evaluation.resume_with_relocated_address(address_index)?
-                }
-                EvaluationResult::RequiresCallFrameCfa => {
-                    match provide_cfa(frame_info.canonical_frame_address, &mut evaluation) {
-                        Ok(value) => value,
-                        Err(value) => return Err(value),
-                    }
+        if piece.location != VariableLocation::Value || piece.value.is_none() {
+            return Ok(ExpressionResult::Location(match &piece.location {
+                VariableLocation::Address(address) => {
+                    VariableLocation::Address(address + offset_in_piece)
                 }
-                unimplemented_expression => {
-                    return Err(DebugError::UnwindIncompleteResults {
-                        message: format!("Unimplemented: Expressions that include {unimplemented_expression:?} are not currently supported."
-                    )});
+                other => other.clone(),
+            }));
+        }
+
+        // The member is part of a register, or a constant, so decode its value with the base type of the member.
+        let member_type = match member_die.attr_value(gimli::DW_AT_type)? {
+            Some(gimli::AttributeValue::UnitRef(type_offset)) => {
+                match resolve_type_modifiers(self, type_offset)? {
+                    Some(member_type_offset) => Some(self.unit.entry(member_type_offset)?),
+                    None => None,
                 }
             }
+            _ => None,
+        };
+        match member_type {
+            Some(base_type) if base_type.tag() == gimli::DW_TAG_base_type => {
+                self.base_type_value_in_pieces(debug_info, &base_type, pieces, byte_offset)
+            }
+            // Members of other types, e.g. a nested struct, or a pointer, are resolved from the parts that contain them.
+            Some(member_type) => match extract_byte_size(&member_type) {
+                Some(byte_size) => Ok(ExpressionResult::Location(VariableLocation::Pieces(
+                    pieces_in_range(pieces, byte_offset, byte_size, debug_info.endianness),
+                ))),
+                None => Ok(ExpressionResult::Location(VariableLocation::Unsupported(
+                    "Unimplemented: Members of an unknown size that are stored in a register."
+                        .to_string(),
+                ))),
+            },
+            None => Ok(ExpressionResult::Location(VariableLocation::Unsupported(
+                "Unimplemented: Members without a type that are stored in a register.".to_string(),
+            ))),
         }
     }
 
-    /// A helper function, to handle memory_location for special cases, such as array members, pointers, and intermediate nodes.
-    /// Normally, the memory_location is calculated before the type is calculated,
-    ///     but special cases require the type related info of the variable to correctly compute the memory_location.
-    fn handle_memory_location_special_cases(
+    /// Decode the value of the `base_type` at `byte_offset` of a variable with a [`VariableLocation::Pieces`] location,
+    /// from the parts that are stored in a register, or are a constant.
+    fn base_type_value_in_pieces(
         &self,
-        unit_ref: UnitOffset,
-        child_variable: &mut Variable,
-        parent_variable: &Variable,
-        memory: &mut dyn MemoryInterface,
-    ) {
-        if let Some(child_member_index) = child_variable.member_index {
-            // If this variable is a member of an array type, and needs special handling to calculate the `memory_location`.
-            if let VariableLocation::Address(address) = parent_variable.memory_location {
+        debug_info: &DebugInfo,
+        base_type: &gimli::DebuggingInformationEntry<GimliReader>,
+        pieces: &[VariablePiece],
+        byte_offset: u64,
+    ) -> Result<ExpressionResult, DebugError> {
+        let byte_size = extract_byte_size(base_type).unwrap_or_default();
+        let Some(bits) = value_in_pieces(pieces, byte_offset, byte_size, debug_info.endianness)
+        else {
+            return Ok(ExpressionResult::Location(VariableLocation::Unsupported(format!(
+                "Unimplemented: Values of {byte_size} bytes that are not completely stored in a register."
+            ))));
+        };
+
+        let unused_bits = 64 - byte_size * 8;
+        let value = match base_type.attr_value(gimli::DW_AT_encoding)? {
+            Some(gimli::AttributeValue::Encoding(
+                gimli::DW_ATE_signed | gimli::DW_ATE_signed_char,
+            )) => (((bits << unused_bits) as i64) >> unused_bits).to_string(),
+            Some(gimli::AttributeValue::Encoding(gimli::DW_ATE_float)) if byte_size == 4 => {
+                f32::from_bits(bits as u32).to_string()
+            }
+            Some(gimli::AttributeValue::Encoding(gimli::DW_ATE_float)) if byte_size == 8 => {
+                f64::from_bits(bits).to_string()
+            }
+            Some(gimli::AttributeValue::Encoding(
+                gimli::DW_ATE_unsigned | gimli::DW_ATE_unsigned_char | gimli::DW_ATE_boolean,
+            )) => bits.to_string(),
+            other => {
+                return Ok(ExpressionResult::Location(VariableLocation::Unsupported(
+                    format!("Unimplemented: Values with encoding {other:?} that are stored in a register."),
+                )));
+            }
+        };
+        Ok(ExpressionResult::Value(VariableValue::Valid(value)))
+    }
+
+    /// Check if `expression` contains an operation that matches `predicate`.
+    pub(crate) fn has_operation(
+        &self,
+        expression: &gimli::Expression<GimliReader>,
+        predicate: impl Fn(&gimli::Operation<GimliReader>) -> bool,
+    ) -> Result<bool, DebugError> {
+        let mut operations = expression.clone().operations(self.unit.encoding());
+        while let Some(operation) = operations.next()? {
+            if predicate(&operation) {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Evaluate `expression` for [`StackFrameInfo::registers_only`], where the value of a variable in a register
+    /// is read from the register, and variables in memory are unavailable.
+    fn registers_only_location(
+        &self,
+        debug_info: &DebugInfo,
+        memory: &mut dyn MemoryInterface,
+        expression: gimli::Expression<GimliReader>,
+        object_address: Option<u64>,
+        frame_info: StackFrameInfo<'_>,
+    ) -> Result<ExpressionResult, DebugError> {
+        let pieces = self.expression_to_piece(
+            debug_info,
+            memory,
+            expression.clone(),
+            object_address,
+            frame_info,
+        )?;
+        let location = match &pieces[..] {
+            [piece] => match &piece.location {
+                Location::Register { register } => {
+                    // The value is decoded with the type of the variable, like the parts of a variable in registers.
+                    let (location, value) = register_piece(*register, frame_info.registers);
+                    VariableLocation::Pieces(vec![VariablePiece {
+                        byte_offset: 0,
+                        byte_size: self.unit.header.address_size().into(),
+                        location,
+                        value,
+                    }])
+                }
+                Location::Address { .. } => {
+                    VariableLocation::Unavailable(UnavailableReason::NeedsMemory)
+                }
+                // Values, and optimized out variables don't need memory.
+                _ => {
+                    return self.evaluate_expression(
+                        debug_info,
+                        memory,
+                        expression,
+                        object_address,
+                        StackFrameInfo {
+                            registers_only: false,
+                            ..frame_info
+                        },
+                    )
+                }
+            },
+            pieces => match pieces_location(pieces, frame_info.registers) {
+                VariableLocation::Pieces(mut pieces) => {
+                    for piece in &mut pieces {
+                        if matches!(piece.location, VariableLocation::Address(_)) {
+                            piece.location =
+                                VariableLocation::Unavailable(UnavailableReason::NeedsMemory);
+                        }
+                    }
+                    VariableLocation::Pieces(pieces)
+                }
+                other => other,
+            },
+        };
+        Ok(ExpressionResult::Location(location))
+    }
+
+    /// Tries to get the result of a DWARF expression in the form of a Piece.
+    pub(crate) fn expression_to_piece(
+        &self,
+        debug_info: &DebugInfo,
+        memory: &mut dyn MemoryInterface,
+        expression: gimli::Expression<GimliReader>,
+        object_address: Option<u64>,
+        frame_info: StackFrameInfo<'_>,
+    ) -> Result<Vec<gimli::Piece<GimliReader, usize>>, DebugError> {
+        self.nested_expression_to_piece(
+            debug_info,
+            memory,
+            expression,
+            object_address,
+            frame_info,
+            0,
+        )
+    }
+
+    /// Evaluate an `expression`, which may be nested `depth` levels deep in the evaluation of
+    /// `DW_OP_GNU_variable_value` operations of other expressions.
+    fn nested_expression_to_piece(
+        &self,
+        debug_info: &DebugInfo,
+        memory: &mut dyn MemoryInterface,
+        expression: gimli::Expression<GimliReader>,
+        object_address: Option<u64>,
+        frame_info: StackFrameInfo<'_>,
+        depth: usize,
+    ) -> Result<Vec<gimli::Piece<GimliReader, usize>>, DebugError> {
+        let expression =
+            self.resolve_variable_values(debug_info, memory, expression, frame_info, depth)?;
+        let mut evaluation = expression.evaluation(self.unit.encoding());
+        if let Some(object_address) = object_address {
+            evaluation.set_initial_value(object_address);
+            evaluation.set_object_address(object_address);
+        }
+        let mut result = evaluation.evaluate()?;
+
+        loop {
+            result = match result {
+                EvaluationResult::Complete => return Ok(evaluation.result()),
+                EvaluationResult::RequiresMemory {
+                    address,
+                    size,
+                    base_type,
+                    ..
+                } => read_memory(size, base_type, memory, address, &mut evaluation)?,
+                EvaluationResult::RequiresFrameBase => {
+                    match provide_frame_base(frame_info.frame_base, &mut evaluation) {
+                        Ok(value) => value,
+                        Err(value) => return Err(value),
+                    }
+                }
+                EvaluationResult::RequiresRegister {
+                    register,
+                    base_type,
+                } => provide_register(frame_info.registers, register, base_type, &mut evaluation)?,
+                EvaluationResult::RequiresRelocatedAddress(address) => {
+                    // E.g. `DW_OP_addr`, which is relative to where the program is loaded.
+                    evaluation
+                        .resume_with_relocated_address(address.wrapping_add(debug_info.load_bias))?
+                }
+                EvaluationResult::RequiresIndexedAddress { index, relocate } => {
+                    // E.g. `DW_OP_addrx`, which is an index into the `.debug_addr` section.
+                    let mut address = debug_info.dwarf.address(&self.unit, index)?;
+                    if relocate {
+                        address = address.wrapping_add(debug_info.load_bias);
+                    }
+                    evaluation.resume_with_indexed_address(address)?
+                }
+                EvaluationResult::RequiresTls(tls_offset) => provide_tls(
+                    frame_info.thread_local_storage_base,
+                    tls_offset,
+                    &mut evaluation,
+                )?,
+                EvaluationResult::RequiresCallFrameCfa => {
+                    // Unwinding supplies the CFA of each frame, but it can also be calculated from the registers.
+                    let canonical_frame_address = frame_info
+                        .canonical_frame_address
+                        .or_else(|| debug_info.canonical_frame_address(frame_info.registers));
+                    provide_cfa(canonical_frame_address, &mut evaluation)?
+                }
+                unimplemented_expression => {
+                    return Err(DebugError::UnwindIncompleteResults {
+                        message: format!("Unimplemented: Expressions that include {unimplemented_expression:?} are not currently supported."
+                    )});
+                }
+            }
+        }
+    }
+
+    /// Replace the `DW_OP_GNU_variable_value` operations in `expression`, which gimli can not evaluate,
+    /// with a constant of the current value of the referenced variable, e.g. the length of a variable length array.
+    ///
+    /// The constant is encoded with the same length as the operation it replaces, so that the targets of branches remain valid.
+    fn resolve_variable_values(
+        &self,
+        debug_info: &DebugInfo,
+        memory: &mut dyn MemoryInterface,
+        expression: gimli::Expression<GimliReader>,
+        frame_info: StackFrameInfo<'_>,
+        depth: usize,
+    ) -> Result<gimli::Expression<GimliReader>, DebugError> {
+        let encoding = self.unit.encoding();
+        let mut resolved_bytes = expression.0.to_slice()?.to_vec();
+        if !resolved_bytes.contains(&DW_OP_GNU_VARIABLE_VALUE.0) {
+            return Ok(expression);
+        }
+
+        let mut has_variable_values = false;
+        let mut remaining = expression.0.clone();
+        while !remaining.is_empty() {
+            let position = remaining.offset_from(&expression.0);
+            let mut operands = remaining.clone();
+            if operands.read_u8()? != DW_OP_GNU_VARIABLE_VALUE.0 {
+                gimli::Operation::parse(&mut remaining, encoding)?;
+                continue;
+            }
+
+            if depth >= MAX_VARIABLE_VALUE_DEPTH {
+                return Err(DebugError::UnwindIncompleteResults {
+                    message: "Unsupported: DW_OP_GNU_variable_value operations are nested too deeply, or refer to each other.".to_string(),
+                });
+            }
+            let die_offset = gimli::DebugInfoOffset(operands.read_offset(encoding.format)?);
+            let Some(die_offset) = die_offset.to_unit_offset(&self.unit.header) else {
+                return Err(DebugError::UnwindIncompleteResults {
+                    message: format!("Unsupported: DW_OP_GNU_variable_value refers to {die_offset:?}, outside of the compilation unit."),
+                });
+            };
+            let value = self.referenced_variable_value(
+                debug_info,
+                memory,
+                die_offset,
+                frame_info,
+                depth + 1,
+            )?;
+
+            let constant = &mut resolved_bytes[position..operands.offset_from(&expression.0)];
+            match encoding.format {
+                gimli::Format::Dwarf32 => {
+                    let Ok(value) = u32::try_from(value) else {
+                        return Err(DebugError::UnwindIncompleteResults {
+                            message: format!("Unsupported: The value {value} of DW_OP_GNU_variable_value does not fit in 32 bits."),
+                        });
+                    };
+                    constant[0] = gimli::DW_OP_const4u.0;
+                    constant[1..].copy_from_slice(&value.to_le_bytes());
+                }
+                gimli::Format::Dwarf64 => {
+                    constant[0] = gimli::DW_OP_const8u.0;
+                    constant[1..].copy_from_slice(&value.to_le_bytes());
+                }
+            }
+            has_variable_values = true;
+            remaining = operands;
+        }
+
+        if !has_variable_values {
+            // The byte value of the operation was an operand of another operation.
+            return Ok(expression);
+        }
+        Ok(gimli::Expression(GimliReader::new(
+            resolved_bytes.into(),
+            gimli::LittleEndian,
+        )))
+    }
+
+    /// Read the value of the variable at `die_offset`, when it is used in the expression of another variable,
+    /// e.g. the length of a variable length array.
+    fn referenced_variable_value(
+        &self,
+        debug_info: &DebugInfo,
+        memory: &mut dyn MemoryInterface,
+        die_offset: UnitOffset,
+        frame_info: StackFrameInfo<'_>,
+        depth: usize,
+    ) -> Result<u64, DebugError> {
+        let variable_die = self.unit.entry(die_offset)?;
+        if let Some(value) = variable_die
+            .attr_value(gimli::DW_AT_const_value)?
+            .and_then(|value| extract_constant(&value))
+        {
+            return Ok(value);
+        }
+
+        let expression = match variable_die.attr_value(gimli::DW_AT_location)? {
+            Some(gimli::AttributeValue::Exprloc(expression)) => expression,
+            Some(gimli::AttributeValue::LocationListsRef(location_list_offset)) => self
+                .location_list_expression(debug_info, location_list_offset, frame_info)
+                .map_err(|location| DebugError::UnwindIncompleteResults {
+                    message: format!(
+                        "The value of the referenced variable is not available: {location}"
+                    ),
+                })?,
+            other => {
+                return Err(DebugError::UnwindIncompleteResults {
+                    message: format!(
+                        "Unsupported: The referenced variable has no location or value: {other:?}"
+                    ),
+                })
+            }
+        };
+
+        let byte_size = match variable_die.attr_value(gimli::DW_AT_type)? {
+            Some(gimli::AttributeValue::UnitRef(type_offset)) => {
+                resolve_type_modifiers(self, type_offset)?
+                    .map(|type_offset| self.unit.entry(type_offset))
+                    .transpose()?
+                    .and_then(|type_die| extract_byte_size(&type_die))
+            }
+            _ => None,
+        }
+        .unwrap_or(self.unit.header.address_size().into())
+        .min(8) as usize;
+
+        let needs_memory = || DebugError::UnwindIncompleteResults {
+            message: format!(
+                "The value of the referenced variable is not available: {}",
+                UnavailableReason::NeedsMemory
+            ),
+        };
+        if frame_info.registers_only
+            && self.has_operation(&expression, |operation| {
+                matches!(operation, gimli::Operation::Deref { .. })
+            })?
+        {
+            return Err(needs_memory());
+        }
+
+        let pieces = self
+            .nested_expression_to_piece(debug_info, memory, expression, None, frame_info, depth)?;
+        match pieces.as_slice() {
+            [gimli::Piece {
+                location: Location::Address { .. },
+                ..
+            }] if frame_info.registers_only => Err(needs_memory()),
+            [gimli::Piece {
+                location: Location::Address { address },
+                ..
+            }] => {
+                let mut bytes = [0u8; 8];
+                memory.read(*address, &mut bytes[..byte_size])?;
+                Ok(u64::from_le_bytes(bytes))
+            }
+            [gimli::Piece {
+                location: Location::Value { value },
+                ..
+            }] => Ok(value.to_u64(u64::MAX)?),
+            [gimli::Piece {
+                location: Location::Register { register },
+                ..
+            }] => frame_info
+                .registers
+                .get_register_by_dwarf_id(register.0)
+                .and_then(|register| register.value)
+                .ok_or_else(|| DebugError::UnwindIncompleteResults {
+                    message: format!("No value for register #:{}.", register.0),
+                })?
+                .try_into()
+                .map_err(|error| DebugError::UnwindIncompleteResults {
+                    message: format!("Invalid register value: {error:?}"),
+                }),
+            other => Err(DebugError::UnwindIncompleteResults {
+                message: format!(
+                    "Unsupported: The value of the referenced variable is not available: {other:?}"
+                ),
+            }),
+        }
+    }
+
+    /// The value of an array bound, e.g. `DW_AT_upper_bound`, which is constant for arrays with a fixed length.
+    /// The bounds of variable length arrays are either computed by an expression, or stored in another variable.
+    fn array_bound(
+        &self,
+        debug_info: &DebugInfo,
+        bound: gimli::AttributeValue<GimliReader>,
+        memory: &mut dyn MemoryInterface,
+        frame_info: StackFrameInfo<'_>,
+    ) -> Result<u64, DebugError> {
+        match bound {
+            gimli::AttributeValue::Exprloc(expression) => {
+                let pieces =
+                    self.expression_to_piece(debug_info, memory, expression, None, frame_info)?;
+                match pieces.as_slice() {
+                    // The computed bound is the value on top of the stack, rather than the address of the bound.
+                    [gimli::Piece {
+                        location: Location::Address { address: value },
+                        ..
+                    }] => Ok(*value),
+                    [gimli::Piece {
+                        location: Location::Value { value },
+                        ..
+                    }] => Ok(value.to_u64(u64::MAX)?),
+                    other => Err(DebugError::UnwindIncompleteResults {
+                        message: format!("Unsupported: Array bound expression result {other:?}"),
+                    }),
+                }
+            }
+            gimli::AttributeValue::UnitRef(die_offset) => {
+                self.referenced_variable_value(debug_info, memory, die_offset, frame_info, 0)
+            }
+            other => other
+                .udata_value()
+                .ok_or_else(|| DebugError::UnwindIncompleteResults {
+                    message: format!("Unimplemented: Attribute Value for array bound: {other:?}"),
+                }),
+        }
+    }
+
+    /// A helper function, to handle memory_location for special cases, such as array members, pointers, and intermediate nodes.
+    /// Normally, the memory_location is calculated before the type is calculated,
+    ///     but special cases require the type related info of the variable to correctly compute the memory_location.
+    fn handle_memory_location_special_cases(
+        &self,
+        debug_info: &DebugInfo,
+        unit_ref: UnitOffset,
+        child_variable: &mut Variable,
+        parent_variable: &Variable,
+        memory: &mut dyn MemoryInterface,
+    ) {
+        if let Some(child_member_index) = child_variable.member_index {
+            // If this variable is a member of an array type, and needs special handling to calculate the `memory_location`.
+            if let VariableLocation::Address(address) = parent_variable.memory_location {
                 if let Some(byte_size) = child_variable.byte_size {
                     let (location, has_overflowed) =
                         address.overflowing_add(child_member_index as u64 * byte_size);
@@ -1831,8 +2794,19 @@ impl UnitInfo {
                     // The first member of an array will have a memory location of the same as it's parent.
                     child_variable.memory_location = parent_variable.memory_location.clone();
                 }
+            } else if let (VariableLocation::Pieces(pieces), Some(byte_size)) =
+                (&parent_variable.memory_location, child_variable.byte_size)
+            {
+                // The members of an array that is stored in registers are resolved from the parts that contain them.
+                child_variable.memory_location = VariableLocation::Pieces(pieces_in_range(
+                    pieces,
+                    child_member_index as u64 * byte_size,
+                    byte_size,
+                    debug_info.endianness,
+                ));
             } else {
-                child_variable.memory_location = VariableLocation::Unavailable;
+                child_variable.memory_location =
+                    VariableLocation::Unavailable(UnavailableReason::NoLocation);
             }
         } else if child_variable.memory_location == VariableLocation::Unknown {
             // Non-array members can inherit their memory location from their parent, but only if the parent has a valid memory location.
@@ -1840,6 +2814,21 @@ impl UnitInfo {
             // Overriding clippy, to defer the processing of `self.has_address_pointer()` until after the `||` conditions.
             if self.is_pointer(child_variable, parent_variable, unit_ref) {
                 match &parent_variable.memory_location {
+                    VariableLocation::Pieces(pieces) => {
+                        // The pointer is stored in a register, which holds the address of the referenced variable.
+                        child_variable.memory_location = match value_in_pieces(
+                            pieces,
+                            0,
+                            parent_variable.byte_size.unwrap_or(4),
+                            debug_info.endianness,
+                        ) {
+                            Some(address) => VariableLocation::Address(address),
+                            None => VariableLocation::Unsupported(format!(
+                                "Location {} not supported for referenced variables.",
+                                parent_variable.memory_location
+                            )),
+                        };
+                    }
                     VariableLocation::Address(address) => {
                         // Now, retrieve the location by reading the adddress pointed to by the parent variable.
                         child_variable.memory_location = match memory.read_word_32(*address) {
@@ -1866,6 +2855,109 @@ impl UnitInfo {
         }
     }
 
+    /// For a pointer to a character type in C, e.g. `const char *`, the value of the pointer,
+    /// followed by the NUL-terminated string that it points to, e.g. `0x00402004 "Hello"`.
+    ///
+    /// The string is read when the `pointer` is expanded, from the address of the `pointee` at `pointee_offset`.
+    /// At most [`DebugInfoOptions::max_string_length`](crate::debug::DebugInfoOptions::max_string_length) characters
+    /// are read. Null pointers, and pointers to memory that can not be read, only show their value.
+    /// Returns `None` for other pointers, or if the value of the pointer is not known.
+    pub(crate) fn c_string_value(
+        &self,
+        debug_info: &DebugInfo,
+        pointee_offset: UnitOffset,
+        pointer: &Variable,
+        pointee: &Variable,
+        memory: &mut dyn MemoryInterface,
+    ) -> Result<Option<VariableValue>, DebugError> {
+        if !matches!(
+            self.get_language(),
+            gimli::DW_LANG_C
+                | gimli::DW_LANG_C89
+                | gimli::DW_LANG_C99
+                | gimli::DW_LANG_C11
+                | gimli::DW_LANG_C17
+        ) {
+            return Ok(None);
+        }
+        let Some(pointee_offset) = resolve_type_modifiers(self, pointee_offset)? else {
+            return Ok(None);
+        };
+        let pointee_type = self.unit.entry(pointee_offset)?;
+        if pointee_type.tag() != gimli::DW_TAG_base_type
+            || !matches!(
+                pointee_type.attr_value(gimli::DW_AT_encoding)?,
+                Some(gimli::AttributeValue::Encoding(
+                    gimli::DW_ATE_signed_char | gimli::DW_ATE_unsigned_char
+                ))
+            )
+        {
+            return Ok(None);
+        }
+
+        let Ok(address) = pointee.memory_location.memory_address() else {
+            return Ok(None);
+        };
+        let address_size = match pointer.byte_size {
+            Some(8) => 8,
+            _ => 4,
+        };
+        let formatted_address = format_address(address, address_size as u8);
+        if address == 0 {
+            return Ok(Some(VariableValue::Valid(formatted_address)));
+        }
+
+        // The string is read in chunks, to limit the number of memory transfers.
+        const CHUNK_SIZE: usize = 32;
+        let max_string_length = debug_info.max_string_length;
+        let mut bytes = Vec::new();
+        let mut terminated = false;
+        'read: while bytes.len() < max_string_length {
+            let mut chunk = [0u8; CHUNK_SIZE];
+            if memory
+                .read(address + bytes.len() as u64, &mut chunk)
+                .is_err()
+            {
+                break;
+            }
+            for byte in chunk {
+                if byte == 0 {
+                    terminated = true;
+                    break 'read;
+                }
+                if bytes.len() == max_string_length {
+                    break 'read;
+                }
+                bytes.push(byte);
+            }
+        }
+        if bytes.is_empty() && !terminated {
+            // The memory that the pointer refers to can not be read.
+            return Ok(Some(VariableValue::Valid(formatted_address)));
+        }
+
+        let mut string = String::with_capacity(bytes.len() + 2);
+        string.push('"');
+        for byte in bytes {
+            match byte {
+                b'"' => string.push_str("\\\""),
+                b'\\' => string.push_str("\\\\"),
+                b'\n' => string.push_str("\\n"),
+                b'\r' => string.push_str("\\r"),
+                b'\t' => string.push_str("\\t"),
+                b' '..=b'~' => string.push(byte as char),
+                other => string.push_str(&format!("\\x{other:02x}")),
+            }
+        }
+        string.push('"');
+        if !terminated {
+            string.push_str("...");
+        }
+        Ok(Some(VariableValue::Valid(format!(
+            "{formatted_address} {string}"
+        ))))
+    }
+
     /// Returns `true` if the variable is a pointer, `false` otherwise.
     fn is_pointer(
         &self,
@@ -1945,6 +3037,21 @@ fn provide_register(
     Ok(evaluation.resume_with_register(gimli::Value::Generic(raw_value.try_into()?))?)
 }
 
+/// Gets the thread local storage address for the DWARF resolver.
+fn provide_tls(
+    thread_local_storage_base: Option<u64>,
+    tls_offset: u64,
+    evaluation: &mut gimli::Evaluation<EndianReader>,
+) -> Result<EvaluationResult<EndianReader>, DebugError> {
+    let Some(thread_local_storage_base) = thread_local_storage_base else {
+        return Err(DebugError::UnwindIncompleteResults {
+            message: "Cannot resolve a thread local `Variable` location without a thread local storage base address."
+                .to_string(),
+        });
+    };
+    Ok(evaluation.resume_with_tls(thread_local_storage_base.wrapping_add(tls_offset))?)
+}
+
 /// Gets necessary framebase information for the DWARF resolver.
 fn provide_frame_base(
     frame_base: Option<u64>,
@@ -1964,6 +3071,20 @@ fn provide_frame_base(
     }
 }
 
+/// The mask for the bits of a discriminant of `byte_size` bytes.
+fn discriminant_mask(byte_size: Option<u64>) -> u64 {
+    match byte_size {
+        Some(byte_size @ 1..=7) => (1_u64 << (byte_size * 8)) - 1,
+        _ => u64::MAX,
+    }
+}
+
+/// The bits of the (possibly signed) `discriminant` value, as they are encoded in the `byte_size` of the discriminant.
+fn discriminant_bits(discriminant: &str, byte_size: Option<u64>) -> Option<u64> {
+    let discriminant = discriminant.parse::<i128>().ok()?;
+    Some(discriminant as u64 & discriminant_mask(byte_size))
+}
+
 /// Gets necessary CFA information for the DWARF resolver.
 fn provide_cfa(
     cfa: Option<u64>,
@@ -1983,36 +3104,213 @@ fn provide_cfa(
     }
 }
 
-/// Reads memory requested by the DWARF resolver.
-fn read_memory(
-    size: u8,
-    memory: &mut dyn MemoryInterface,
-    address: u64,
-    evaluation: &mut gimli::Evaluation<EndianReader>,
-) -> Result<EvaluationResult<EndianReader>, DebugError> {
-    fn decode_error(error: Vec<u8>) -> DebugError {
-        DebugError::UnwindIncompleteResults {
-            message: format!("Unexpected error while dereferencing debug expressions from target memory: {error:?}. Please report this as a bug.")
-        }
+/// The location and value of a part of a variable that is stored in `register`.
+fn register_piece(
+    register: gimli::Register,
+    registers: &DebugRegisters,
+) -> (VariableLocation, Option<u64>) {
+    match registers
+        .get_register_by_dwarf_id(register.0)
+        .and_then(|register| register.value)
+        .and_then(|value| TryInto::<u64>::try_into(value).ok())
+    {
+        Some(value) => (VariableLocation::Value, Some(value)),
+        None => (
+            VariableLocation::Error(format!("Error: Cannot resolve register: {register:?}")),
+            None,
+        ),
     }
+}
 
-    fn read_error(error: Error) -> DebugError {
-        DebugError::UnwindIncompleteResults {
-            message: format!("Unexpected error while reading debug expressions from target memory: {error:?}. Please report this as a bug.")
-        }
-    }
+/// The raw value of the `byte_size` bytes at `byte_offset` of a variable with a [`VariableLocation::Pieces`] location,
+/// if these are stored in registers, or are constants, e.g. a `u64` that is stored in `r0:r1`.
+///
+/// A part that does not fill its register is stored in the least significant bytes of the register.
+/// The bytes of the parts are in the byte order of the target, like the variable in memory.
+pub(crate) fn value_in_pieces(
+    pieces: &[VariablePiece],
+    byte_offset: u64,
+    byte_size: u64,
+    endianness: gimli::RunTimeEndian,
+) -> Option<u64> {
+    use gimli::Endianity;
 
-    fn size_error(x: u8) -> DebugError {
-        DebugError::UnwindIncompleteResults {
-            message: format!(
-                "Unimplemented: Requested memory with size {x}, which is not supported yet."
-            ),
-        }
+    if byte_size == 0 || byte_size > 8 {
+        return None;
+    }
+    let mut bytes = [0u8; 8];
+    for (index, byte) in bytes[..byte_size as usize].iter_mut().enumerate() {
+        let offset = byte_offset + index as u64;
+        let piece = pieces.iter().find(|piece| {
+            piece.byte_offset <= offset && offset < piece.byte_offset + piece.byte_size
+        })?;
+        let (VariableLocation::Value, Some(value)) = (&piece.location, piece.value) else {
+            return None;
+        };
+        let offset_in_piece = offset - piece.byte_offset;
+        let significance = if endianness.is_big_endian() {
+            piece.byte_size.min(8).checked_sub(offset_in_piece + 1)?
+        } else {
+            offset_in_piece
+        };
+        *byte = value
+            .checked_shr(significance as u32 * 8)
+            .unwrap_or_default() as u8;
     }
+    Some(if endianness.is_big_endian() {
+        u64::from_be_bytes(bytes) >> (64 - byte_size * 8)
+    } else {
+        u64::from_le_bytes(bytes)
+    })
+}
 
-    let mut buff = vec![0u8; size as usize];
-    memory.read(address, &mut buff).map_err(read_error)?;
-    Ok(match size {
+/// The parts of the `byte_size` bytes at `byte_offset` of a variable with a [`VariableLocation::Pieces`] location,
+/// relative to `byte_offset`, e.g. for a member of a struct that is stored in registers.
+pub(crate) fn pieces_in_range(
+    pieces: &[VariablePiece],
+    byte_offset: u64,
+    byte_size: u64,
+    endianness: gimli::RunTimeEndian,
+) -> Vec<VariablePiece> {
+    use gimli::Endianity;
+
+    let range_end = byte_offset + byte_size;
+    pieces
+        .iter()
+        .filter_map(|piece| {
+            let start = piece.byte_offset.max(byte_offset);
+            let end = (piece.byte_offset + piece.byte_size).min(range_end);
+            if start >= end {
+                return None;
+            }
+            let skipped_bytes = start - piece.byte_offset;
+            let location = match &piece.location {
+                VariableLocation::Address(address) => {
+                    VariableLocation::Address(address + skipped_bytes)
+                }
+                other => other.clone(),
+            };
+            // The bytes in the range become the least significant bytes of the value of the part.
+            let value = piece.value.map(|value| {
+                let shift = if endianness.is_big_endian() {
+                    piece
+                        .byte_size
+                        .min(8)
+                        .saturating_sub(end - piece.byte_offset)
+                } else {
+                    skipped_bytes
+                };
+                value.checked_shr(shift as u32 * 8).unwrap_or_default()
+            });
+            Some(VariablePiece {
+                byte_offset: start - byte_offset,
+                byte_size: end - start,
+                location,
+                value,
+            })
+        })
+        .collect()
+}
+
+/// Convert the pieces of a composite location, e.g. `DW_OP_reg0 DW_OP_piece 4 DW_OP_piece 4`, to a [`VariableLocation::Pieces`].
+///
+/// The values of the parts that are stored in registers are read from `registers`,
+/// because these may be overwritten before the members are resolved.
+fn pieces_location(
+    pieces: &[gimli::Piece<GimliReader, usize>],
+    registers: &DebugRegisters,
+) -> VariableLocation {
+    let mut variable_pieces = Vec::with_capacity(pieces.len());
+    let mut byte_offset = 0;
+    for piece in pieces {
+        let byte_size = match (piece.size_in_bits, piece.bit_offset) {
+            (Some(size_in_bits), None) if size_in_bits % 8 == 0 => size_in_bits / 8,
+            _ => {
+                return VariableLocation::Unsupported(
+                    "<unsupported memory implementation>".to_string(),
+                )
+            }
+        };
+        let (location, value) = match &piece.location {
+            Location::Empty => (
+                VariableLocation::Unavailable(UnavailableReason::OptimizedOut),
+                None,
+            ),
+            Location::Address { address } => (VariableLocation::Address(*address), None),
+            Location::Register { register } => register_piece(*register, registers),
+            Location::Value { value } => match value.to_u64(u64::MAX) {
+                Ok(value) => (VariableLocation::Value, Some(value)),
+                Err(_) => (
+                    VariableLocation::Unsupported(format!(
+                        "Unimplemented: Pieces with a value of {value:?}"
+                    )),
+                    None,
+                ),
+            },
+            other => (
+                VariableLocation::Unsupported(format!(
+                    "Unimplemented: Pieces with a location of {:.100}",
+                    format!("{other:?}")
+                )),
+                None,
+            ),
+        };
+        variable_pieces.push(VariablePiece {
+            byte_offset,
+            byte_size,
+            location,
+            value,
+        });
+        byte_offset += byte_size;
+    }
+    VariableLocation::Pieces(variable_pieces)
+}
+
+/// Reads memory requested by the DWARF resolver.
+///
+/// Without a `base_type`, e.g. for `DW_OP_deref`, the value is of the generic type,
+/// so that it can be used in arithmetic with other generic values, e.g. `DW_OP_plus`.
+fn read_memory(
+    size: u8,
+    base_type: UnitOffset,
+    memory: &mut dyn MemoryInterface,
+    address: u64,
+    evaluation: &mut gimli::Evaluation<EndianReader>,
+) -> Result<EvaluationResult<EndianReader>, DebugError> {
+    fn decode_error(error: Vec<u8>) -> DebugError {
+        DebugError::UnwindIncompleteResults {
+            message: format!("Unexpected error while dereferencing debug expressions from target memory: {error:?}. Please report this as a bug.")
+        }
+    }
+
+    fn read_error(error: Error) -> DebugError {
+        DebugError::UnwindIncompleteResults {
+            message: format!("Unexpected error while reading debug expressions from target memory: {error:?}. Please report this as a bug.")
+        }
+    }
+
+    fn size_error(x: u8) -> DebugError {
+        DebugError::UnwindIncompleteResults {
+            message: format!(
+                "Unimplemented: Requested memory with size {x}, which is not supported yet."
+            ),
+        }
+    }
+
+    let mut buff = vec![0u8; size as usize];
+    memory.read(address, &mut buff).map_err(read_error)?;
+    if base_type == UnitOffset(0) {
+        let value = match size {
+            1 => buff[0] as u64,
+            2 => u16::from_le_bytes(buff.try_into().map_err(decode_error)?) as u64,
+            4 => u32::from_le_bytes(buff.try_into().map_err(decode_error)?) as u64,
+            // The generic type has the size of an address, which is 64 bits on 64-bit targets.
+            8 => u64::from_le_bytes(buff.try_into().map_err(decode_error)?),
+            x => return Err(size_error(x)),
+        };
+        return Ok(evaluation.resume_with_memory(gimli::Value::Generic(value))?);
+    }
+    Ok(match size {
         1 => evaluation.resume_with_memory(gimli::Value::U8(buff[0]))?,
         2 => evaluation.resume_with_memory(gimli::Value::U16(u16::from_le_bytes(
             buff.try_into().map_err(decode_error)?,
@@ -2020,8 +3318,1761 @@ fn read_memory(
         4 => evaluation.resume_with_memory(gimli::Value::U32(u32::from_le_bytes(
             buff.try_into().map_err(decode_error)?,
         )))?,
+        8 => evaluation.resume_with_memory(gimli::Value::U64(u64::from_le_bytes(
+            buff.try_into().map_err(decode_error)?,
+        )))?,
         x => {
             return Err(size_error(x));
         }
     })
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        debug::{
+            stack_frame::StackFrameInfo, ArtificialVariables, DebugInfo, DebugInfoOptions,
+            DebugRegisters, SectionMemory, UnavailableReason, Variable, VariableCache,
+            VariableLocation, VariableName, VariablePiece, VariableType,
+        },
+        test::{
+            cortex_m_registers, get_path_for_test_files, load_test_elf_as_debug_info,
+            x86_64::{RAX, RBP, RDI, RIP, RSP},
+            x86_64_registers, MockMemory,
+        },
+        MemoryInterface,
+    };
+    use test_case::test_case;
+
+    #[test_case(gimli::RunTimeEndian::Little, 0, 8, 0x1122_3344_5566_7788; "u64 in two registers")]
+    #[test_case(gimli::RunTimeEndian::Little, 2, 4, 0x3344_5566; "u32 across two registers")]
+    #[test_case(gimli::RunTimeEndian::Big, 0, 8, 0x5566_7788_1122_3344; "big endian u64 in two registers")]
+    #[test_case(gimli::RunTimeEndian::Big, 2, 4, 0x7788_1122; "big endian u32 across two registers")]
+    fn scalar_value_in_pieces(
+        endianness: gimli::RunTimeEndian,
+        byte_offset: u64,
+        byte_size: u64,
+        expected_value: u64,
+    ) {
+        // A 64-bit value in `r0:r1`, where each register holds 4 bytes of the value in memory.
+        let pieces = [
+            VariablePiece {
+                byte_offset: 0,
+                byte_size: 4,
+                location: VariableLocation::Value,
+                value: Some(0x5566_7788),
+            },
+            VariablePiece {
+                byte_offset: 4,
+                byte_size: 4,
+                location: VariableLocation::Value,
+                value: Some(0x1122_3344),
+            },
+        ];
+
+        assert_eq!(
+            value_in_pieces(&pieces, byte_offset, byte_size, endianness),
+            Some(expected_value)
+        );
+    }
+
+    #[test]
+    fn scalar_value_in_pieces_in_memory() {
+        let pieces = [
+            VariablePiece {
+                byte_offset: 0,
+                byte_size: 4,
+                location: VariableLocation::Value,
+                value: Some(0x5566_7788),
+            },
+            VariablePiece {
+                byte_offset: 4,
+                byte_size: 4,
+                location: VariableLocation::Address(0x2000_0000),
+                value: None,
+            },
+        ];
+
+        // Values that are partly stored in memory are not reassembled.
+        assert_eq!(
+            value_in_pieces(&pieces, 0, 8, gimli::RunTimeEndian::Little),
+            None
+        );
+        assert_eq!(
+            value_in_pieces(&pieces, 0, 4, gimli::RunTimeEndian::Little),
+            Some(0x5566_7788)
+        );
+    }
+
+    #[test_case(0x40_1010, false; "before the assignment")]
+    #[test_case(0x40_101D, true; "while live")]
+    #[test_case(0x40_1022, false; "after the last use")]
+    fn variable_live_in_part_of_its_scope(program_counter: u64, is_live: bool) {
+        let debug_info = load_test_elf_as_debug_info("c-optimized/c-optimized.elf");
+
+        // The location list of `first` in `partially_live` only covers 0x401018..0x40101F,
+        // where it is kept in `rdi` (DWARF register 5).
+        let (unit_info, function_die) = debug_info
+            .unit_infos
+            .iter()
+            .find_map(|unit_info| {
+                unit_info
+                    .get_function_dies(&debug_info, program_counter, false)
+                    .ok()?
+                    .into_iter()
+                    .next()
+                    .map(|function_die| (unit_info, function_die))
+            })
+            .unwrap();
+        assert_eq!(
+            function_die.function_name(&debug_info).as_deref(),
+            Some("partially_live")
+        );
+
+        // Only the liveness of `first` is checked, but resolving a live variable may read memory.
+        let registers = x86_64_registers(|id| {
+            if id == RIP {
+                program_counter
+            } else {
+                0x2000_0000
+            }
+        });
+        let mut memory = MockMemory::new();
+        memory.add_word_range(0x2000_0000, &[0]);
+
+        let mut local_variables = debug_info
+            .create_function_scope_cache(&function_die, unit_info)
+            .unwrap();
+        local_variables.recurse_deferred_variables(
+            &debug_info,
+            &mut memory,
+            None,
+            10,
+            0,
+            StackFrameInfo {
+                registers: &registers,
+                frame_base: None,
+                canonical_frame_address: None,
+                thread_local_storage_base: None,
+                registers_only: false,
+                frame_torn_down: false,
+            },
+        );
+
+        let first = local_variables
+            .get_variable_by_name(&VariableName::Named("first".to_string()))
+            .unwrap();
+        if is_live {
+            assert_ne!(
+                first.memory_location,
+                VariableLocation::Unavailable(UnavailableReason::NotLiveHere)
+            );
+        } else {
+            assert_eq!(
+                first.memory_location,
+                VariableLocation::Unavailable(UnavailableReason::NotLiveHere)
+            );
+            assert_eq!(
+                first.get_value(&local_variables),
+                "< <value not live at this location> >"
+            );
+        }
+    }
+
+    #[test]
+    fn variable_in_register() {
+        let debug_info = load_test_elf_as_debug_info("c-optimized/c-optimized.elf");
+
+        // `first` in `partially_live` is an `int`, which is kept in `rdi` (DWARF register 5) at 0x40101D.
+        let program_counter = 0x40_101D;
+        let (unit_info, function_die) = debug_info
+            .unit_infos
+            .iter()
+            .find_map(|unit_info| {
+                unit_info
+                    .get_function_dies(&debug_info, program_counter, false)
+                    .ok()?
+                    .into_iter()
+                    .next()
+                    .map(|function_die| (unit_info, function_die))
+            })
+            .unwrap();
+        assert_eq!(
+            function_die.function_name(&debug_info).as_deref(),
+            Some("partially_live")
+        );
+
+        // The upper half of `rdi` is not part of the `int` value.
+        let registers = x86_64_registers(|id| match id {
+            RIP => program_counter,
+            RDI => 0xDEAD_BEEF_FFFF_FFD6,
+            _ => 0x2000_0000,
+        });
+
+        let mut memory = MockMemory::new();
+
+        let mut local_variables = debug_info
+            .create_function_scope_cache(&function_die, unit_info)
+            .unwrap();
+        local_variables.recurse_deferred_variables(
+            &debug_info,
+            &mut memory,
+            None,
+            10,
+            0,
+            StackFrameInfo {
+                registers: &registers,
+                frame_base: None,
+                canonical_frame_address: None,
+                thread_local_storage_base: None,
+                registers_only: false,
+                frame_torn_down: false,
+            },
+        );
+
+        let first = local_variables
+            .get_variable_by_name(&VariableName::Named("first".to_string()))
+            .unwrap();
+        assert_eq!(first.get_value(&local_variables), "-42");
+    }
+
+    #[test_case(0x40_106A, "5", Some("-3"); "both members in registers")]
+    #[test_case(0x40_1070, "5", None; "right member optimized out")]
+    fn struct_members_available_individually(
+        program_counter: u64,
+        expected_left: &str,
+        expected_right: Option<&str>,
+    ) {
+        let debug_info = load_test_elf_as_debug_info("c-optimized/c-optimized.elf");
+        let unit_info = &debug_info.unit_infos[0];
+
+        // `pair` in `partial_struct` is stored in pieces, with `left` in `rax` (DWARF register 0),
+        // and `right` in `rdi` (DWARF register 5) until it is optimized out.
+        let registers = x86_64_registers(|id| match id {
+            RAX => 5,
+            RDI => -3i64 as u64,
+            RIP => program_counter,
+            _ => 0,
+        });
+        let function_die = unit_info
+            .get_function_dies(&debug_info, program_counter, false)
+            .unwrap()
+            .pop()
+            .unwrap();
+        assert_eq!(
+            function_die.function_name(&debug_info).as_deref(),
+            Some("partial_struct")
+        );
+        let mut local_variables = debug_info
+            .create_function_scope_cache(&function_die, unit_info)
+            .unwrap();
+        // The parameter `value`, in `rax`, is resolved like a frame base, i.e. as the address of the value.
+        let mut memory = MockMemory::new();
+        memory.add_range(0, vec![0; 16]);
+        local_variables.recurse_deferred_variables(
+            &debug_info,
+            &mut memory,
+            None,
+            10,
+            0,
+            StackFrameInfo {
+                registers: &registers,
+                frame_base: None,
+                canonical_frame_address: None,
+                thread_local_storage_base: None,
+                registers_only: false,
+                frame_torn_down: false,
+            },
+        );
+
+        let pair = local_variables
+            .get_variable_by_name(&VariableName::Named("pair".to_string()))
+            .unwrap();
+        assert!(matches!(pair.memory_location, VariableLocation::Pieces(_)));
+        let member = |name: &str| {
+            local_variables
+                .get_children(pair.variable_key())
+                .unwrap()
+                .into_iter()
+                .find(|member| member.name.to_string() == name)
+                .unwrap()
+        };
+
+        assert_eq!(member("left").get_value(&local_variables), expected_left);
+        let right = member("right");
+        match expected_right {
+            Some(value) => assert_eq!(right.get_value(&local_variables), value),
+            None => assert_eq!(
+                right.memory_location,
+                VariableLocation::Unavailable(UnavailableReason::OptimizedOut)
+            ),
+        }
+    }
+
+    #[test_case("c-epilogue.elf", 0x40_1013, Some(["18", "52", "38"]); "before the epilogue")]
+    #[test_case("c-epilogue.elf", 0x40_1016, Some(["18", "52", "38"]); "in the epilogue with a frame base relative to the CFA")]
+    #[test_case("c-epilogue-register-frame-base.elf", 0x40_1013, Some(["18", "52", "38"]); "before the epilogue with a frame base in a register")]
+    #[test_case("c-epilogue-register-frame-base.elf", 0x40_1016, None; "in the epilogue with a frame base in a register")]
+    fn locals_in_epilogue(file: &str, program_counter: u64, values: Option<[&str; 3]>) {
+        let debug_info = load_test_elf_as_debug_info(&format!("c-epilogue/{file}"));
+
+        // Before and while returning from `checksum`, with the CFA and the frame base at `rbp + 16` = 0x2000_1010.
+        let registers = x86_64_registers(|id| match id {
+            RBP => 0x2000_1000,
+            RSP => 0x2000_1000,
+            RIP => program_counter,
+            _ => 0,
+        });
+        let mut memory = MockMemory::new();
+        memory.add_word_range(0x2000_0FE8, &[0x34, 0x12, 0, 0, 0, 0x26]);
+
+        let mut frame = debug_info
+            .get_stackframe_info(
+                &mut memory,
+                program_counter,
+                &mut gimli::UnwindContext::new(),
+                &registers,
+            )
+            .unwrap()
+            .pop()
+            .unwrap();
+        assert_eq!(frame.frame_base, Some(0x2000_1010));
+        assert_eq!(frame.frame_torn_down, values.is_none());
+
+        let frame_info = StackFrameInfo {
+            registers: &frame.registers,
+            frame_base: frame.frame_base,
+            canonical_frame_address: frame.canonical_frame_address,
+            thread_local_storage_base: None,
+            registers_only: false,
+            frame_torn_down: frame.frame_torn_down,
+        };
+        let local_variables = frame.local_variables.as_mut().unwrap();
+        local_variables.recurse_deferred_variables(
+            &debug_info,
+            &mut memory,
+            None,
+            10,
+            0,
+            frame_info,
+        );
+
+        for (index, name) in ["a", "b", "sum"].into_iter().enumerate() {
+            let variable = local_variables
+                .get_variable_by_name(&VariableName::Named(name.to_string()))
+                .unwrap();
+            match values {
+                Some(values) => {
+                    assert!(matches!(
+                        variable.memory_location,
+                        VariableLocation::Address(_)
+                    ));
+                    assert_eq!(variable.get_value(local_variables), values[index]);
+                }
+                None => assert_eq!(
+                    variable.memory_location,
+                    VariableLocation::Unavailable(UnavailableReason::FrameTornDown)
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn variables_from_registers_only() {
+        // The mock memory has no ranges, so any memory read panics.
+        let mut memory = MockMemory::new();
+
+        // `first` in `partially_live` is kept in `rdi` (DWARF register 5), and is decoded as an `int`.
+        let debug_info = load_test_elf_as_debug_info("c-optimized/c-optimized.elf");
+        let program_counter = 0x40_101D;
+        let (unit_info, function_die) = debug_info
+            .unit_infos
+            .iter()
+            .find_map(|unit_info| {
+                unit_info
+                    .get_function_dies(&debug_info, program_counter, false)
+                    .ok()?
+                    .into_iter()
+                    .next()
+                    .map(|function_die| (unit_info, function_die))
+            })
+            .unwrap();
+        let registers = x86_64_registers(|id| match id {
+            RDI => 0xFFFF_FFFF_FFFF_FFFE,
+            RIP => program_counter,
+            _ => 0x2000_0000,
+        });
+        let mut local_variables = debug_info
+            .create_function_scope_cache(&function_die, unit_info)
+            .unwrap();
+        local_variables.recurse_deferred_variables(
+            &debug_info,
+            &mut memory,
+            None,
+            10,
+            0,
+            StackFrameInfo {
+                registers: &registers,
+                frame_base: None,
+                canonical_frame_address: None,
+                thread_local_storage_base: None,
+                registers_only: true,
+                frame_torn_down: false,
+            },
+        );
+        let first = local_variables
+            .get_variable_by_name(&VariableName::Named("first".to_string()))
+            .unwrap();
+        assert_eq!(first.get_value(&local_variables), "-2");
+
+        // The locals of `checksum` are stored in its stack frame.
+        let debug_info = load_test_elf_as_debug_info("c-epilogue/c-epilogue.elf");
+        let unit_info = &debug_info.unit_infos[0];
+        let function_die = unit_info
+            .get_function_dies(&debug_info, 0x40_1013, false)
+            .unwrap()
+            .pop()
+            .unwrap();
+        let mut local_variables = debug_info
+            .create_function_scope_cache(&function_die, unit_info)
+            .unwrap();
+        local_variables.recurse_deferred_variables(
+            &debug_info,
+            &mut memory,
+            None,
+            10,
+            0,
+            StackFrameInfo {
+                registers: &registers,
+                frame_base: Some(0x2000_1010),
+                canonical_frame_address: None,
+                thread_local_storage_base: None,
+                registers_only: true,
+                frame_torn_down: false,
+            },
+        );
+        for name in ["a", "b", "sum"] {
+            let variable = local_variables
+                .get_variable_by_name(&VariableName::Named(name.to_string()))
+                .unwrap();
+            assert_eq!(
+                variable.memory_location,
+                VariableLocation::Unavailable(UnavailableReason::NeedsMemory)
+            );
+        }
+    }
+
+    #[test]
+    fn locations_relative_to_cfa_without_frame_base() {
+        let debug_info = load_test_elf_as_debug_info("c-cfa/c-cfa.elf");
+        let unit_info = &debug_info.unit_infos[0];
+
+        // Before returning from `scale`, which has no `DW_AT_frame_base`. The locations of its variables use
+        // `DW_OP_call_frame_cfa` directly, and the CFA is `rbp + 16` = 0x2000_1010.
+        let program_counter = 0x40_1014;
+        let registers = x86_64_registers(|id| match id {
+            RBP => 0x2000_1000,
+            RSP => 0x2000_0FF8,
+            RIP => program_counter,
+            _ => 0,
+        });
+        let function_die = unit_info
+            .get_function_dies(&debug_info, program_counter, false)
+            .unwrap()
+            .pop()
+            .unwrap();
+        let mut memory = MockMemory::new();
+        memory.add_word_range(0x2000_0FE8, &[7, 6, 0, 0, 0, 42]);
+
+        // The CFA is not supplied by unwinding, so it has to be calculated from the registers.
+        let frame_info = StackFrameInfo {
+            registers: &registers,
+            frame_base: None,
+            canonical_frame_address: None,
+            thread_local_storage_base: None,
+            registers_only: false,
+            frame_torn_down: false,
+        };
+        assert_eq!(
+            function_die
+                .frame_base(&debug_info, &mut memory, frame_info)
+                .unwrap(),
+            None
+        );
+
+        let mut local_variables = debug_info
+            .create_function_scope_cache(&function_die, unit_info)
+            .unwrap();
+        local_variables.recurse_deferred_variables(
+            &debug_info,
+            &mut memory,
+            None,
+            10,
+            0,
+            frame_info,
+        );
+        let variable = |name: &str| {
+            let variable = local_variables
+                .get_variable_by_name(&VariableName::Named(name.to_string()))
+                .unwrap();
+            (
+                variable.memory_location.clone(),
+                variable.get_value(&local_variables),
+            )
+        };
+        assert_eq!(
+            variable("value"),
+            (VariableLocation::Address(0x2000_0FEC), "6".to_string())
+        );
+        assert_eq!(
+            variable("factor"),
+            (VariableLocation::Address(0x2000_0FE8), "7".to_string())
+        );
+        assert_eq!(
+            variable("product"),
+            (VariableLocation::Address(0x2000_0FFC), "42".to_string())
+        );
+    }
+
+    #[test_case(0x40_1001, 0x2000_0FF0, 0x2000_2000; "in the prologue, relative to the stack pointer")]
+    #[test_case(0x40_100A, 0x2000_0FF0, 0x2000_0FF0; "after the prologue, relative to the frame pointer")]
+    #[test_case(0x40_1019, 0x2000_0FF8, 0x2000_2000; "in the epilogue, relative to the stack pointer")]
+    fn frame_base_from_location_list(program_counter: u64, stack_pointer: u64, frame_pointer: u64) {
+        let debug_info = load_test_elf_as_debug_info("c-frame-base/c-frame-base.elf");
+        let unit_info = &debug_info.unit_infos[0];
+
+        // The frame base of `add` is `rsp + 8` on entry, `rsp + 16` after pushing `rbp`, and `rbp + 16` after
+        // the prologue, so it is always 0x2000_1000, with `b`, `a` and `sum` at `DW_OP_fbreg` -40, -36 and -20.
+        let registers = x86_64_registers(|id| match id {
+            RBP => frame_pointer,
+            RSP => stack_pointer,
+            RIP => program_counter,
+            _ => 0,
+        });
+        let function_die = unit_info
+            .get_function_dies(&debug_info, program_counter, false)
+            .unwrap()
+            .pop()
+            .unwrap();
+        let mut memory = MockMemory::new();
+        memory.add_word_range(0x2000_0FD8, &[2, 1, 0, 0, 0, 3]);
+
+        let frame_base = function_die
+            .frame_base(
+                &debug_info,
+                &mut memory,
+                StackFrameInfo {
+                    registers: &registers,
+                    frame_base: None,
+                    canonical_frame_address: None,
+                    thread_local_storage_base: None,
+                    registers_only: false,
+                    frame_torn_down: false,
+                },
+            )
+            .unwrap();
+        assert_eq!(frame_base, Some(0x2000_1000));
+
+        let mut local_variables = debug_info
+            .create_function_scope_cache(&function_die, unit_info)
+            .unwrap();
+        local_variables.recurse_deferred_variables(
+            &debug_info,
+            &mut memory,
+            None,
+            10,
+            0,
+            StackFrameInfo {
+                registers: &registers,
+                frame_base,
+                canonical_frame_address: None,
+                thread_local_storage_base: None,
+                registers_only: false,
+                frame_torn_down: false,
+            },
+        );
+        let sum = local_variables
+            .get_variable_by_name(&VariableName::Named("sum".to_string()))
+            .unwrap();
+        assert_eq!(sum.memory_location, VariableLocation::Address(0x2000_0FEC));
+        assert_eq!(sum.get_value(&local_variables), "3");
+    }
+
+    /// Encode `value` as a signed LEB128 number, as used by the `DW_OP_breg*` operand.
+    fn encode_sleb128(mut value: i64, bytes: &mut Vec<u8>) {
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if (value == 0 && byte & 0x40 == 0) || (value == -1 && byte & 0x40 != 0) {
+                bytes.push(byte);
+                return;
+            }
+            bytes.push(byte | 0x80);
+        }
+    }
+
+    #[test_case(13, 16, false, 0x2000_1010; "breg13 positive offset")]
+    #[test_case(13, -8, false, 0x2000_0ff8; "breg13 negative offset")]
+    #[test_case(13, -0x1000, false, 0x2000_0000; "breg13 multi-byte negative offset")]
+    #[test_case(13, 0xf000_0000, false, 0x1000_1000; "breg13 positive offset wraps at address size")]
+    #[test_case(7, -0x20, false, 0xffff_fff0; "breg7 negative offset wraps below zero")]
+    #[test_case(13, -8, true, 0x2000_0ff8; "bregx negative offset")]
+    #[test_case(7, 0x1234, true, 0x1244; "bregx positive offset")]
+    fn register_offset_location(register: u8, offset: i64, use_bregx: bool, expected: u64) {
+        let debug_info = load_test_elf_as_debug_info("inlined-functions");
+        let unit_info = &debug_info.unit_infos[0];
+
+        let registers = cortex_m_registers(|id| match id {
+            7 => 0x0000_0010,
+            13 => 0x2000_1000,
+            _ => 0,
+        });
+
+        let mut bytes = if use_bregx {
+            // DW_OP_bregx, followed by the register number as an unsigned LEB128.
+            vec![gimli::constants::DW_OP_bregx.0, register]
+        } else {
+            vec![gimli::constants::DW_OP_breg0.0 + register]
+        };
+        encode_sleb128(offset, &mut bytes);
+        let expression = gimli::Expression(gimli::EndianReader::new(
+            std::rc::Rc::from(bytes.as_slice()),
+            gimli::LittleEndian,
+        ));
+
+        let result = unit_info
+            .evaluate_expression(
+                &debug_info,
+                &mut MockMemory::new(),
+                expression,
+                None,
+                StackFrameInfo {
+                    registers: &registers,
+                    frame_base: None,
+                    canonical_frame_address: None,
+                    thread_local_storage_base: None,
+                    registers_only: false,
+                    frame_torn_down: false,
+                },
+            )
+            .unwrap();
+
+        assert!(
+            matches!(
+                result,
+                ExpressionResult::Location(VariableLocation::Address(address)) if address == expected
+            ),
+            "Expected address {expected:#010x}, got {result:?}"
+        );
+    }
+
+    #[test_case("c-types/c-types-dwarf4.elf"; "DWARF 4")]
+    #[test_case("c-types/c-types-dwarf5.elf"; "DWARF 5")]
+    fn static_local_variable_in_function_scope(elf_file: &str) {
+        use object::{Object, ObjectSymbol};
+
+        let debug_info = load_test_elf_as_debug_info(elf_file);
+
+        // The function `count_calls` contains a `static uint32_t counter`, which gcc names `counter.<n>` in the symbol table.
+        let elf_data = std::fs::read(get_path_for_test_files(elf_file)).unwrap();
+        let elf = object::File::parse(elf_data.as_slice()).unwrap();
+        let symbol_address = |predicate: &dyn Fn(&str) -> bool| {
+            elf.symbols()
+                .find(|symbol| symbol.name().is_ok_and(predicate))
+                .map(|symbol| symbol.address())
+                .unwrap()
+        };
+        let function_address = symbol_address(&|name| name == "count_calls");
+        let counter_address = symbol_address(&|name| name.starts_with("counter."));
+
+        let program_counter = function_address + 4;
+        let (unit_info, function_die) = debug_info
+            .unit_infos
+            .iter()
+            .find_map(|unit_info| {
+                unit_info
+                    .get_function_dies(&debug_info, program_counter, false)
+                    .ok()?
+                    .into_iter()
+                    .next()
+                    .map(|function_die| (unit_info, function_die))
+            })
+            .unwrap();
+
+        let registers = x86_64_registers(|id| if id == RIP { program_counter } else { 0 });
+
+        let mut memory = MockMemory::new();
+        memory.add_word_range(counter_address, &[42]);
+
+        let mut local_variables = debug_info
+            .create_function_scope_cache(&function_die, unit_info)
+            .unwrap();
+        // Static locals have an absolute location, so they can be resolved without a frame base.
+        local_variables.recurse_deferred_variables(
+            &debug_info,
+            &mut memory,
+            None,
+            10,
+            0,
+            StackFrameInfo {
+                registers: &registers,
+                frame_base: None,
+                canonical_frame_address: None,
+                thread_local_storage_base: None,
+                registers_only: false,
+                frame_torn_down: false,
+            },
+        );
+
+        let counter = local_variables
+            .get_variable_by_name(&VariableName::Named("counter".to_string()))
+            .expect("The static local variable should be in the function scope");
+        assert_eq!(
+            counter.memory_location,
+            VariableLocation::Address(counter_address)
+        );
+        assert_eq!(counter.get_value(&local_variables), "42");
+    }
+
+    #[test]
+    fn incomplete_type_defined_in_other_unit() {
+        let debug_info = load_test_elf_as_debug_info("c-opaque-types/c-opaque-types.elf");
+
+        // `struct Device` is only declared in `main.c`, and defined in `device.c`.
+        // `struct Missing` is never defined.
+        let mut memory = MockMemory::new();
+        let mut data = Vec::new();
+        data.extend_from_slice(&7_u32.to_le_bytes()); // device.id
+        data.extend_from_slice(&3_u16.to_le_bytes()); // device.flags
+        data.extend_from_slice(&[0_u8; 2]);
+        data.extend_from_slice(&0x40_2000_u64.to_le_bytes()); // device_handle
+        data.extend_from_slice(&0x40_2000_u64.to_le_bytes()); // missing_handle
+        memory.add_range(0x40_2000, data);
+
+        // Resolving variables requires a program counter, which we set to `_start`.
+        let registers = x86_64_registers(|id| if id == RIP { 0x40_1000 } else { 0 });
+        let unit_info = &debug_info.unit_infos[0];
+        let mut static_variables = debug_info.create_static_scope_cache(unit_info).unwrap();
+        static_variables.recurse_deferred_variables(
+            &debug_info,
+            &mut memory,
+            None,
+            10,
+            0,
+            StackFrameInfo {
+                registers: &registers,
+                frame_base: None,
+                canonical_frame_address: None,
+                thread_local_storage_base: None,
+                registers_only: false,
+                frame_torn_down: false,
+            },
+        );
+
+        let device = static_variables
+            .get_variable_by_name(&VariableName::Named("*device_handle".to_string()))
+            .unwrap();
+        assert_eq!(device.type_name, VariableType::Struct("Device".to_string()));
+        assert_eq!(device.byte_size, Some(8));
+        let members = static_variables
+            .get_children(device.variable_key())
+            .unwrap()
+            .into_iter()
+            .map(|member| (member.name.to_string(), member.get_value(&static_variables)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            members,
+            [
+                ("id".to_string(), "7".to_string()),
+                ("flags".to_string(), "3".to_string())
+            ]
+        );
+
+        let missing = static_variables
+            .get_variable_by_name(&VariableName::Named("*missing_handle".to_string()))
+            .unwrap();
+        assert_eq!(
+            missing.type_name,
+            VariableType::Struct("Missing".to_string())
+        );
+        assert_eq!(
+            missing.get_value(&static_variables),
+            "<incomplete type Missing>"
+        );
+    }
+
+    #[test]
+    fn qualified_names_of_static_members() {
+        let elf_path = get_path_for_test_files("cpp-static-members/cpp-static-members.elf");
+        let debug_info = DebugInfo::from_file(&elf_path).unwrap();
+        let mut memory = SectionMemory::from_file(&elf_path).unwrap();
+
+        // Resolving variables requires a program counter, which we set to `_start`.
+        let registers = x86_64_registers(|id| if id == RIP { 0x40_1000 } else { 0 });
+        let unit_info = &debug_info.unit_infos[0];
+        let mut static_variables = debug_info.create_static_scope_cache(unit_info).unwrap();
+        static_variables.recurse_deferred_variables(
+            &debug_info,
+            &mut memory,
+            None,
+            10,
+            0,
+            StackFrameInfo {
+                registers: &registers,
+                frame_base: None,
+                canonical_frame_address: None,
+                thread_local_storage_base: None,
+                registers_only: false,
+                frame_torn_down: false,
+            },
+        );
+
+        // The definitions refer to the declarations inside of the class and namespace,
+        // and the declaration of `app::level` inside of `namespace app` is not a variable on its own.
+        let statics = static_variables
+            .get_children(static_variables.root_variable().variable_key())
+            .unwrap()
+            .into_iter()
+            .map(|variable| (variable.name.to_string(), variable.memory_location))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            statics,
+            [
+                (
+                    "Widget::instance_count".to_string(),
+                    VariableLocation::Address(0x40_2000)
+                ),
+                (
+                    "app::Registry::entries".to_string(),
+                    VariableLocation::Address(0x40_2004)
+                ),
+                (
+                    "app::level".to_string(),
+                    VariableLocation::Address(0x40_2008)
+                ),
+            ]
+        );
+    }
+
+    #[test_case(ArtificialVariables::ShowReceiver, &["this", "total", "value"], false; "show receiver")]
+    #[test_case(ArtificialVariables::Hide, &["total", "value"], false; "hide")]
+    #[test_case(ArtificialVariables::Show, &["this", "total", "value", "__for_range", "__for_begin", "__for_end"], false; "show")]
+    #[test_case(ArtificialVariables::Mark, &["this", "total", "value", "__for_range", "__for_begin", "__for_end"], true; "mark")]
+    fn artificial_variables_policy(
+        artificial_variables: ArtificialVariables,
+        expected_names: &[&str],
+        expect_marked: bool,
+    ) {
+        let mut options = DebugInfoOptions::new();
+        options.artificial_variables = artificial_variables;
+        let debug_info = DebugInfo::from_file_with_options(
+            get_path_for_test_files("cpp-artificial/cpp-artificial.elf"),
+            options,
+        )
+        .unwrap();
+
+        // Inside the body of the range based `for` loop in `Counter::sum`.
+        let program_counter = 0x40_1049;
+        let unit_info = &debug_info.unit_infos[0];
+        let function_die = unit_info
+            .get_function_dies(&debug_info, program_counter, false)
+            .unwrap()
+            .pop()
+            .unwrap();
+
+        let registers = x86_64_registers(|id| if id == RIP { program_counter } else { 0 });
+
+        // The stack frame of `Counter::sum`, with all pointers referring to `counter.values`.
+        let frame_base = 0x2000_1000;
+        let mut memory = MockMemory::new();
+        memory.add_word_range(0x2000_0000, &[1, 2, 3]);
+        let mut frame = Vec::new();
+        for _ in 0..9 {
+            frame.extend_from_slice(&0x2000_0000_u64.to_le_bytes());
+        }
+        memory.add_range(frame_base - 72, frame);
+
+        let mut local_variables = debug_info
+            .create_function_scope_cache(&function_die, unit_info)
+            .unwrap();
+        local_variables.recurse_deferred_variables(
+            &debug_info,
+            &mut memory,
+            None,
+            1,
+            0,
+            StackFrameInfo {
+                registers: &registers,
+                frame_base: Some(frame_base),
+                canonical_frame_address: None,
+                thread_local_storage_base: None,
+                registers_only: false,
+                frame_torn_down: false,
+            },
+        );
+
+        let root_key = local_variables.root_variable().variable_key();
+        let locals = local_variables.get_children(root_key).unwrap();
+        let names = locals
+            .iter()
+            .map(|variable| variable.name.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(names, expected_names);
+
+        for variable in locals {
+            let is_artificial_name =
+                variable.name.to_string() == "this" || variable.name.to_string().starts_with("__");
+            assert_eq!(variable.is_artificial, expect_marked && is_artificial_name);
+        }
+    }
+
+    #[test_case("c-bitfields/c-bitfields-dwarf4.elf"; "dwarf4")]
+    #[test_case("c-bitfields/c-bitfields-dwarf5.elf"; "dwarf5")]
+    fn wide_bitfield_values(elf_file: &str) {
+        let elf_path = get_path_for_test_files(elf_file);
+        let debug_info = DebugInfo::from_file(&elf_path).unwrap();
+        let mut memory = SectionMemory::from_file(&elf_path).unwrap();
+
+        let registers = x86_64_registers(|id| if id == RIP { 0x40_1000 } else { 0 });
+        let unit_info = &debug_info.unit_infos[0];
+        let mut static_variables = debug_info.create_static_scope_cache(unit_info).unwrap();
+        static_variables.recurse_deferred_variables(
+            &debug_info,
+            &mut memory,
+            None,
+            10,
+            0,
+            StackFrameInfo {
+                registers: &registers,
+                frame_base: None,
+                canonical_frame_address: None,
+                thread_local_storage_base: None,
+                registers_only: false,
+                frame_torn_down: false,
+            },
+        );
+        let wide = static_variables
+            .get_variable_by_name(&VariableName::Named("wide".to_string()))
+            .unwrap();
+        let members = static_variables
+            .get_children(wide.variable_key())
+            .unwrap()
+            .into_iter()
+            .map(|member| (member.name.to_string(), member.get_value(&static_variables)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            members,
+            vec![
+                ("low".to_string(), 0xA_BCDE.to_string()),
+                // A 40-bit field, which starts in the middle of a byte, and spans two 32-bit words.
+                ("timestamp".to_string(), 0x12_3456_789A_u64.to_string()),
+                ("flags".to_string(), "9".to_string()),
+                ("delta".to_string(), "-5".to_string()),
+                ("top".to_string(), 0x12_3456.to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn variable_length_array_with_gnu_variable_value() {
+        let debug_info = load_test_elf_as_debug_info("c-vla/c-vla.elf");
+
+        // After both loops in `sum_squares(4)`, where the upper bound of `squares` is `count - 1`,
+        // computed with `DW_OP_GNU_variable_value`.
+        let program_counter = 0x40_10c1;
+        let (unit_info, function_die) = debug_info
+            .unit_infos
+            .iter()
+            .find_map(|unit_info| {
+                let function_die = unit_info
+                    .get_function_dies(&debug_info, program_counter, false)
+                    .ok()?
+                    .pop()?;
+                Some((unit_info, function_die))
+            })
+            .unwrap();
+        let registers = x86_64_registers(|id| if id == RIP { program_counter } else { 0 });
+
+        // The frame base is the CFA, with `count` at CFA-68, the pointer to `squares` at CFA-64,
+        // and the array itself at the bottom of the stack.
+        let canonical_frame_address = 0x1000_0100;
+        let mut stack = vec![0u8; 0x100];
+        for (index, square) in [0_u32, 1, 4, 9].iter().enumerate() {
+            stack[index * 4..index * 4 + 4].copy_from_slice(&square.to_le_bytes());
+        }
+        stack[0xbc..0xc0].copy_from_slice(&4_u32.to_le_bytes());
+        stack[0xc0..0xc8].copy_from_slice(&0x1000_0000_u64.to_le_bytes());
+        let mut memory = MockMemory::new();
+        memory.add_range(0x1000_0000, stack);
+
+        let frame_info = StackFrameInfo {
+            registers: &registers,
+            frame_base: None,
+            canonical_frame_address: Some(canonical_frame_address),
+            thread_local_storage_base: None,
+            registers_only: false,
+            frame_torn_down: false,
+        };
+        let frame_base = function_die
+            .frame_base(&debug_info, &mut memory, frame_info)
+            .unwrap();
+        let mut local_variables = debug_info
+            .create_function_scope_cache(&function_die, unit_info)
+            .unwrap();
+        local_variables.recurse_deferred_variables(
+            &debug_info,
+            &mut memory,
+            None,
+            10,
+            0,
+            StackFrameInfo {
+                frame_base,
+                ..frame_info
+            },
+        );
+
+        let squares = local_variables
+            .get_variable_by_name(&VariableName::Named("squares".to_string()))
+            .unwrap();
+        let elements = local_variables
+            .get_children(squares.variable_key())
+            .unwrap()
+            .into_iter()
+            .map(|element| element.get_value(&local_variables))
+            .collect::<Vec<_>>();
+        assert_eq!(elements, vec!["0", "1", "4", "9"]);
+    }
+
+    #[test]
+    fn referenced_variable_value_from_registers_only() {
+        let debug_info = load_test_elf_as_debug_info("c-vla/c-vla.elf");
+
+        let unit_info = &debug_info.unit_infos[0];
+        let mut entries = unit_info.unit.entries();
+        let mut count_offset = None;
+        while let Some((_, entry)) = entries.next_dfs().unwrap() {
+            if entry.tag() == gimli::DW_TAG_formal_parameter
+                && entry
+                    .attr_value(gimli::DW_AT_name)
+                    .unwrap()
+                    .is_some_and(|name| extract_name(&debug_info, name) == "count")
+            {
+                count_offset = Some(entry.offset());
+            }
+        }
+
+        // `count`, which is the length of `squares`, is stored in the stack frame of `sum_squares`.
+        // The mock memory has no ranges, so reading it would panic.
+        let registers = x86_64_registers(|id| if id == RIP { 0x40_10c1 } else { 0 });
+        let error = unit_info
+            .referenced_variable_value(
+                &debug_info,
+                &mut MockMemory::new(),
+                count_offset.unwrap(),
+                StackFrameInfo {
+                    registers: &registers,
+                    frame_base: Some(0x1000_0100),
+                    canonical_frame_address: Some(0x1000_0100),
+                    thread_local_storage_base: None,
+                    registers_only: true,
+                    frame_torn_down: false,
+                },
+                0,
+            )
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "The value of the referenced variable is not available: <value stored in memory, which is not read>"
+        );
+    }
+
+    #[test]
+    fn array_members_limited_by_max_array_elements() {
+        let elf_path = get_path_for_test_files("c-types/c-types-dwarf5.elf");
+        let mut options = DebugInfoOptions::new();
+        options.max_array_elements = 3;
+        let debug_info = DebugInfo::from_file_with_options(&elf_path, options).unwrap();
+        let mut memory = SectionMemory::from_file(&elf_path).unwrap();
+
+        let registers = x86_64_registers(|id| if id == RIP { 0x40_1000 } else { 0 });
+        let unit_info = &debug_info.unit_infos[0];
+        let mut static_variables = debug_info.create_static_scope_cache(unit_info).unwrap();
+        static_variables.recurse_deferred_variables(
+            &debug_info,
+            &mut memory,
+            None,
+            10,
+            0,
+            StackFrameInfo {
+                registers: &registers,
+                frame_base: None,
+                canonical_frame_address: None,
+                thread_local_storage_base: None,
+                registers_only: false,
+                frame_torn_down: false,
+            },
+        );
+        let rx_frame = static_variables
+            .get_variable_by_name(&VariableName::Named("rx_frame".to_string()))
+            .unwrap();
+        let data = static_variables
+            .get_variable_by_name_and_parent(
+                &VariableName::Named("data".to_string()),
+                rx_frame.variable_key(),
+            )
+            .unwrap();
+
+        // Only 3 of the 256 members of `uint8_t data[256]` are resolved,
+        // followed by a member that shows how many were omitted.
+        let members = static_variables.get_children(data.variable_key()).unwrap();
+        assert_eq!(
+            members
+                .iter()
+                .map(|member| member.name.to_string())
+                .collect::<Vec<_>>(),
+            vec!["__0", "__1", "__2", "..."]
+        );
+        assert_eq!(
+            members.last().unwrap().get_value(&static_variables),
+            "<253 more elements>"
+        );
+    }
+
+    #[test]
+    fn c_array_members_include_upper_bound() {
+        let elf_path = get_path_for_test_files("c-types/c-types-dwarf5.elf");
+        let debug_info = DebugInfo::from_file(&elf_path).unwrap();
+        let mut memory = SectionMemory::from_file(&elf_path).unwrap();
+
+        let registers = x86_64_registers(|id| if id == RIP { 0x40_1000 } else { 0 });
+        let unit_info = &debug_info.unit_infos[0];
+        let mut static_variables = debug_info.create_static_scope_cache(unit_info).unwrap();
+        static_variables.recurse_deferred_variables(
+            &debug_info,
+            &mut memory,
+            None,
+            10,
+            0,
+            StackFrameInfo {
+                registers: &registers,
+                frame_base: None,
+                canonical_frame_address: None,
+                thread_local_storage_base: None,
+                registers_only: false,
+                frame_torn_down: false,
+            },
+        );
+        let rx_frame = static_variables
+            .get_variable_by_name(&VariableName::Named("rx_frame".to_string()))
+            .unwrap();
+        let data = static_variables
+            .get_variable_by_name_and_parent(
+                &VariableName::Named("data".to_string()),
+                rx_frame.variable_key(),
+            )
+            .unwrap();
+
+        // `uint8_t data[256]` has a `DW_AT_upper_bound` of 255, which is the index of the last member.
+        let members = static_variables.get_children(data.variable_key()).unwrap();
+        assert_eq!(members.len(), 256);
+        assert_eq!(members.last().unwrap().name.to_string(), "__255");
+    }
+
+    #[test]
+    fn struct_member_byte_size_from_its_type() {
+        let debug_info = load_test_elf_as_debug_info("c-types/c-types-dwarf5.elf");
+
+        // `.data` is at 0x402000, followed by `.bss`.
+        let mut memory = MockMemory::new();
+        memory.add_range(0x40_2000, vec![0; 0x250]);
+        // `nested.next` is a null pointer.
+        memory.add_range(0, vec![0; 8]);
+
+        // Resolving variables requires a program counter, which we set to `_start`.
+        let registers = x86_64_registers(|id| if id == RIP { 0x40_1000 } else { 0 });
+        let unit_info = &debug_info.unit_infos[0];
+        let mut static_variables = debug_info.create_static_scope_cache(unit_info).unwrap();
+        static_variables.recurse_deferred_variables(
+            &debug_info,
+            &mut memory,
+            None,
+            10,
+            0,
+            StackFrameInfo {
+                registers: &registers,
+                frame_base: None,
+                canonical_frame_address: None,
+                thread_local_storage_base: None,
+                registers_only: false,
+                frame_torn_down: false,
+            },
+        );
+
+        let nested = static_variables
+            .get_variable_by_name(&VariableName::Named("nested".to_string()))
+            .unwrap();
+        let member_byte_size = |name: &str| {
+            static_variables
+                .get_variable_by_name_and_parent(
+                    &VariableName::Named(name.to_string()),
+                    nested.variable_key(),
+                )
+                .unwrap()
+                .byte_size
+        };
+
+        // The members have no `DW_AT_byte_size` of their own, and the `typedef`s of their types neither,
+        // so the size is that of the type they refer to, not the size of `struct Nested`.
+        assert_eq!(nested.byte_size, Some(32));
+        assert_eq!(member_byte_size("id"), Some(1));
+        assert_eq!(member_byte_size("origin"), Some(4));
+        assert_eq!(member_byte_size("next"), Some(8));
+    }
+
+    #[test]
+    fn static_struct_members_with_load_bias() {
+        let elf_path = get_path_for_test_files("c-types/c-types-dwarf5.elf");
+        let load_bias = 0x1000_0000;
+        let mut options = DebugInfoOptions::new();
+        options.load_bias = load_bias;
+        let debug_info = DebugInfo::from_file_with_options(&elf_path, options).unwrap();
+
+        // The program is loaded at `load_bias`, with `.data` at 0x402000, followed by `.bss`, in the ELF file.
+        let mut elf_memory = SectionMemory::from_file(&elf_path).unwrap();
+        let mut data = vec![0; 0x20];
+        elf_memory.read(0x40_2000, &mut data).unwrap();
+        data.resize(0x250, 0);
+        let mut memory = MockMemory::new();
+        memory.add_range(0x40_2000 + load_bias, data);
+        // `nested.next` is a null pointer, which is not relocated.
+        memory.add_range(0, vec![0; 8]);
+
+        let registers = x86_64_registers(|id| if id == RIP { 0x40_1000 } else { 0 });
+        let unit_info = &debug_info.unit_infos[0];
+        let mut static_variables = debug_info.create_static_scope_cache(unit_info).unwrap();
+        static_variables.recurse_deferred_variables(
+            &debug_info,
+            &mut memory,
+            None,
+            10,
+            0,
+            StackFrameInfo {
+                registers: &registers,
+                frame_base: None,
+                canonical_frame_address: None,
+                thread_local_storage_base: None,
+                registers_only: false,
+                frame_torn_down: false,
+            },
+        );
+
+        // `nested` is located with `DW_OP_addr 0x402000`, and `nested.origin.y` at an offset of 4 bytes from it.
+        let nested = static_variables
+            .get_variable_by_name(&VariableName::Named("nested".to_string()))
+            .unwrap();
+        assert_eq!(
+            nested.memory_location,
+            VariableLocation::Address(0x40_2000 + load_bias)
+        );
+        let member = |parent: &Variable, name: &str| {
+            static_variables
+                .get_variable_by_name_and_parent(
+                    &VariableName::Named(name.to_string()),
+                    parent.variable_key(),
+                )
+                .unwrap()
+        };
+        let origin_y = member(&member(&nested, "origin"), "y");
+        assert_eq!(
+            origin_y.memory_location,
+            VariableLocation::Address(0x40_2004 + load_bias)
+        );
+        assert_eq!(origin_y.get_value(&static_variables), "3");
+    }
+
+    #[test_case("c-types/c-types-dwarf4.elf"; "DWARF 4")]
+    #[test_case("c-types/c-types-dwarf5.elf"; "DWARF 5")]
+    fn enum_member_with_implicit_const_enumerator(elf_file: &str) {
+        use object::{Object, ObjectSymbol};
+
+        let elf_path = get_path_for_test_files(elf_file);
+        let debug_info = DebugInfo::from_file(&elf_path).unwrap();
+        let elf_data = std::fs::read(&elf_path).unwrap();
+        let elf = object::File::parse(elf_data.as_slice()).unwrap();
+        let rx_frame_address = elf
+            .symbols()
+            .find(|symbol| symbol.name() == Ok("rx_frame"))
+            .unwrap()
+            .address();
+
+        // `rx_frame` is zero-initialized in `.bss`, so we provide it with `baud`, at an offset of 260 bytes,
+        // set to `RX_BAUD_DEFAULT`.
+        let mut frame = vec![0; 264];
+        frame[260..].copy_from_slice(&115_200u32.to_le_bytes());
+        let mut memory = MockMemory::new();
+        memory.add_range(rx_frame_address, frame);
+
+        let registers = x86_64_registers(|id| if id == RIP { 0x40_1000 } else { 0 });
+        let frame_info = StackFrameInfo {
+            registers: &registers,
+            frame_base: None,
+            canonical_frame_address: None,
+            thread_local_storage_base: None,
+            registers_only: false,
+            frame_torn_down: false,
+        };
+        let unit_info = &debug_info.unit_infos[0];
+        let mut static_variables = debug_info.create_static_scope_cache(unit_info).unwrap();
+        let mut static_scope = static_variables.root_variable();
+        debug_info
+            .cache_deferred_variables(
+                &mut static_variables,
+                &mut SectionMemory::from_file(&elf_path).unwrap(),
+                &mut static_scope,
+                frame_info,
+            )
+            .unwrap();
+        let mut rx_frame = static_variables
+            .get_variable_by_name(&VariableName::Named("rx_frame".to_string()))
+            .unwrap();
+        debug_info
+            .cache_deferred_variables(
+                &mut static_variables,
+                &mut memory,
+                &mut rx_frame,
+                frame_info,
+            )
+            .unwrap();
+
+        // With DWARF 5, gcc encodes the value of `RX_BAUD_DEFAULT` as `DW_FORM_implicit_const`.
+        let baud = static_variables
+            .get_variable_by_name_and_parent(
+                &VariableName::Named("baud".to_string()),
+                rx_frame.variable_key(),
+            )
+            .unwrap();
+        assert_eq!(baud.get_value(&static_variables), "RxBaud::RX_BAUD_DEFAULT");
+    }
+
+    #[test_case(gimli::constants::DW_OP_form_tls_address.0; "DW_OP_form_tls_address")]
+    #[test_case(gimli::constants::DW_OP_GNU_push_tls_address.0; "DW_OP_GNU_push_tls_address")]
+    fn thread_local_storage_location(tls_operation: u8) {
+        let debug_info = load_test_elf_as_debug_info("inlined-functions");
+        let unit_info = &debug_info.unit_infos[0];
+
+        // The saved context of a suspended task, which is not the one running on the core.
+        let task_registers = cortex_m_registers(|id| if id == 13 { 0x2000_3f00 } else { 0 });
+        let task_frame_info = |thread_local_storage_base| StackFrameInfo {
+            registers: &task_registers,
+            frame_base: None,
+            canonical_frame_address: None,
+            thread_local_storage_base,
+            registers_only: false,
+            frame_torn_down: false,
+        };
+        let evaluate = |bytes: &[u8], frame_info| {
+            unit_info.evaluate_expression(
+                &debug_info,
+                &mut MockMemory::new(),
+                gimli::Expression(gimli::EndianReader::new(
+                    std::rc::Rc::from(bytes),
+                    gimli::LittleEndian,
+                )),
+                None,
+                frame_info,
+            )
+        };
+
+        // DW_OP_const1u 0x10, followed by the TLS operation.
+        let tls_expression = [gimli::constants::DW_OP_const1u.0, 0x10, tls_operation];
+        let result = evaluate(&tls_expression, task_frame_info(Some(0x2000_4000))).unwrap();
+        assert!(
+            matches!(
+                result,
+                ExpressionResult::Location(VariableLocation::Address(0x2000_4010))
+            ),
+            "{result:?}"
+        );
+
+        // Without a TLS base, the location cannot be resolved.
+        assert!(evaluate(&tls_expression, task_frame_info(None)).is_err());
+
+        // Register based locations use the supplied task registers, e.g. DW_OP_breg13 (SP) -4.
+        let result = evaluate(
+            &[gimli::constants::DW_OP_breg13.0, 0x7c],
+            task_frame_info(None),
+        )
+        .unwrap();
+        assert!(
+            matches!(
+                result,
+                ExpressionResult::Location(VariableLocation::Address(0x2000_3efc))
+            ),
+            "{result:?}"
+        );
+    }
+
+    #[test]
+    fn element_address_from_object_address() {
+        use gimli::constants::*;
+
+        let debug_info = load_test_elf_as_debug_info("inlined-functions");
+        let unit_info = &debug_info.unit_infos[0];
+
+        // A descriptor at 0x2000_0100, with a pointer to the data and the number of elements,
+        // for a container of three `u32` elements at 0x2000_0200.
+        let descriptor_address = 0x2000_0100;
+        let mut memory = MockMemory::new();
+        memory.add_word_range(descriptor_address, &[0x2000_0200, 3]);
+        memory.add_word_range(0x2000_0200, &[11, 22, 33]);
+
+        let registers = DebugRegisters(Vec::new());
+        let mut element_value = |bytes: &[u8]| {
+            let result = unit_info
+                .evaluate_expression(
+                    &debug_info,
+                    &mut memory,
+                    gimli::Expression(gimli::EndianReader::new(
+                        std::rc::Rc::from(bytes),
+                        gimli::LittleEndian,
+                    )),
+                    Some(descriptor_address),
+                    StackFrameInfo {
+                        registers: &registers,
+                        frame_base: None,
+                        canonical_frame_address: None,
+                        thread_local_storage_base: None,
+                        registers_only: false,
+                        frame_torn_down: false,
+                    },
+                )
+                .unwrap();
+            let ExpressionResult::Location(VariableLocation::Address(address)) = result else {
+                panic!("Expected an address, got {result:?}");
+            };
+            memory.read_word_32(address).unwrap()
+        };
+
+        // The address of the element at `index` is `*object_address + index * 4`.
+        let element_expression = |index: u8| {
+            vec![
+                DW_OP_push_object_address.0,
+                DW_OP_deref.0,
+                DW_OP_lit0.0 + index,
+                DW_OP_lit4.0,
+                DW_OP_mul.0,
+                DW_OP_plus.0,
+            ]
+        };
+        assert_eq!(element_value(&element_expression(0)), 11);
+        assert_eq!(element_value(&element_expression(2)), 33);
+
+        // A member location starts with the address of the containing object on the stack,
+        // e.g. the number of elements in the descriptor.
+        assert_eq!(element_value(&[DW_OP_plus_uconst.0, 4]), 3);
+    }
+
+    #[test]
+    fn deref_of_64_bit_address() {
+        let debug_info = load_test_elf_as_debug_info("c-types/c-types-dwarf5.elf");
+        let unit_info = &debug_info.unit_infos[0];
+
+        // On a 64-bit target, `DW_OP_deref` reads a pointer of 8 bytes.
+        let pointer_address = 0x1000_0000;
+        let mut memory = MockMemory::new();
+        memory.add_range(pointer_address, 0x2000_0400_u64.to_le_bytes().to_vec());
+
+        let registers = DebugRegisters(Vec::new());
+        let result = unit_info
+            .evaluate_expression(
+                &debug_info,
+                &mut memory,
+                gimli::Expression(gimli::EndianReader::new(
+                    std::rc::Rc::from(
+                        [gimli::DW_OP_push_object_address.0, gimli::DW_OP_deref.0].as_slice(),
+                    ),
+                    gimli::LittleEndian,
+                )),
+                Some(pointer_address),
+                StackFrameInfo {
+                    registers: &registers,
+                    frame_base: None,
+                    canonical_frame_address: None,
+                    thread_local_storage_base: None,
+                    registers_only: false,
+                    frame_torn_down: false,
+                },
+            )
+            .unwrap();
+        assert!(
+            matches!(
+                result,
+                ExpressionResult::Location(VariableLocation::Address(0x2000_0400))
+            ),
+            "{result:?}"
+        );
+    }
+
+    /// The local variables of `enums` in the `rust-enums` test binary, with the values that it assigns.
+    fn rust_enum_local_variables() -> VariableCache {
+        let mut stack = vec![0u8; 0x40];
+        // sample: the `Sample` payload, whose `valid` niche holds the discriminant at offset 4.
+        stack[0x08..0x0c].copy_from_slice(&42_u32.to_le_bytes());
+        stack[0x0c] = 1;
+        // missing and disconnected
+        stack[0x14] = 2;
+        stack[0x1c] = 3;
+        // backward and forward: the `i8` discriminant, followed by the `u16` payload.
+        stack[0x20] = (-1_i8) as u8;
+        stack[0x22..0x24].copy_from_slice(&3_u16.to_le_bytes());
+        stack[0x24] = 1;
+        stack[0x26..0x28].copy_from_slice(&4_u16.to_le_bytes());
+
+        // Before the call to `black_box` in `enums`, with the locals at `rsp` + 0x08..0x28.
+        rust_enum_function_variables(0x20_188f, stack)
+    }
+
+    /// The local variables of the function at `program_counter` in the `rust-enums` test binary,
+    /// with `stack` at `rsp`.
+    fn rust_enum_function_variables(program_counter: u64, stack: Vec<u8>) -> VariableCache {
+        let debug_info = load_test_elf_as_debug_info("rust-enums/enums.elf");
+
+        let (unit_info, function_die) = debug_info
+            .unit_infos
+            .iter()
+            .find_map(|unit_info| {
+                let function_die = unit_info
+                    .get_function_dies(&debug_info, program_counter, false)
+                    .ok()?
+                    .pop()?;
+                Some((unit_info, function_die))
+            })
+            .unwrap();
+        let registers = x86_64_registers(|id| match id {
+            RSP => 0x1000_0000,
+            RIP => program_counter,
+            _ => 0,
+        });
+
+        let mut memory = MockMemory::new();
+        memory.add_range(0x1000_0000, stack);
+
+        let frame_info = StackFrameInfo {
+            registers: &registers,
+            frame_base: None,
+            canonical_frame_address: None,
+            thread_local_storage_base: None,
+            registers_only: false,
+            frame_torn_down: false,
+        };
+        let frame_base = function_die
+            .frame_base(&debug_info, &mut memory, frame_info)
+            .unwrap();
+        let mut local_variables = debug_info
+            .create_function_scope_cache(&function_die, unit_info)
+            .unwrap();
+        local_variables.recurse_deferred_variables(
+            &debug_info,
+            &mut memory,
+            None,
+            10,
+            0,
+            StackFrameInfo {
+                frame_base,
+                ..frame_info
+            },
+        );
+        local_variables
+    }
+
+    #[test]
+    fn rust_enum_discriminants() {
+        let local_variables = rust_enum_local_variables();
+
+        let variant_of = |name: &str| {
+            let variable = local_variables
+                .get_variable_by_name(&VariableName::Named(name.to_string()))
+                .unwrap();
+            let variants = local_variables.get_children(variable.variable_key).unwrap();
+            assert_eq!(variants.len(), 1, "{name} should have one active variant");
+            variants.into_iter().next().unwrap()
+        };
+        let payload_of = |variant: &Variable| {
+            local_variables
+                .get_variable_by_name_and_parent(
+                    &VariableName::Named("__0".to_string()),
+                    variant.variable_key,
+                )
+                .unwrap()
+                .get_value(&local_variables)
+        };
+
+        assert_eq!(
+            variant_of("sample").name,
+            VariableName::Named("Sample".to_string())
+        );
+        assert_eq!(
+            variant_of("missing").name,
+            VariableName::Named("Missing".to_string())
+        );
+        assert_eq!(
+            variant_of("disconnected").name,
+            VariableName::Named("Disconnected".to_string())
+        );
+        let backward = variant_of("backward");
+        assert_eq!(backward.name, VariableName::Named("Backward".to_string()));
+        assert_eq!(payload_of(&backward), "3");
+        let forward = variant_of("forward");
+        assert_eq!(forward.name, VariableName::Named("Forward".to_string()));
+        assert_eq!(payload_of(&forward), "4");
+    }
+
+    #[test]
+    fn rust_enum_discriminant_values() {
+        let local_variables = rust_enum_local_variables();
+
+        let discriminant_value = |name: &str| {
+            local_variables
+                .get_variable_by_name(&VariableName::Named(name.to_string()))
+                .unwrap()
+                .discriminant_value
+        };
+
+        // The niche in `Sample::valid` holds the value of the `bool` for `Sample`, and 2 and 3 for the other variants.
+        assert_eq!(discriminant_value("sample"), Some(1));
+        assert_eq!(discriminant_value("missing"), Some(2));
+        assert_eq!(discriminant_value("disconnected"), Some(3));
+        // The encoding of `-1_i8`.
+        assert_eq!(discriminant_value("backward"), Some(0xFF));
+        assert_eq!(discriminant_value("forward"), Some(1));
+    }
+
+    #[test]
+    fn rust_result_active_variant_names() {
+        let mut stack = vec![0u8; 0x18];
+        // ok: the discriminant 0, with the `u32` payload at offset 4.
+        stack[0x0c..0x10].copy_from_slice(&7_u32.to_le_bytes());
+        // err: the discriminant 1, with the `SensorError::Checksum(5)` payload at offset 1.
+        stack[0x10] = 1;
+        stack[0x11] = 1;
+        stack[0x12] = 5;
+
+        // Before the call to `black_box` in `results`, with the locals at `rsp` + 0x08..0x14.
+        let local_variables = rust_enum_function_variables(0x20_18ca, stack);
+        let variable = |name: &str| {
+            local_variables
+                .get_variable_by_name(&VariableName::Named(name.to_string()))
+                .unwrap()
+        };
+
+        assert_eq!(variable("ok").active_variant_name(), Some("Ok".to_string()));
+        let err = variable("err");
+        assert_eq!(err.active_variant_name(), Some("Err".to_string()));
+        // The payload of the `Err` variant is itself an enum, with its own active variant.
+        let error = local_variables
+            .get_variable_by_name_and_parent(&VariableName::Named("__0".to_string()), {
+                local_variables
+                    .get_variable_by_name_and_parent(
+                        &VariableName::Named("Err".to_string()),
+                        err.variable_key,
+                    )
+                    .unwrap()
+                    .variable_key
+            })
+            .unwrap();
+        assert_eq!(error.active_variant_name(), Some("Checksum".to_string()));
+        // Variables that are not tagged unions have no active variant.
+        assert_eq!(
+            local_variables
+                .get_variable_by_name_and_parent(&VariableName::Named("__0".to_string()), {
+                    local_variables
+                        .get_variable_by_name_and_parent(
+                            &VariableName::Named("Checksum".to_string()),
+                            error.variable_key,
+                        )
+                        .unwrap()
+                        .variable_key
+                })
+                .unwrap()
+                .active_variant_name(),
+            None
+        );
+    }
+
+    #[test]
+    fn c_strings_through_char_pointers() {
+        let debug_info = load_test_elf_as_debug_info("c-strings/c-strings.elf");
+        let static_variables = c_string_statics(&debug_info);
+        let value = |name: &str| {
+            static_variables
+                .get_variable_by_name(&VariableName::Named(name.to_string()))
+                .unwrap()
+                .get_value(&static_variables)
+        };
+
+        assert_eq!(value("greeting"), r#"0x0000000000402000 "Hello, world!""#);
+        assert_eq!(value("bytes"), r#"0x000000000040200E "\x01\x02""#);
+        // Strings are truncated after 256 characters.
+        assert_eq!(
+            value("long_text"),
+            format!(
+                r#"0x0000000000402018 "{}"..."#,
+                "0123456789".repeat(26).split_at(256).0
+            )
+        );
+        // Null pointers, and pointers to memory that can't be read, only show the address.
+        assert_eq!(value("no_string"), "0x0000000000000000");
+        assert_eq!(value("dangling"), "0x0000000000000010");
+        // Other pointers are unchanged.
+        assert_eq!(value("numbers"), "<referenced type> @ 0x0000000000403170");
+    }
+
+    #[test]
+    fn c_strings_limited_by_max_string_length() {
+        let mut options = DebugInfoOptions::new();
+        options.max_string_length = 5;
+        let debug_info = DebugInfo::from_file_with_options(
+            get_path_for_test_files("c-strings/c-strings.elf"),
+            options,
+        )
+        .unwrap();
+        let static_variables = c_string_statics(&debug_info);
+
+        let greeting = static_variables
+            .get_variable_by_name(&VariableName::Named("greeting".to_string()))
+            .unwrap();
+        assert_eq!(
+            greeting.get_value(&static_variables),
+            r#"0x0000000000402000 "Hello"..."#
+        );
+    }
+
+    /// Resolve the static variables of `c-strings.elf`, including the strings that the `char *` variables point to.
+    fn c_string_statics(debug_info: &DebugInfo) -> VariableCache {
+        let mut memory =
+            SectionMemory::from_file(get_path_for_test_files("c-strings/c-strings.elf")).unwrap();
+
+        // Resolving variables requires a program counter, which we set to `_start`.
+        let registers = x86_64_registers(|id| if id == RIP { 0x40_1000 } else { 0 });
+        let unit_info = &debug_info.unit_infos[0];
+        let mut static_variables = debug_info.create_static_scope_cache(unit_info).unwrap();
+        // The strings are read when the pointers are expanded.
+        static_variables.recurse_deferred_variables(
+            debug_info,
+            &mut memory,
+            None,
+            10,
+            0,
+            StackFrameInfo {
+                registers: &registers,
+                frame_base: None,
+                canonical_frame_address: None,
+                thread_local_storage_base: None,
+                registers_only: false,
+                frame_torn_down: false,
+            },
+        );
+        static_variables
+    }
+}
@@ -9,10 +9,12 @@ use std::ops::Range;
 /// Define the role that a variable plays in a Variant relationship. See section '5.7.10 Variant Entries' of the DWARF 5 specification
 #[derive(Debug, Clone, Eq, PartialEq, Default)]
 pub enum VariantRole {
-    /// A (parent) Variable that can have any number of Variant's as its value
-    VariantPart(u64),
+    /// A (parent) Variable that can have any number of Variant's as its value.
+    /// Holds the discriminant of the active variant, or `None` if the default variant is active.
+    VariantPart(Option<u64>),
     /// A (child) Variable that defines one of many possible types to hold the current value of a VariantPart.
-    Variant(u64),
+    /// Holds the discriminant value of this variant, or `None` for the default variant.
+    Variant(Option<u64>),
     /// This variable doesn't play a role in a Variant relationship
     #[default]
     NonVariant,
@@ -62,6 +64,37 @@ impl VariableValue {
     }
 }
 
+/// Controls which compiler generated (`DW_AT_artificial`) variables and parameters are included
+/// when the variables of a function are resolved, e.g. the `this` pointer of a C++ method,
+/// or the temporaries of a range based `for` loop.
+///
+/// Artificial members of types, e.g. the discriminant of a Rust enum, are never shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArtificialVariables {
+    /// Show the `this` or `self` receiver of a method, and hide all other artificial variables.
+    #[default]
+    ShowReceiver,
+    /// Hide all artificial variables, including the receiver.
+    Hide,
+    /// Show all artificial variables, like any other variable.
+    Show,
+    /// Show all artificial variables, with [`Variable::is_artificial`] set to `true`.
+    Mark,
+}
+
+impl ArtificialVariables {
+    /// Check if an artificial variable with the given `name` should be shown.
+    pub(crate) fn shows(&self, name: &VariableName) -> bool {
+        match self {
+            ArtificialVariables::ShowReceiver => {
+                matches!(name, VariableName::Named(name) if name == "this" || name == "self")
+            }
+            ArtificialVariables::Hide => false,
+            ArtificialVariables::Show | ArtificialVariables::Mark => true,
+        }
+    }
+}
+
 /// The type of variable we have at hand.
 #[derive(Debug, PartialEq, Eq, Clone, Default, Serialize)]
 pub enum VariableName {
@@ -188,7 +221,25 @@ impl VariableType {
         }
     }
 
+    /// Is this variable a Rust reference counted smart pointer (`Rc<T>` or `Arc<T>`)?
+    pub fn is_reference_counted(&self) -> bool {
+        match self {
+            VariableType::Struct(name) => name.starts_with("Rc<") || name.starts_with("Arc<"),
+            _ => false,
+        }
+    }
+
+    /// Is this variable a Rust clone-on-write smart pointer (`Cow<T>`)?
+    pub fn is_clone_on_write(&self) -> bool {
+        match self {
+            VariableType::Struct(name) => name.starts_with("Cow<"),
+            _ => false,
+        }
+    }
+
     /// Is this variable is a reference to another variable?
+    ///
+    /// Rust references (`&T` and `&mut T`) are encoded in DWARF as pointers, and are identified by the `&` prefix of their type name.
     pub fn is_reference(&self) -> bool {
         match self {
             VariableType::Pointer(Some(name)) => name.starts_with('&'),
@@ -196,6 +247,16 @@ impl VariableType {
         }
     }
 
+    /// Is this variable a raw pointer (`*const T` or `*mut T`)?
+    pub fn is_raw_pointer(&self) -> bool {
+        match self {
+            VariableType::Pointer(Some(name)) => {
+                name.starts_with("*const") || name.starts_with("*mut")
+            }
+            _ => false,
+        }
+    }
+
     /// Is this variable an array?
     pub fn is_array(&self) -> bool {
         matches!(self, VariableType::Array { .. })
@@ -229,8 +290,8 @@ pub enum VariableLocation {
     /// Location of the variable is not known. This means that it has not been evaluated yet.
     #[default]
     Unknown,
-    /// The variable does not have a location currently, probably due to optimisations.
-    Unavailable,
+    /// The variable does not have a location currently, for the given reason.
+    Unavailable(UnavailableReason),
     /// The variable can be found in memory, at this address.
     Address(u64),
     /// The value of the variable is directly available.
@@ -239,6 +300,59 @@ pub enum VariableLocation {
     Error(String),
     /// Support for handling the location of this variable is not (yet) implemented.
     Unsupported(String),
+    /// The parts of the variable are stored in different places, e.g. a `struct` with some members in registers,
+    /// and others optimized out. The location of each member is resolved from the part that contains it.
+    Pieces(Vec<VariablePiece>),
+}
+
+/// A part of a variable, that is stored separately from the other parts. See [`VariableLocation::Pieces`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariablePiece {
+    /// The offset, in bytes, of this part from the start of the variable.
+    pub byte_offset: u64,
+    /// The size of this part in bytes.
+    pub byte_size: u64,
+    /// The location of this part, which is one of:
+    /// - [`VariableLocation::Address`], for a part that is stored in memory.
+    /// - [`VariableLocation::Value`], for a part that is stored in a register, or is a constant, with the value in `value`.
+    /// - [`VariableLocation::Unavailable`], for a part that was optimized out.
+    pub location: VariableLocation,
+    /// The raw value of a part that is stored in a register, or is a constant.
+    pub value: Option<u64>,
+}
+
+/// The reason why a variable does not have a location.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnavailableReason {
+    /// The value of the variable was optimized away by the compiler.
+    OptimizedOut,
+    /// The variable has a location for only part of its scope,
+    /// and the current program counter is outside of those parts.
+    NotLiveHere,
+    /// The variable has no location of its own, e.g. a namespace, or a variable with an invalid value.
+    NoLocation,
+    /// The variable is stored relative to the frame base, but the program counter is in the epilogue
+    /// of the function, where the stack frame is being torn down.
+    FrameTornDown,
+    /// The variable is stored in memory, but only the registers are available,
+    /// see [`StackFrameInfo::registers_only`](crate::debug::stack_frame::StackFrameInfo::registers_only).
+    NeedsMemory,
+}
+
+impl std::fmt::Display for UnavailableReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UnavailableReason::OptimizedOut => {
+                "<value optimized away by compiler, out of scope, or dropped>".fmt(f)
+            }
+            UnavailableReason::NotLiveHere => "<value not live at this location>".fmt(f),
+            UnavailableReason::NoLocation => "<value not available>".fmt(f),
+            UnavailableReason::FrameTornDown => {
+                "<value not available in the function epilogue>".fmt(f)
+            }
+            UnavailableReason::NeedsMemory => "<value stored in memory, which is not read>".fmt(f),
+        }
+    }
 }
 
 impl VariableLocation {
@@ -255,27 +369,68 @@ impl VariableLocation {
     /// Check if the location is valid, ie. not an error, unsupported, or unavailable.
     pub fn valid(&self) -> bool {
         match self {
-            VariableLocation::Address(_) | VariableLocation::Value | VariableLocation::Unknown => {
-                true
-            }
+            VariableLocation::Address(_)
+            | VariableLocation::Value
+            | VariableLocation::Unknown
+            | VariableLocation::Pieces(_) => true,
             _other => false,
         }
     }
+
+    /// Format the location like its [`Display`](std::fmt::Display) implementation,
+    /// but with addresses padded to the width of an address of `address_size` bytes on the target.
+    pub fn format_with_address_size(&self, address_size: u8) -> String {
+        match self {
+            VariableLocation::Address(address) => super::format_address(*address, address_size),
+            other => other.to_string(),
+        }
+    }
 }
 
 impl std::fmt::Display for VariableLocation {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             VariableLocation::Unknown => "<unknown value>".fmt(f),
-            VariableLocation::Unavailable => "<value not available>".fmt(f),
-            VariableLocation::Address(address) => write!(f, "{address:#010X}"),
+            VariableLocation::Unavailable(reason) => reason.fmt(f),
+            // The address size of the target is not known here, so the address is at least as wide as a 32-bit address.
+            VariableLocation::Address(address) => super::format_address(*address, 4).fmt(f),
             VariableLocation::Value => "<not applicable - statically stored value>".fmt(f),
             VariableLocation::Error(error) => error.fmt(f),
             VariableLocation::Unsupported(reason) => reason.fmt(f),
+            VariableLocation::Pieces(_) => "<value stored in multiple pieces>".fmt(f),
         }
     }
 }
 
+/// Controls how much detail is included when a [`Variable`] value is rendered with [`Variable::get_value_formatted`].
+///
+/// The default options render the complete value, with integers in decimal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct VariableFormatOptions {
+    /// The number of nested levels of child values to include. Deeper values are collapsed to `{...}`.
+    /// `None` includes all levels.
+    pub max_depth: Option<usize>,
+    /// The maximum number of characters in the rendered value, after which it is truncated with `...`.
+    /// `None` never truncates.
+    pub max_length: Option<usize>,
+    /// Render integer values in hexadecimal, instead of decimal.
+    pub hexadecimal: bool,
+    /// The maximum number of elements of an array, or members of a struct, that are included in the value,
+    /// after which the remaining ones are summarized with `...`, e.g. `[1, 2, 3, ...]`.
+    /// `None` includes all elements and members.
+    pub summary_element_count: Option<usize>,
+}
+
+impl VariableFormatOptions {
+    /// The number of the `element_count` elements or members of a value that are included in its summary.
+    fn summary_count(&self, element_count: usize) -> usize {
+        self.summary_element_count
+            .map_or(element_count, |summary_element_count| {
+                summary_element_count.min(element_count)
+            })
+    }
+}
+
 /// The `Variable` struct is used in conjunction with `VariableCache` to cache data about variables.
 ///
 /// Any modifications to the `Variable` value will be transient (lost when it goes out of scope),
@@ -318,6 +473,27 @@ pub struct Variable {
     pub range_upper_bound: i64,
     /// The role of this variable.
     pub role: VariantRole,
+    /// For Rust enums and other tagged unions, the value of the discriminant that selected the active variant,
+    /// as it is encoded in memory, e.g. the value of the niche.
+    /// The active variant itself is the only child of the variable.
+    pub discriminant_value: Option<u64>,
+    /// For Rust enums and other tagged unions, the name of the active variant, e.g. `Err` for a `Result`.
+    /// See [`Variable::active_variant_name`].
+    pub(super) active_variant_name: Option<String>,
+    /// This variable was generated by the compiler (`DW_AT_artificial`), and is shown because of [`ArtificialVariables::Mark`].
+    pub is_artificial: bool,
+    /// This variable is a parameter of a function (`DW_TAG_formal_parameter`).
+    /// See [`DebugInfo::stack_frame_arguments`](super::DebugInfo::stack_frame_arguments).
+    pub is_parameter: bool,
+    /// The type of this variable is `volatile`, or it is a member of a `volatile` variable,
+    /// e.g. a memory mapped peripheral register, so its cached value may already be stale.
+    /// See [`VariableCache::read_volatile_value`](variable_cache::VariableCache::read_volatile_value).
+    pub is_volatile: bool,
+    /// The size of an address on the target, in bytes, used to format the addresses of this variable.
+    pub address_size: u8,
+    /// The bit layout, if this variable is a bitfield member.
+    /// The `bit_offset` is counted from the least significant bit of the byte at the `memory_location`.
+    pub bit_field: Option<BitFieldLayout>,
 }
 
 impl Variable {
@@ -330,6 +506,9 @@ impl Variable {
             language: unit_info
                 .map(|info| info.get_language())
                 .unwrap_or(gimli::DW_LANG_Rust),
+            address_size: unit_info
+                .map(|info| info.unit.header.address_size())
+                .unwrap_or(4),
 
             variable_key: Default::default(),
             parent_key: Default::default(),
@@ -344,6 +523,12 @@ impl Variable {
             range_lower_bound: 0,
             range_upper_bound: 0,
             role: Default::default(),
+            discriminant_value: None,
+            active_variant_name: None,
+            is_artificial: false,
+            is_parameter: false,
+            is_volatile: false,
+            bit_field: None,
         }
     }
 
@@ -369,7 +554,7 @@ impl Variable {
         }
         if !self.value.is_valid() {
             // If the value is invalid, then make sure we don't propogate invalid memory location values.
-            self.memory_location = VariableLocation::Unavailable;
+            self.memory_location = VariableLocation::Unavailable(UnavailableReason::NoLocation);
         }
     }
 
@@ -428,14 +613,41 @@ impl Variable {
 
     /// Implementing get_value(), because Variable.value has to be private (a requirement of updating the value without overriding earlier values ... see set_value()).
     pub fn get_value(&self, variable_cache: &variable_cache::VariableCache) -> String {
+        self.get_value_formatted(variable_cache, &VariableFormatOptions::default())
+    }
+
+    /// Get the human readable value of this variable, with the level of detail controlled by the [`VariableFormatOptions`].
+    pub fn get_value_formatted(
+        &self,
+        variable_cache: &variable_cache::VariableCache,
+        options: &VariableFormatOptions,
+    ) -> String {
+        let mut value = self.unbounded_value(variable_cache, options);
+        if let Some(max_length) = options.max_length {
+            if let Some((truncate_at, _)) = value.char_indices().nth(max_length) {
+                value.truncate(truncate_at);
+                value.push_str("...");
+            }
+        }
+        value
+    }
+
+    /// Get the value of this variable, without applying [`VariableFormatOptions::max_length`].
+    fn unbounded_value(
+        &self,
+        variable_cache: &variable_cache::VariableCache,
+        options: &VariableFormatOptions,
+    ) -> String {
         // Allow for chained `if let` without complaining
         if VariableNodeType::SvdRegister == self.variable_node_type {
             if let VariableValue::Valid(register_value) = &self.value {
                 if let Ok(register_u32_value) = register_value.parse::<u32>() {
                     format!(
-                        "{:032b} @ {:#010X}",
+                        "{:032b} @ {}",
                         register_u32_value,
-                        self.memory_location.memory_address().unwrap_or(u64::MAX) // We should never encounter a memory location that is invalid if we already used it to read the register value.
+                        // We should never encounter a memory location that is invalid if we already used it to read the register value.
+                        self.memory_location
+                            .format_with_address_size(self.address_size)
                     )
                 } else {
                     format!("Invalid register value {register_value}")
@@ -451,9 +663,10 @@ impl Variable {
                     bit_value <<= 32 - self.range_upper_bound;
                     bit_value >>= 32 - (self.range_upper_bound - self.range_lower_bound);
                     format!(
-                        "{:0width$b} @ {:#010X}:{}..{}",
+                        "{:0width$b} @ {}:{}..{}",
                         bit_value,
-                        self.memory_location.memory_address().unwrap_or(u64::MAX),
+                        self.memory_location
+                            .format_with_address_size(self.address_size),
                         self.range_lower_bound,
                         self.range_upper_bound,
                         width = self.subrange_bounds().count()
@@ -467,11 +680,18 @@ impl Variable {
             } else {
                 format!("{}", self.value)
             }
+        } else if let Some(referenced_value) = self.referenced_value(variable_cache, options) {
+            // References are automatically dereferenced, so we display the value they refer to.
+            referenced_value
+        } else if let Some(smart_pointer_value) = self.smart_pointer_value(variable_cache, options)
+        {
+            // Smart pointers display the value they point to, rather than their internal layout.
+            smart_pointer_value
         } else if !self.value.is_empty() {
             // The `value` for this `Variable` is non empty because ...
             // - It is base data type for which a value was determined based on the core runtime, or ...
             // - We encountered an error somewhere, so report it to the user
-            format!("{}", self.value)
+            self.formatted_base_value(options)
         } else if matches!(
             self.name,
             VariableName::AnonymousNamespace | VariableName::Namespace(_)
@@ -481,7 +701,7 @@ impl Variable {
         } else {
             // We need to construct a 'human readable' value using `fmt::Display` to represent the values of complex types and pointers.
             match variable_cache.has_children(self) {
-                Ok(true) => self.formatted_variable_value(variable_cache, 0_usize, false),
+                Ok(true) => self.formatted_variable_value(variable_cache, options, 0_usize, false),
                 Ok(false) => {
                     if self.type_name == VariableType::Unknown || !self.memory_location.valid() {
                         if self.variable_node_type.is_deferred() {
@@ -496,7 +716,7 @@ impl Variable {
                     {
                         "None".to_string()
                     } else if matches!(self.type_name, VariableType::Array { count: 0, .. }) {
-                        self.formatted_variable_value(variable_cache, 0_usize, false)
+                        self.formatted_variable_value(variable_cache, options, 0_usize, false)
                     } else {
                         format!(
                             "Unimplemented: Evaluate type {:?} of ({:?} bytes) at location 0x{:08x?}",
@@ -532,8 +752,9 @@ impl Variable {
                 Ok(u32_value) => self.value = VariableValue::Valid(u32_value.to_le().to_string()),
                 Err(error) => {
                     self.value = VariableValue::Error(format!(
-                        "Unable to read peripheral register value @ {:#010X}: {:?}",
-                        self.memory_location.memory_address().unwrap_or(u64::MAX),
+                        "Unable to read peripheral register value @ {}: {:?}",
+                        self.memory_location
+                            .format_with_address_size(self.address_size),
                         error
                     ))
                 }
@@ -554,8 +775,12 @@ impl Variable {
 
         if self.variable_node_type.is_deferred() {
             // And we have not previously assigned the value, then assign the type and address as the value
-            self.value =
-                VariableValue::Valid(format!("{} @ {}", self.type_name, self.memory_location));
+            self.value = VariableValue::Valid(format!(
+                "{} @ {}",
+                self.type_name,
+                self.memory_location
+                    .format_with_address_size(self.address_size)
+            ));
             return;
         }
 
@@ -569,6 +794,168 @@ impl Variable {
             language::from_dwarf(self.language).read_variable_value(self, memory, variable_cache);
     }
 
+    /// For a reference (`&T` or `&mut T`) or a `Box<T>`, the value of the variable it refers to,
+    /// formatted like the value of that variable itself, e.g. an array as the list of its members.
+    fn referenced_value(
+        &self,
+        variable_cache: &variable_cache::VariableCache,
+        options: &VariableFormatOptions,
+    ) -> Option<String> {
+        let referenced_variable = self.referenced_variable(variable_cache)?;
+        match referenced_variable.type_name {
+            VariableType::Array { .. } => {
+                let members = variable_cache
+                    .get_children(referenced_variable.variable_key)
+                    .ok()?;
+                Some(referenced_variable.formatted_array_members(
+                    &members,
+                    variable_cache,
+                    options,
+                    0,
+                ))
+            }
+            _ => Some(referenced_variable.unbounded_value(variable_cache, options)),
+        }
+    }
+
+    /// For a reference (`&T` or `&mut T`) or a `Box<T>`, the variable it refers to, if it has already been resolved.
+    /// Raw pointers are never dereferenced automatically, because they may be dangling or null.
+    fn referenced_variable(
+        &self,
+        variable_cache: &variable_cache::VariableCache,
+    ) -> Option<Variable> {
+        if !self.is_valid() {
+            return None;
+        }
+        let pointer_key = if self.type_name.is_reference() {
+            self.variable_key
+        } else {
+            self.box_pointer(variable_cache)?.variable_key
+        };
+        match variable_cache.get_children(pointer_key) {
+            Ok(children) if children.len() == 1 => children.into_iter().next(),
+            _ => None,
+        }
+    }
+
+    /// For a Rust `Box<T>`, the pointer to the value it owns, which is never null or dangling.
+    ///
+    /// A `Box<T>` is encoded in DWARF either as the pointer itself, which is the only Rust pointer type that is
+    /// not a reference, a raw pointer, or a function pointer, or as a structure whose first member is a `Unique<T>`,
+    /// that wraps a `NonNull<T>`, that wraps the pointer, e.g. if the allocator of the `Box<T, A>` is not zero sized.
+    fn box_pointer(&self, variable_cache: &variable_cache::VariableCache) -> Option<Variable> {
+        if self.language != gimli::DW_LANG_Rust {
+            return None;
+        }
+        match &self.type_name {
+            VariableType::Pointer(Some(name)) => (!self.type_name.is_reference()
+                && !self.type_name.is_raw_pointer()
+                && !["fn", "unsafe ", "extern "]
+                    .iter()
+                    .any(|prefix| name.starts_with(prefix)))
+            .then(|| self.clone()),
+            VariableType::Struct(_) => {
+                let unique = variable_cache
+                    .get_children(self.variable_key)
+                    .ok()?
+                    .into_iter()
+                    .next()
+                    .filter(|member| {
+                        matches!(&member.type_name, VariableType::Struct(name) if name.starts_with("Unique<"))
+                    })?;
+                let non_null = variable_cache
+                    .get_variable_by_name_and_parent(
+                        &VariableName::Named("pointer".to_string()),
+                        unique.variable_key,
+                    )
+                    .filter(|member| {
+                        matches!(&member.type_name, VariableType::Struct(name) if name.starts_with("NonNull<"))
+                    })?;
+                variable_cache
+                    .get_variable_by_name_and_parent(
+                        &VariableName::Named("pointer".to_string()),
+                        non_null.variable_key,
+                    )
+                    .filter(|pointer| matches!(pointer.type_name, VariableType::Pointer(_)))
+            }
+            _ => None,
+        }
+    }
+
+    /// For `Rc<T>`, `Arc<T>` and `Cow<T>`, the value of the data they point to, if it has already been resolved.
+    ///
+    /// `Rc<T>` and `Arc<T>` show the shared value, with the strong and weak reference counts reported
+    /// the same way as `Rc::strong_count` and `Rc::weak_count`. `Cow<T>` shows the active variant, e.g. `Borrowed("text")`.
+    fn smart_pointer_value(
+        &self,
+        variable_cache: &variable_cache::VariableCache,
+        options: &VariableFormatOptions,
+    ) -> Option<String> {
+        if self.type_name.is_reference_counted() {
+            // `Rc<T>` is a `NonNull<RcInner<T>>` (`RcBox<T>` in older versions), which in turn wraps a
+            // `*const RcInner<T>`. `Arc<T>` has the same layout, using `ArcInner<T>`.
+            let non_null = variable_cache.get_variable_by_name_and_parent(
+                &VariableName::Named("ptr".to_string()),
+                self.variable_key,
+            )?;
+            let pointer = variable_cache.get_variable_by_name_and_parent(
+                &VariableName::Named("pointer".to_string()),
+                non_null.variable_key,
+            )?;
+            let inner = variable_cache
+                .get_children(pointer.variable_key)
+                .ok()?
+                .into_iter()
+                .next()?;
+
+            let mut strong_count = None;
+            let mut weak_count = None;
+            let mut shared_value = None;
+            for field in variable_cache.get_children(inner.variable_key).ok()? {
+                match &field.name {
+                    VariableName::Named(name) if name == "strong" => {
+                        strong_count = innermost_value(variable_cache, field)
+                    }
+                    VariableName::Named(name) if name == "weak" => {
+                        weak_count = innermost_value(variable_cache, field)
+                    }
+                    _ => shared_value = Some(field),
+                }
+            }
+            // All strong references together hold one implicit weak reference.
+            let weak_count = weak_count?.saturating_sub(1);
+
+            Some(format!(
+                "{} (strong: {}, weak: {})",
+                shared_value?.unbounded_value(variable_cache, options),
+                strong_count?,
+                weak_count
+            ))
+        } else if self.type_name.is_clone_on_write() {
+            // The tagged union decoder resolves `Cow<T>` to the active `Borrowed` or `Owned` variant.
+            let variant = variable_cache
+                .get_children(self.variable_key)
+                .ok()?
+                .into_iter()
+                .next()?;
+            let payload = variable_cache.get_variable_by_name_and_parent(
+                &VariableName::Named("__0".to_string()),
+                variant.variable_key,
+            )?;
+            let payload_value = payload.unbounded_value(variable_cache, options);
+
+            Some(
+                if matches!(payload.type_name, VariableType::Struct(ref name) if name == "&str") {
+                    format!("{}({:?})", variant.name, payload_value)
+                } else {
+                    format!("{}({})", variant.name, payload_value)
+                },
+            )
+        } else {
+            None
+        }
+    }
+
     /// The variable is considered to be an 'indexed' variable if the name starts with two underscores followed by a number. e.g. "__1".
     /// TODO: Consider replacing this logic with `std::str::pattern::Pattern` when that API stabilizes
     pub fn is_indexed(&self) -> bool {
@@ -590,9 +977,39 @@ impl Variable {
         self.value.is_valid()
     }
 
+    /// The name of the active variant of a Rust enum, or other tagged union, e.g. `Err` for a `Result` that holds an error.
+    ///
+    /// This allows e.g. a condition like `result.is_err()` to be evaluated, by comparing the name of the variant.
+    /// Returns `None` if this variable is not a tagged union, or its discriminant does not match any variant.
+    pub fn active_variant_name(&self) -> Option<String> {
+        self.active_variant_name.clone()
+    }
+
+    /// Render the stored value of a base type, converting integers to hexadecimal if requested.
+    fn formatted_base_value(&self, options: &VariableFormatOptions) -> String {
+        if let (true, VariableValue::Valid(value), VariableType::Base(type_name)) =
+            (options.hexadecimal, &self.value, &self.type_name)
+        {
+            // Character values are not integers, even if they happen to look like one.
+            if !type_name.contains("char") {
+                if let Ok(integer_value) = value.parse::<i128>() {
+                    return if integer_value.is_negative() {
+                        format!("-{:#x}", integer_value.unsigned_abs())
+                    } else {
+                        format!("{integer_value:#x}")
+                    };
+                } else if let Ok(integer_value) = value.parse::<u128>() {
+                    return format!("{integer_value:#x}");
+                }
+            }
+        }
+        format!("{}", self.value)
+    }
+
     fn formatted_variable_value(
         &self,
         variable_cache: &variable_cache::VariableCache,
+        options: &VariableFormatOptions,
         indentation: usize,
         show_name: bool,
     ) -> String {
@@ -604,11 +1021,20 @@ impl Variable {
                 // Use the supplied value or error message.
                 format!(
                     "{}{:\t<indentation$}{}: {} = {}",
-                    line_feed, "", self.name, self.type_name, self.value
+                    line_feed,
+                    "",
+                    self.name,
+                    self.type_name,
+                    self.formatted_base_value(options)
                 )
             } else {
                 // Use the supplied value or error message.
-                format!("{}{:\t<indentation$}{}", line_feed, "", self.value)
+                format!(
+                    "{}{:\t<indentation$}{}",
+                    line_feed,
+                    "",
+                    self.formatted_base_value(options)
+                )
             }
         } else if let VariableName::AnonymousNamespace = self.name {
             // Namespaces do not have values
@@ -616,6 +1042,19 @@ impl Variable {
         } else if let VariableName::Namespace(_) = self.name {
             // Namespaces do not have values
             String::new()
+        } else if options
+            .max_depth
+            .is_some_and(|max_depth| indentation >= max_depth)
+        {
+            // We have reached the requested depth, so collapse the value of this variable.
+            if show_name {
+                format!(
+                    "{}{:\t<indentation$}{}: {} = {{...}}",
+                    line_feed, "", self.name, self.type_name
+                )
+            } else {
+                format!("{}{:\t<indentation$}{{...}}", line_feed, "")
+            }
         } else {
             // Infer a human readable value using the available children of this variable.
             let mut compound_value = String::new();
@@ -632,6 +1071,7 @@ impl Variable {
                             if let Some(first_child) = children.first() {
                                 first_child.formatted_variable_value(
                                     variable_cache,
+                                    options,
                                     indentation + 1,
                                     true,
                                 )
@@ -643,31 +1083,19 @@ impl Variable {
                     }
                     VariableType::Array { .. } => {
                         // Arrays
-                        compound_value = format!(
-                            "{}{}{:\t<indentation$}: {} = [",
-                            compound_value, line_feed, "", self.type_name,
-                        );
-                        let mut child_count: usize = 0;
-                        for child in children.iter() {
-                            child_count += 1;
-
-                            compound_value = format!(
-                                "{}{}{}",
-                                compound_value,
-                                child.formatted_variable_value(
-                                    variable_cache,
-                                    indentation + 1,
-                                    false
-                                ),
-                                if child_count == children.len() {
-                                    // Do not add a separator at the end of the list
-                                    ""
-                                } else {
-                                    ", "
-                                }
-                            );
-                        }
-                        format!("{}{}{:\t<indentation$}]", compound_value, line_feed, "")
+                        format!(
+                            "{}{}{:\t<indentation$}: {} = {}",
+                            compound_value,
+                            line_feed,
+                            "",
+                            self.type_name,
+                            self.formatted_array_members(
+                                &children,
+                                variable_cache,
+                                options,
+                                indentation
+                            )
+                        )
                     }
                     VariableType::Struct(name) if name == "Ok" || name == "Err" => {
                         // Handle special structure types like the variant values of `Option<>` and `Result<>`
@@ -681,6 +1109,7 @@ impl Variable {
                                 compound_value,
                                 child.formatted_variable_value(
                                     variable_cache,
+                                    options,
                                     indentation + 1,
                                     false
                                 )
@@ -702,11 +1131,12 @@ impl Variable {
                             let (mut pre_fix, mut post_fix): (Option<String>, Option<String>) =
                                 (None, None);
 
+                            let summary_count = options.summary_count(children.len());
                             let mut child_count: usize = 0;
 
                             let mut is_tuple = false;
 
-                            for child in children.iter() {
+                            for child in children.iter().take(summary_count) {
                                 child_count += 1;
                                 if pre_fix.is_none() && post_fix.is_none() {
                                     if let VariableName::Named(child_name) = &child.name {
@@ -762,6 +1192,7 @@ impl Variable {
                                     compound_value,
                                     child.formatted_variable_value(
                                         variable_cache,
+                                        options,
                                         indentation + 1,
                                         print_name
                                     ),
@@ -773,6 +1204,14 @@ impl Variable {
                                     }
                                 );
                             }
+                            if summary_count < children.len() {
+                                compound_value = format!(
+                                    "{}\n{:\t<width$}...",
+                                    compound_value,
+                                    "",
+                                    width = indentation + 1
+                                );
+                            }
                             if let Some(post_fix) = &post_fix {
                                 compound_value = format!("{compound_value}{post_fix}");
                             };
@@ -787,6 +1226,44 @@ impl Variable {
         }
     }
 
+    /// Format the `members` of an array variable as a list, e.g. `[1, 2, 3]`, with each member on its own line.
+    fn formatted_array_members(
+        &self,
+        members: &[Variable],
+        variable_cache: &variable_cache::VariableCache,
+        options: &VariableFormatOptions,
+        indentation: usize,
+    ) -> String {
+        let line_feed = if indentation == 0 { "" } else { "\n" };
+        let mut compound_value = "[".to_string();
+        let summary_count = options.summary_count(members.len());
+        let mut child_count: usize = 0;
+        for child in members.iter().take(summary_count) {
+            child_count += 1;
+
+            compound_value = format!(
+                "{}{}{}",
+                compound_value,
+                child.formatted_variable_value(variable_cache, options, indentation + 1, false),
+                if child_count == members.len() {
+                    // Do not add a separator at the end of the list
+                    ""
+                } else {
+                    ", "
+                }
+            );
+        }
+        if summary_count < members.len() {
+            compound_value = format!(
+                "{}\n{:\t<width$}...",
+                compound_value,
+                "",
+                width = indentation + 1
+            );
+        }
+        format!("{}{}{:\t<indentation$}]", compound_value, line_feed, "")
+    }
+
     /// Calculate the memory range that contains the value of this variable.
     /// If the location and/or byte size is not known, then return None.
     /// Note: We don't do any validation of the memory range here, and leave it up to the caller to
@@ -806,7 +1283,567 @@ impl Variable {
         }
     }
 
+    /// Get the address ranges where this variable has a valid location, e.g. to show its lifetime
+    /// along the address range of a function.
+    ///
+    /// A variable with a location list has a range for each entry of the list, while a variable with a
+    /// single location is live in its whole scope. Returns an empty list if the variable has no location,
+    /// or was not created from the debug information.
+    pub fn liveness_ranges(&self, debug_info: &DebugInfo) -> Result<Vec<Range<u64>>, DebugError> {
+        let (Some(header_offset), Some(variable_offset)) =
+            (self.unit_header_offset, self.variable_unit_offset)
+        else {
+            return Ok(Vec::new());
+        };
+
+        let Some(unit_info) = debug_info.unit_info_at_offset(header_offset) else {
+            return Err(DebugError::Other(anyhow::anyhow!(
+                "The unit at {header_offset:?} is not in the debug information."
+            )));
+        };
+        unit_info.variable_liveness_ranges(debug_info, variable_offset)
+    }
+
     pub(crate) fn subrange_bounds(&self) -> Range<i64> {
         self.range_lower_bound..self.range_upper_bound
     }
 }
+
+/// Follow the first child of each level of wrapper types, e.g. `Cell<usize>` or `AtomicUsize`,
+/// to the integer value they contain.
+fn innermost_value(
+    variable_cache: &variable_cache::VariableCache,
+    variable: Variable,
+) -> Option<usize> {
+    let mut variable = variable;
+    loop {
+        if let (VariableType::Base(_), VariableValue::Valid(value)) =
+            (&variable.type_name, &variable.value)
+        {
+            return value.parse().ok();
+        }
+        variable = variable_cache
+            .get_children(variable.variable_key)
+            .ok()?
+            .into_iter()
+            .next()?;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        debug::{stack_frame::StackFrameInfo, VariableCache},
+        test::{
+            load_test_elf_as_debug_info,
+            x86_64::{RIP, RSP},
+            x86_64_registers, MockMemory,
+        },
+    };
+    use test_case::test_case;
+
+    /// Add a variable with the given name, type and (optional) value to the `cache`, as a child of `parent_key`.
+    fn add_variable(
+        cache: &mut VariableCache,
+        parent_key: ObjectRef,
+        name: &str,
+        type_name: VariableType,
+        value: Option<&str>,
+    ) -> Variable {
+        let mut variable = cache.create_variable(parent_key, None, None).unwrap();
+        variable.name = VariableName::Named(name.to_string());
+        variable.type_name = type_name;
+        if let Some(value) = value {
+            variable.set_value(VariableValue::Valid(value.to_string()));
+        }
+        cache.update_variable(&variable).unwrap();
+        variable
+    }
+
+    /// Build a cache containing `config: Config { id: u32, limits: Limits { low: i16, high: i16 } }`.
+    fn build_config_variable() -> (VariableCache, Variable) {
+        let mut cache = VariableCache::new_svd_cache();
+        let root_key = cache.root_variable().variable_key;
+
+        let config = add_variable(
+            &mut cache,
+            root_key,
+            "config",
+            VariableType::Struct("Config".to_string()),
+            None,
+        );
+        add_variable(
+            &mut cache,
+            config.variable_key,
+            "id",
+            VariableType::Base("u32".to_string()),
+            Some("255"),
+        );
+        let limits = add_variable(
+            &mut cache,
+            config.variable_key,
+            "limits",
+            VariableType::Struct("Limits".to_string()),
+            None,
+        );
+        add_variable(
+            &mut cache,
+            limits.variable_key,
+            "low",
+            VariableType::Base("i16".to_string()),
+            Some("-16"),
+        );
+        add_variable(
+            &mut cache,
+            limits.variable_key,
+            "high",
+            VariableType::Base("i16".to_string()),
+            Some("4096"),
+        );
+
+        (cache, config)
+    }
+
+    #[test]
+    fn default_format_options_render_complete_value() {
+        let (cache, config) = build_config_variable();
+
+        assert_eq!(
+            config.get_value_formatted(&cache, &VariableFormatOptions::default()),
+            config.get_value(&cache)
+        );
+        assert_eq!(
+            config.get_value(&cache),
+            "Config {\n\tid: u32 = 255, \n\tlimits: Limits = Limits {\n\t\tlow: i16 = -16, \n\t\thigh: i16 = 4096\n\t}}"
+        );
+    }
+
+    #[test]
+    fn format_options_limit_depth() {
+        let (cache, config) = build_config_variable();
+
+        let options = VariableFormatOptions {
+            max_depth: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(
+            config.get_value_formatted(&cache, &options),
+            "Config {\n\tid: u32 = 255, \n\tlimits: Limits = {...}}"
+        );
+    }
+
+    #[test]
+    fn format_options_limit_length() {
+        let (cache, config) = build_config_variable();
+
+        let options = VariableFormatOptions {
+            max_length: Some(8),
+            ..Default::default()
+        };
+        assert_eq!(config.get_value_formatted(&cache, &options), "Config {...");
+    }
+
+    #[test]
+    fn format_options_hexadecimal() {
+        let (cache, config) = build_config_variable();
+
+        let options = VariableFormatOptions {
+            hexadecimal: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            config.get_value_formatted(&cache, &options),
+            "Config {\n\tid: u32 = 0xff, \n\tlimits: Limits = Limits {\n\t\tlow: i16 = -0x10, \n\t\thigh: i16 = 0x1000\n\t}}"
+        );
+    }
+
+    #[test]
+    fn format_options_summary_element_count() {
+        let (cache, config) = build_config_variable();
+
+        let options = VariableFormatOptions {
+            summary_element_count: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(
+            config.get_value_formatted(&cache, &options),
+            "Config {\n\tid: u32 = 255, \n\t...}"
+        );
+
+        // A count that includes all members does not add an ellipsis.
+        let options = VariableFormatOptions {
+            summary_element_count: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(
+            config.get_value_formatted(&cache, &options),
+            config.get_value(&cache)
+        );
+    }
+
+    #[test]
+    fn format_options_summary_element_count_of_array() {
+        let mut cache = VariableCache::new_svd_cache();
+        let root_key = cache.root_variable().variable_key;
+
+        let array = add_variable(
+            &mut cache,
+            root_key,
+            "values",
+            VariableType::Array {
+                item_type_name: "u8".to_string(),
+                count: 5,
+            },
+            None,
+        );
+        for (index, value) in ["1", "2", "3", "4", "5"].into_iter().enumerate() {
+            add_variable(
+                &mut cache,
+                array.variable_key,
+                &format!("__{index}"),
+                VariableType::Base("u8".to_string()),
+                Some(value),
+            );
+        }
+
+        let options = VariableFormatOptions {
+            summary_element_count: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(
+            array.get_value_formatted(&cache, &options),
+            ": [u8; 5] = [\n\t1, \n\t2, \n\t3, \n\t...]"
+        );
+    }
+
+    /// Build a cache containing a pointer variable of the given type, which points to a `u32` with value `42`.
+    fn build_pointer_variable(pointer_type_name: &str) -> (VariableCache, Variable) {
+        let mut cache = VariableCache::new_svd_cache();
+        let root_key = cache.root_variable().variable_key;
+
+        let pointer = add_variable(
+            &mut cache,
+            root_key,
+            "value_ptr",
+            VariableType::Pointer(Some(pointer_type_name.to_string())),
+            Some(&format!("{pointer_type_name} @ 0x20003CD8")),
+        );
+        add_variable(
+            &mut cache,
+            pointer.variable_key,
+            "*value_ptr",
+            VariableType::Base("u32".to_string()),
+            Some("42"),
+        );
+
+        (cache, pointer)
+    }
+
+    #[test]
+    fn reference_is_dereferenced() {
+        let (cache, reference) = build_pointer_variable("&u32");
+
+        assert!(reference.type_name.is_reference());
+        assert!(!reference.type_name.is_raw_pointer());
+        assert_eq!(reference.get_value(&cache), "42");
+        assert_eq!(reference.type_name.to_string(), "&u32");
+    }
+
+    #[test]
+    fn mutable_reference_is_dereferenced() {
+        let (cache, reference) = build_pointer_variable("&mut u32");
+
+        assert!(reference.type_name.is_reference());
+        assert_eq!(reference.get_value(&cache), "42");
+        assert_eq!(reference.type_name.to_string(), "&mut u32");
+    }
+
+    #[test]
+    fn raw_pointer_is_not_dereferenced() {
+        let (cache, pointer) = build_pointer_variable("*const u32");
+
+        assert!(!pointer.type_name.is_reference());
+        assert!(pointer.type_name.is_raw_pointer());
+        assert_eq!(pointer.get_value(&cache), "*const u32 @ 0x20003CD8");
+    }
+
+    #[test]
+    fn box_is_dereferenced() {
+        let (cache, boxed) = build_pointer_variable("alloc::boxed::Box<u32, alloc::alloc::Global>");
+
+        assert_eq!(boxed.get_value(&cache), "42");
+    }
+
+    #[test]
+    fn function_pointer_is_not_dereferenced() {
+        let (cache, pointer) = build_pointer_variable("fn(u32) -> u32");
+
+        assert_eq!(pointer.get_value(&cache), "fn(u32) -> u32 @ 0x20003CD8");
+    }
+
+    #[test]
+    fn box_with_allocator_is_dereferenced() {
+        // `Box<T, A>` is a structure, if the allocator is not zero sized.
+        let mut cache = VariableCache::new_svd_cache();
+        let root_key = cache.root_variable().variable_key;
+        let boxed = add_variable(
+            &mut cache,
+            root_key,
+            "boxed",
+            VariableType::Struct("Box<u32, Pool>".to_string()),
+            None,
+        );
+        let mut parent_key = boxed.variable_key;
+        for (name, type_name) in [("__0", "Unique<u32>"), ("pointer", "NonNull<u32>")] {
+            parent_key = add_variable(
+                &mut cache,
+                parent_key,
+                name,
+                VariableType::Struct(type_name.to_string()),
+                None,
+            )
+            .variable_key;
+        }
+        let pointer = add_variable(
+            &mut cache,
+            parent_key,
+            "pointer",
+            VariableType::Pointer(Some("*const u32".to_string())),
+            None,
+        );
+        add_variable(
+            &mut cache,
+            pointer.variable_key,
+            "*pointer",
+            VariableType::Base("u32".to_string()),
+            Some("42"),
+        );
+
+        assert_eq!(boxed.get_value(&cache), "42");
+    }
+
+    #[test]
+    fn address_renders_with_target_width() {
+        let location = VariableLocation::Address(0x2000_0100);
+
+        assert_eq!(location.format_with_address_size(4), "0x20000100");
+        assert_eq!(location.format_with_address_size(8), "0x0000000020000100");
+        assert_eq!(
+            VariableLocation::Address(0xFFFF_8000_0000_1000).format_with_address_size(8),
+            "0xFFFF800000001000"
+        );
+        assert_eq!(
+            VariableLocation::Unknown.format_with_address_size(8),
+            "<unknown value>"
+        );
+    }
+
+    #[test_case("c-optimized/c-optimized.elf", 0x40_101D, "a", &[(0x40_1010, 0x40_1018), (0x40_1018, 0x40_1022), (0x40_1022, 0x40_1028)]; "moves between registers")]
+    #[test_case("c-optimized/c-optimized.elf", 0x40_101D, "first", &[(0x40_1018, 0x40_101F)]; "live in part of its scope")]
+    #[test_case("cpp-artificial/cpp-artificial.elf", 0x40_1049, "this", &[(0x40_101C, 0x40_106C)]; "single location")]
+    fn variable_liveness_ranges(
+        elf_file: &str,
+        program_counter: u64,
+        variable_name: &str,
+        expected_ranges: &[(u64, u64)],
+    ) {
+        let debug_info = load_test_elf_as_debug_info(elf_file);
+
+        let (unit_info, function_die) = debug_info
+            .unit_infos
+            .iter()
+            .find_map(|unit_info| {
+                unit_info
+                    .get_function_dies(&debug_info, program_counter, false)
+                    .ok()?
+                    .into_iter()
+                    .next()
+                    .map(|function_die| (unit_info, function_die))
+            })
+            .unwrap();
+        // Find the parameter or variable in the scope of the function.
+        let mut tree = unit_info
+            .unit
+            .header
+            .entries_tree(
+                &unit_info.unit.abbreviations,
+                Some(function_die.function_die.offset()),
+            )
+            .unwrap();
+        let mut children = tree.root().unwrap().children();
+        let mut variable = None;
+        while let Some(child) = children.next().unwrap() {
+            let Some(name) = child.entry().attr_value(gimli::DW_AT_name).unwrap() else {
+                continue;
+            };
+            if extract_name(&debug_info, name) == variable_name {
+                variable = Some(Variable::new(Some(child.entry().offset()), Some(unit_info)));
+                break;
+            }
+        }
+
+        let expected_ranges = expected_ranges
+            .iter()
+            .map(|&(begin, end)| begin..end)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            variable.unwrap().liveness_ranges(&debug_info).unwrap(),
+            expected_ranges
+        );
+    }
+
+    #[test]
+    fn rust_smart_pointers() {
+        let mut stack = vec![0u8; 0xc0];
+        for (offset, value) in [
+            // shared, shared_clone and atomic
+            (0x08, 0x1000_1000_u64),
+            (0x10, 0x1000_1000),
+            (0x18, 0x1000_1020),
+            // borrowed: the capacity niche, followed by the `&str`
+            (0x20, 0x8000_0000_0000_0000),
+            (0x28, 0x1000_1040),
+            (0x30, 8),
+            // owned: capacity, pointer and length of the `String`
+            (0x38, 5),
+            (0x40, 0x1000_1048),
+            (0x48, 5),
+        ] {
+            stack[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+        }
+        let mut heap = vec![0u8; 0x50];
+        // `RcInner<u32>`, with one `Weak` reference, and `ArcInner<u16>`.
+        heap[0x00..0x08].copy_from_slice(&2_u64.to_le_bytes());
+        heap[0x08..0x10].copy_from_slice(&2_u64.to_le_bytes());
+        heap[0x10..0x14].copy_from_slice(&42_u32.to_le_bytes());
+        heap[0x20..0x28].copy_from_slice(&1_u64.to_le_bytes());
+        heap[0x28..0x30].copy_from_slice(&1_u64.to_le_bytes());
+        heap[0x30..0x32].copy_from_slice(&7_u16.to_le_bytes());
+        heap[0x40..0x4d].copy_from_slice(b"borrowedowned");
+
+        // Before the call to `black_box` in `smart_pointers`, with the locals at `rsp` + 0x08..0x50.
+        let local_variables = rust_smart_pointer_function_variables(0x20_36c0, stack, heap);
+
+        let value_of = |name: &str| {
+            local_variables
+                .get_variable_by_name(&VariableName::Named(name.to_string()))
+                .unwrap()
+                .get_value(&local_variables)
+        };
+        assert_eq!(value_of("shared"), "42 (strong: 2, weak: 1)");
+        assert_eq!(value_of("shared_clone"), "42 (strong: 2, weak: 1)");
+        assert_eq!(value_of("atomic"), "7 (strong: 1, weak: 0)");
+        assert_eq!(value_of("borrowed"), r#"Borrowed("borrowed")"#);
+        assert!(value_of("owned").starts_with("Owned("));
+    }
+
+    /// The local variables of the function at `program_counter` in the `rust-smart-pointers` test binary,
+    /// with `stack` at `rsp` (0x1000_0000) and `heap` at 0x1000_1000.
+    fn rust_smart_pointer_function_variables(
+        program_counter: u64,
+        stack: Vec<u8>,
+        heap: Vec<u8>,
+    ) -> VariableCache {
+        let debug_info = load_test_elf_as_debug_info("rust-smart-pointers/smart-pointers.elf");
+
+        let (unit_info, function_die) = debug_info
+            .unit_infos
+            .iter()
+            .find_map(|unit_info| {
+                let function_die = unit_info
+                    .get_function_dies(&debug_info, program_counter, false)
+                    .ok()?
+                    .pop()?;
+                Some((unit_info, function_die))
+            })
+            .unwrap();
+        let registers = x86_64_registers(|id| match id {
+            RSP => 0x1000_0000,
+            RIP => program_counter,
+            _ => 0,
+        });
+
+        let mut memory = MockMemory::new();
+        memory.add_range(0x1000_0000, stack);
+        memory.add_range(0x1000_1000, heap);
+
+        let frame_info = StackFrameInfo {
+            registers: &registers,
+            frame_base: None,
+            canonical_frame_address: None,
+            thread_local_storage_base: None,
+            registers_only: false,
+            frame_torn_down: false,
+        };
+        let frame_base = function_die
+            .frame_base(&debug_info, &mut memory, frame_info)
+            .unwrap();
+        let mut local_variables = debug_info
+            .create_function_scope_cache(&function_die, unit_info)
+            .unwrap();
+        local_variables.recurse_deferred_variables(
+            &debug_info,
+            &mut memory,
+            None,
+            10,
+            0,
+            StackFrameInfo {
+                frame_base,
+                ..frame_info
+            },
+        );
+        local_variables
+    }
+
+    #[test]
+    fn rust_linked_list() {
+        let mut stack = vec![0u8; 0x48];
+        // list: the `Box<Node>` of the head, where null would be the niche of `None`.
+        stack[0x00..0x08].copy_from_slice(&0x1000_1010_u64.to_le_bytes());
+        let mut heap = vec![0u8; 0x20];
+        // The tail `Node`, with a null `next`, followed by the head `Node`, which points to the tail.
+        heap[0x08..0x0c].copy_from_slice(&2_u32.to_le_bytes());
+        heap[0x10..0x18].copy_from_slice(&0x1000_1000_u64.to_le_bytes());
+        heap[0x18..0x1c].copy_from_slice(&1_u32.to_le_bytes());
+
+        // Before the call to `black_box` in `linked_list`, with the locals at `rsp` + 0x00..0x18.
+        let local_variables = rust_smart_pointer_function_variables(0x20_35c0, stack, heap);
+        let child = |parent: &Variable, name: &str| {
+            local_variables
+                .get_variable_by_name_and_parent(
+                    &VariableName::Named(name.to_string()),
+                    parent.variable_key,
+                )
+                .unwrap()
+        };
+        // Follow `Some(Box<Node>)` to the `Node` it points to.
+        let dereference = |option: &Variable| {
+            assert_eq!(option.active_variant_name(), Some("Some".to_string()));
+            let boxed = child(&child(option, "Some"), "__0");
+            let node = local_variables
+                .get_children(boxed.variable_key)
+                .unwrap()
+                .pop()
+                .unwrap();
+            // The `Box<Node>` is dereferenced automatically, like a reference.
+            assert_eq!(
+                boxed.get_value(&local_variables),
+                node.get_value(&local_variables)
+            );
+            node
+        };
+
+        let list = local_variables
+            .get_variable_by_name(&VariableName::Named("list".to_string()))
+            .unwrap();
+        let head = dereference(&list);
+        assert_eq!(child(&head, "value").get_value(&local_variables), "1");
+        let next = child(&head, "next");
+
+        let tail = dereference(&next);
+        assert_eq!(child(&tail, "value").get_value(&local_variables), "2");
+        let end = child(&tail, "next");
+        assert_eq!(end.active_variant_name(), Some("None".to_string()));
+    }
+}
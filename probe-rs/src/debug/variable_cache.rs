@@ -1,6 +1,9 @@
 use super::*;
 use crate::{
-    debug::{stack_frame::StackFrameInfo, unit_info::UnitInfo},
+    debug::{
+        stack_frame::StackFrameInfo,
+        unit_info::{ExpressionResult, UnitInfo},
+    },
     Error,
 };
 use anyhow::anyhow;
@@ -368,6 +371,55 @@ impl VariableCache {
         }
     }
 
+    /// Find the variable whose value occupies `address` in target memory, e.g. to identify the variable in a memory view.
+    ///
+    /// When several variables contain `address`, e.g. a struct and its members, the one with the smallest
+    /// memory range is returned. Variables that are only reached by dereferencing a pointer are skipped,
+    /// because their memory belongs to another variable.
+    pub fn variable_at_address(&self, address: u64) -> Option<Variable> {
+        self.variable_hash_map
+            .values()
+            .filter(|variable| variable.byte_size != Some(0))
+            .filter_map(|variable| {
+                let memory_range = variable.memory_range()?;
+                memory_range
+                    .contains(&address)
+                    .then_some((variable, memory_range))
+            })
+            .filter(|(variable, _)| !self.is_dereferenced(variable))
+            .min_by_key(|(variable, memory_range)| {
+                // Members can have the same size as their parent, in which case the deepest one is the most specific.
+                (
+                    memory_range.end - memory_range.start,
+                    std::cmp::Reverse(self.depth(variable)),
+                )
+            })
+            .map(|(variable, _)| variable.clone())
+    }
+
+    /// Is `variable` a child of a pointer, at any level?
+    fn is_dereferenced(&self, variable: &Variable) -> bool {
+        let mut parent_key = variable.parent_key;
+        while let Some(parent) = self.variable_hash_map.get(&parent_key) {
+            if matches!(parent.type_name, VariableType::Pointer(_)) {
+                return true;
+            }
+            parent_key = parent.parent_key;
+        }
+        false
+    }
+
+    /// The number of ancestors of `variable` in this cache.
+    fn depth(&self, variable: &Variable) -> usize {
+        let mut depth = 0;
+        let mut parent_key = variable.parent_key;
+        while let Some(parent) = self.variable_hash_map.get(&parent_key) {
+            depth += 1;
+            parent_key = parent.parent_key;
+        }
+        depth
+    }
+
     /// Retrieve `clone`d version of all the children of a `Variable`.
     /// If `parent_key == None`, it will return all the top level variables (no parents) in this cache.
     pub fn get_children(&self, parent_key: ObjectRef) -> Result<Vec<Variable>, Error> {
@@ -485,6 +537,248 @@ impl VariableCache {
         }
     }
 
+    /// Read the value of a single variable again, e.g. for a watched variable after the core halted again,
+    /// without rebuilding the rest of the cache.
+    ///
+    /// The location of a variable or parameter is evaluated again with `frame_info`:
+    /// - If the location is unchanged, the values of the variable and its resolved children are read again in place,
+    ///   including the values that could not be read before.
+    /// - If the location changed, e.g. when an optimized variable moved to another register,
+    ///   the variable and its children are resolved again. The variable, and the children that were resolved before,
+    ///   keep their keys, and the children that were already expanded stay expanded.
+    /// - If the variable is not live at the program counter, e.g. because it went out of scope,
+    ///   the location becomes [`UnavailableReason::NotLiveHere`], and its children are removed.
+    pub fn refresh(
+        &mut self,
+        variable_key: ObjectRef,
+        debug_info: &DebugInfo,
+        memory: &mut dyn MemoryInterface,
+        frame_info: StackFrameInfo<'_>,
+    ) -> Result<(), DebugError> {
+        let Some(mut variable) = self.get_variable_by_key(variable_key) else {
+            return Err(anyhow!(
+                "VariableCache: Attempted to refresh a non existent variable with key: {:?}.",
+                variable_key
+            )
+            .into());
+        };
+
+        let (Some(header_offset), Some(variable_offset)) =
+            (variable.unit_header_offset, variable.variable_unit_offset)
+        else {
+            // Without debug information, e.g. for the members of a type, only the values can be read again.
+            return Ok(self.read_values_again(variable_key, memory)?);
+        };
+        let Some(unit_info) = debug_info.unit_info_at_offset(header_offset) else {
+            return Err(anyhow!(
+                "VariableCache: The unit at {header_offset:?} is not in the debug information."
+            )
+            .into());
+        };
+        let variable_die = unit_info.unit.entry(variable_offset)?;
+        if !matches!(
+            variable_die.tag(),
+            gimli::DW_TAG_variable | gimli::DW_TAG_formal_parameter
+        ) {
+            // Members follow the location of their parent.
+            return Ok(self.read_values_again(variable_key, memory)?);
+        }
+
+        if unit_info.is_in_function(variable_offset)? {
+            let program_counter: u64 = frame_info
+                .registers
+                .get_program_counter()
+                .and_then(|register| register.value)
+                .ok_or_else(|| DebugError::UnwindIncompleteResults {
+                    message: "Cannot refresh a variable without a valid PC (program_counter)"
+                        .to_string(),
+                })?
+                .try_into()?;
+            if !variable
+                .liveness_ranges(debug_info)?
+                .iter()
+                .any(|range| range.contains(&program_counter))
+            {
+                self.remove_cache_entry_children(variable_key)?;
+                variable.memory_location =
+                    VariableLocation::Unavailable(UnavailableReason::NotLiveHere);
+                variable.value = VariableValue::Error(UnavailableReason::NotLiveHere.to_string());
+                self.update_variable(&variable)?;
+                return Ok(());
+            }
+        }
+
+        let Some(mut parent_variable) = self.get_variable_by_key(variable.parent_key) else {
+            return Err(anyhow!(
+                "VariableCache: Attempted to refresh variable {:?} with non existent `parent_key`: {:?}. Please report this as a bug.",
+                variable.name,
+                variable.parent_key
+            )
+            .into());
+        };
+        let location = match unit_info.extract_location(
+            debug_info,
+            &variable_die,
+            &parent_variable.memory_location,
+            memory,
+            frame_info,
+        )? {
+            ExpressionResult::Location(location) => location,
+            // The value is part of the location expression, so it has to be resolved again.
+            ExpressionResult::Value(_) => VariableLocation::Value,
+        };
+        if matches!(location, VariableLocation::Address(_)) && location == variable.memory_location
+        {
+            return Ok(self.read_values_again(variable_key, memory)?);
+        }
+
+        // Resolve the variable again, like when the scope was first cached, but keep its key.
+        let previous_children = self.take_children(variable_key);
+        let mut resolved_variable = Variable::new(Some(variable_offset), Some(unit_info));
+        resolved_variable.variable_key = variable_key;
+        let mut variable_tree = unit_info
+            .unit
+            .header
+            .entries_tree(&unit_info.unit.abbreviations, Some(variable_offset))?;
+        let mut variable_node = variable_tree.root()?;
+        let resolved_variable = unit_info.process_tree_node_attributes(
+            debug_info,
+            &mut variable_node,
+            &mut parent_variable,
+            resolved_variable,
+            memory,
+            self,
+            frame_info,
+        )?;
+        if resolved_variable.is_valid() {
+            unit_info.process_tree(
+                debug_info,
+                variable_node,
+                resolved_variable,
+                memory,
+                self,
+                frame_info,
+            )?;
+        }
+        self.restore_resolved_children(
+            variable_key,
+            &previous_children,
+            debug_info,
+            memory,
+            frame_info,
+        )
+    }
+
+    /// Remove the children of `parent_key`, and all their children, from the cache, and return them.
+    fn take_children(&mut self, parent_key: ObjectRef) -> Vec<Variable> {
+        let mut children = Vec::new();
+        let mut parent_keys = vec![parent_key];
+        while let Some(parent_key) = parent_keys.pop() {
+            let child_keys = self
+                .variable_hash_map
+                .values()
+                .filter(|child_variable| child_variable.parent_key == parent_key)
+                .map(|child_variable| child_variable.variable_key)
+                .collect::<Vec<_>>();
+            for child_key in child_keys {
+                if let Some(child) = self.variable_hash_map.remove(&child_key) {
+                    children.push(child);
+                }
+                parent_keys.push(child_key);
+            }
+        }
+        children
+    }
+
+    /// Give the children of `parent_key` the keys of the `previous_variables` with the same name and parent,
+    /// and resolve the children of those that were resolved before, so that a refreshed variable keeps its expanded children.
+    fn restore_resolved_children(
+        &mut self,
+        parent_key: ObjectRef,
+        previous_variables: &[Variable],
+        debug_info: &DebugInfo,
+        memory: &mut dyn MemoryInterface,
+        frame_info: StackFrameInfo<'_>,
+    ) -> Result<(), DebugError> {
+        for mut child in self.get_children(parent_key)? {
+            let Some(previous_child) = previous_variables.iter().find(|previous_variable| {
+                previous_variable.parent_key == parent_key && previous_variable.name == child.name
+            }) else {
+                continue;
+            };
+
+            let resolved_key = child.variable_key;
+            self.variable_hash_map.remove(&resolved_key);
+            self.variable_hash_map
+                .values_mut()
+                .filter(|grand_child| grand_child.parent_key == resolved_key)
+                .for_each(|grand_child| grand_child.parent_key = previous_child.variable_key);
+            child.variable_key = previous_child.variable_key;
+            self.variable_hash_map
+                .insert(child.variable_key, child.clone());
+
+            if previous_variables
+                .iter()
+                .any(|previous_variable| previous_variable.parent_key == child.variable_key)
+            {
+                debug_info.cache_deferred_variables(self, memory, &mut child, frame_info)?;
+                self.restore_resolved_children(
+                    child.variable_key,
+                    previous_variables,
+                    debug_info,
+                    memory,
+                    frame_info,
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Read the value of a `volatile` variable, e.g. a memory mapped peripheral register, and its members,
+    /// from the target again, even if the core did not run since they were cached.
+    ///
+    /// Other variables keep their cached values.
+    pub fn read_volatile_value(
+        &mut self,
+        variable_key: ObjectRef,
+        memory: &mut dyn MemoryInterface,
+    ) -> Result<(), Error> {
+        match self.get_variable_by_key(variable_key) {
+            Some(variable) if variable.is_volatile => self.read_values_again(variable_key, memory),
+            _ => Ok(()),
+        }
+    }
+
+    /// Read the values of a variable and its resolved children again, from their current locations.
+    fn read_values_again(
+        &mut self,
+        variable_key: ObjectRef,
+        memory: &mut dyn MemoryInterface,
+    ) -> Result<(), Error> {
+        let Some(mut variable) = self.get_variable_by_key(variable_key) else {
+            return Ok(());
+        };
+
+        // Values that were not read from memory, e.g. constants, stay the same.
+        // Values that could not be read before, e.g. because the memory was not accessible, are read again.
+        if matches!(variable.memory_location, VariableLocation::Address(_)) {
+            let previous_value = std::mem::take(&mut variable.value);
+            variable.extract_value(memory, self);
+            if variable.value != previous_value
+                && matches!(variable.type_name, VariableType::Pointer(_))
+            {
+                // The referenced variable is resolved again when it is requested.
+                self.remove_cache_entry_children(variable_key)?;
+            }
+            self.update_variable(&variable)?;
+        }
+
+        for child in self.get_children(variable_key)? {
+            self.read_values_again(child.variable_key, memory)?;
+        }
+        Ok(())
+    }
+
     /// Traverse the `VariableCache` and return a Vec of all the memory ranges that are referenced by the variables.
     /// This is used to determine which memory ranges to read from the target when creating a 'default' [`crate::CoreDump`].
     pub fn get_discrete_memory_ranges(&self) -> Vec<Range<u64>> {
@@ -563,12 +857,20 @@ impl VariableCache {
 
 #[cfg(test)]
 mod test {
+
     use gimli::UnitOffset;
     use termtree::Tree;
 
-    use crate::debug::{
-        Variable, VariableCache, VariableLocation, VariableName, VariableNodeType, VariableType,
-        VariantRole,
+    use crate::{
+        debug::{
+            stack_frame::StackFrameInfo, DebugInfo, SectionMemory, UnavailableReason, Variable,
+            VariableCache, VariableLocation, VariableName, VariableNodeType, VariableType,
+            VariantRole,
+        },
+        test::{
+            get_path_for_test_files, load_test_elf_as_debug_info, x86_64::RIP, x86_64_registers,
+            MockMemory,
+        },
     };
 
     fn show_tree(cache: &VariableCache) {
@@ -783,4 +1085,284 @@ mod test {
 
         assert_eq!(new_children, vec![vars[4].clone(), vars[5].clone()]);
     }
+
+    #[test]
+    fn refresh_single_variable_across_halts() {
+        let debug_info = load_test_elf_as_debug_info("c-types/c-types-dwarf5.elf");
+        let unit_info = &debug_info.unit_infos[0];
+
+        // The frame of `sum_values`, with `count` at `DW_OP_fbreg: -44`, `values` at `DW_OP_fbreg: -40`
+        // (pointing to 0x2000_0000), `index` at `DW_OP_fbreg: -24`, and `sum` at `DW_OP_fbreg: -20`.
+        let frame_base = 0x2000_1000;
+        let frame_memory = |sum: u32, index: u32| {
+            let mut memory = MockMemory::new();
+            memory.add_word_range(frame_base - 44, &[256, 0x2000_0000, 0, 0, 0, index, sum]);
+            // `values` is shown as a string, which is read in chunks of 32 bytes.
+            memory.add_word_range(0x2000_0000, &[0; 8]);
+            memory
+        };
+        let registers = |program_counter: u64| {
+            x86_64_registers(|id| if id == RIP { program_counter } else { 0 })
+        };
+        let frame_info = |registers| StackFrameInfo {
+            registers,
+            frame_base: Some(frame_base),
+            canonical_frame_address: None,
+            thread_local_storage_base: None,
+            registers_only: false,
+            frame_torn_down: false,
+        };
+
+        // The first halt is inside the loop.
+        let first_halt = registers(0x40_1040);
+        let function_die = unit_info
+            .get_function_dies(&debug_info, 0x40_1040, false)
+            .unwrap()
+            .pop()
+            .unwrap();
+        let mut local_variables = debug_info
+            .create_function_scope_cache(&function_die, unit_info)
+            .unwrap();
+        local_variables.recurse_deferred_variables(
+            &debug_info,
+            &mut frame_memory(1, 0),
+            None,
+            1,
+            0,
+            frame_info(&first_halt),
+        );
+        let variable = |local_variables: &VariableCache, name: &str| {
+            local_variables
+                .get_variable_by_name(&VariableName::Named(name.to_string()))
+                .unwrap()
+        };
+        let sum = variable(&local_variables, "sum");
+        let index = variable(&local_variables, "index");
+        assert_eq!(sum.get_value(&local_variables), "1");
+        assert_eq!(index.get_value(&local_variables), "0");
+
+        // The second halt is still inside the loop, and only `sum` is refreshed.
+        let second_halt = registers(0x40_1040);
+        local_variables
+            .refresh(
+                sum.variable_key(),
+                &debug_info,
+                &mut frame_memory(6, 2),
+                frame_info(&second_halt),
+            )
+            .unwrap();
+        let refreshed_sum = variable(&local_variables, "sum");
+        assert_eq!(refreshed_sum.variable_key(), sum.variable_key());
+        assert_eq!(refreshed_sum.get_value(&local_variables), "6");
+        assert_eq!(
+            variable(&local_variables, "index").get_value(&local_variables),
+            "0"
+        );
+
+        // After the loop, `index` is out of scope.
+        let third_halt = registers(0x40_1055);
+        local_variables
+            .refresh(
+                index.variable_key(),
+                &debug_info,
+                &mut frame_memory(6, 3),
+                frame_info(&third_halt),
+            )
+            .unwrap();
+        let refreshed_index = variable(&local_variables, "index");
+        assert_eq!(
+            refreshed_index.memory_location,
+            VariableLocation::Unavailable(UnavailableReason::NotLiveHere)
+        );
+        assert_eq!(
+            refreshed_index.get_value(&local_variables),
+            "< <value not live at this location> >"
+        );
+    }
+
+    #[test]
+    fn refresh_keeps_expanded_children() {
+        let debug_info = load_test_elf_as_debug_info("c-types/c-types-dwarf5.elf");
+        let unit_info = &debug_info.unit_infos[0];
+
+        // The frame of `sum_values`, with `count` at `DW_OP_fbreg: -44` and `values` at `DW_OP_fbreg: -40`.
+        let frame_memory = |frame_base: u64, values: u32, first_value: u32| {
+            let mut memory = MockMemory::new();
+            memory.add_word_range(frame_base - 44, &[256, values, 0, 0, 0, 0, 0]);
+            memory.add_word_range(values as u64, &[first_value, 0, 0, 0, 0, 0, 0, 0]);
+            memory
+        };
+        let registers = x86_64_registers(|id| if id == RIP { 0x40_1040 } else { 0 });
+        let frame_info = |frame_base| StackFrameInfo {
+            registers: &registers,
+            frame_base: Some(frame_base),
+            canonical_frame_address: None,
+            thread_local_storage_base: None,
+            registers_only: false,
+            frame_torn_down: false,
+        };
+
+        let function_die = unit_info
+            .get_function_dies(&debug_info, 0x40_1040, false)
+            .unwrap()
+            .pop()
+            .unwrap();
+        let mut local_variables = debug_info
+            .create_function_scope_cache(&function_die, unit_info)
+            .unwrap();
+        local_variables.recurse_deferred_variables(
+            &debug_info,
+            &mut frame_memory(0x2000_1000, 0x2000_0000, 0x41),
+            None,
+            2,
+            0,
+            frame_info(0x2000_1000),
+        );
+        let variable = |local_variables: &VariableCache, name: &str| {
+            local_variables
+                .get_variable_by_name(&VariableName::Named(name.to_string()))
+                .unwrap()
+        };
+        let values = variable(&local_variables, "values");
+        let referenced_value = local_variables
+            .get_children(values.variable_key())
+            .unwrap()
+            .pop()
+            .expect("`values` is expanded");
+        assert_eq!(referenced_value.get_value(&local_variables), "65");
+
+        // The frame moved, and `values` points to another buffer.
+        local_variables
+            .refresh(
+                values.variable_key(),
+                &debug_info,
+                &mut frame_memory(0x2000_2000, 0x2000_0100, 0x42),
+                frame_info(0x2000_2000),
+            )
+            .unwrap();
+        let refreshed_values = variable(&local_variables, "values");
+        assert_eq!(refreshed_values.variable_key(), values.variable_key());
+        assert_eq!(
+            refreshed_values.memory_location,
+            VariableLocation::Address(0x2000_2000 - 40)
+        );
+        let refreshed_children = local_variables.get_children(values.variable_key()).unwrap();
+        assert_eq!(refreshed_children.len(), 1);
+        assert_eq!(
+            refreshed_children[0].variable_key(),
+            referenced_value.variable_key()
+        );
+        assert_eq!(refreshed_children[0].get_value(&local_variables), "66");
+    }
+
+    #[test]
+    fn refresh_reads_values_that_failed_before() {
+        let elf_path = get_path_for_test_files("c-types/c-types-dwarf5.elf");
+        let debug_info = DebugInfo::from_file(&elf_path).unwrap();
+        let unit_info = &debug_info.unit_infos[0];
+
+        let frame_base = 0x2000_1000;
+        let registers = x86_64_registers(|id| if id == RIP { 0x40_1040 } else { 0 });
+        let frame_info = StackFrameInfo {
+            registers: &registers,
+            frame_base: Some(frame_base),
+            canonical_frame_address: None,
+            thread_local_storage_base: None,
+            registers_only: false,
+            frame_torn_down: false,
+        };
+
+        let function_die = unit_info
+            .get_function_dies(&debug_info, 0x40_1040, false)
+            .unwrap()
+            .pop()
+            .unwrap();
+        let mut local_variables = debug_info
+            .create_function_scope_cache(&function_die, unit_info)
+            .unwrap();
+        // The frame is not in the memory of the ELF file, so it can not be read at the first halt.
+        local_variables.recurse_deferred_variables(
+            &debug_info,
+            &mut SectionMemory::from_file(&elf_path).unwrap(),
+            None,
+            1,
+            0,
+            frame_info,
+        );
+        let count = local_variables
+            .get_variable_by_name(&VariableName::Named("count".to_string()))
+            .unwrap();
+        assert!(matches!(count.value, crate::debug::VariableValue::Error(_)));
+
+        // `count` is at `DW_OP_fbreg: -44`.
+        let mut memory = MockMemory::new();
+        memory.add_word_range(frame_base - 44, &[256]);
+        local_variables
+            .refresh(count.variable_key(), &debug_info, &mut memory, frame_info)
+            .unwrap();
+        let refreshed_count = local_variables
+            .get_variable_by_key(count.variable_key())
+            .unwrap();
+        assert_eq!(refreshed_count.get_value(&local_variables), "256");
+    }
+
+    #[test]
+    fn volatile_variable_is_read_again() {
+        let elf_path = get_path_for_test_files("c-types/c-types-dwarf5.elf");
+        let debug_info = DebugInfo::from_file(&elf_path).unwrap();
+
+        let registers = x86_64_registers(|id| if id == RIP { 0x40_1000 } else { 0 });
+        let unit_info = &debug_info.unit_infos[0];
+        let mut static_variables = debug_info.create_static_scope_cache(unit_info).unwrap();
+        static_variables.recurse_deferred_variables(
+            &debug_info,
+            &mut SectionMemory::from_file(&elf_path).unwrap(),
+            None,
+            10,
+            0,
+            StackFrameInfo {
+                registers: &registers,
+                frame_base: None,
+                canonical_frame_address: None,
+                thread_local_storage_base: None,
+                registers_only: false,
+                frame_torn_down: false,
+            },
+        );
+
+        // `volatile struct Nested nested`, with its members.
+        let nested = static_variables
+            .get_variable_by_name(&VariableName::Named("nested".to_string()))
+            .unwrap();
+        assert!(nested.is_volatile);
+        let id_value = |static_variables: &VariableCache| {
+            let id = static_variables
+                .get_children(nested.variable_key())
+                .unwrap()
+                .into_iter()
+                .find(|member| member.name.to_string() == "id")
+                .unwrap();
+            assert!(id.is_volatile);
+            id.get_value(static_variables)
+        };
+        assert_eq!(id_value(&static_variables), "1");
+
+        // Without the core running, the hardware changes the value twice.
+        let nested_address = nested.memory_location.memory_address().unwrap();
+        let nested_size = nested.byte_size.unwrap() as usize;
+        for id in [7, 8] {
+            let mut memory = MockMemory::new();
+            let mut bytes = vec![0; nested_size];
+            bytes[0] = id;
+            memory.add_range(nested_address, bytes);
+            // `next` is a null pointer, and the `Point` it refers to could not be read from the ELF file,
+            // so it is read again as well.
+            memory.add_range(0, vec![0; 4]);
+
+            static_variables
+                .read_volatile_value(nested.variable_key(), &mut memory)
+                .unwrap();
+            assert_eq!(id_value(&static_variables), id.to_string());
+        }
+    }
 }
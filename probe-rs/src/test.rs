@@ -1,6 +1,17 @@
 //! Helpers for testing the crate
 
-use crate::MemoryInterface;
+use std::{collections::HashMap, path::PathBuf, time::Duration};
+
+use once_cell::sync::Lazy;
+use probe_rs_target::InstructionSet;
+
+use crate::{
+    architecture::arm::core::registers::cortex_m::CORTEX_M_CORE_REGISTERS,
+    core::{Architecture, CoreInformation, RegisterDataType, RegisterRole, UnwindRule},
+    debug::{DebugInfo, DebugRegister, DebugRegisters},
+    CoreInterface, CoreRegister, CoreRegisters, CoreStatus, CoreType, HaltReason, MemoryInterface,
+    RegisterId, RegisterValue,
+};
 
 #[derive(Debug)]
 pub(crate) struct MockMemory {
@@ -89,12 +100,18 @@ impl MemoryInterface for MockMemory {
         Ok(u32::from_le_bytes(bytes))
     }
 
-    fn read_word_8(&mut self, _address: u64) -> anyhow::Result<u8, crate::Error> {
-        todo!()
+    fn read_word_8(&mut self, address: u64) -> anyhow::Result<u8, crate::Error> {
+        let mut bytes = [0u8; 1];
+        self.read_8(address, &mut bytes)?;
+
+        Ok(bytes[0])
     }
 
-    fn read_word_16(&mut self, _address: u64) -> anyhow::Result<u16, crate::Error> {
-        todo!()
+    fn read_word_16(&mut self, address: u64) -> anyhow::Result<u16, crate::Error> {
+        let mut bytes = [0u8; 2];
+        self.read_8(address, &mut bytes)?;
+
+        Ok(u16::from_le_bytes(bytes))
     }
 
     fn read_64(&mut self, _address: u64, _data: &mut [u64]) -> anyhow::Result<(), crate::Error> {
@@ -201,6 +218,373 @@ impl MemoryInterface for MockMemory {
     }
 }
 
+/// Get the full path to a file in the `tests` directory.
+pub(crate) fn get_path_for_test_files(relative_file: &str) -> PathBuf {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("tests");
+    path.push(relative_file);
+    path
+}
+
+/// Load the DebugInfo from the `elf_file` for the test.
+/// `elf_file` should be the name of a file(or relative path) in the `tests` directory.
+pub(crate) fn load_test_elf_as_debug_info(elf_file: &str) -> DebugInfo {
+    DebugInfo::from_file(get_path_for_test_files(elf_file)).unwrap()
+}
+
+/// The registers R0 to R15 of a Cortex-M core, with the value of each register
+/// given by its DWARF register number.
+pub(crate) fn cortex_m_registers(value: impl Fn(u16) -> u32) -> DebugRegisters {
+    DebugRegisters(
+        (0..16)
+            .map(|id| DebugRegister {
+                dwarf_id: Some(id as u16),
+                core_register: CORTEX_M_CORE_REGISTERS.core_register(id),
+                value: Some(RegisterValue::U32(value(id as u16))),
+                origin: None,
+            })
+            .collect(),
+    )
+}
+
+/// DWARF register numbers of the x86-64 registers used by the test fixtures,
+/// as defined in the System V AMD64 psABI.
+pub(crate) mod x86_64 {
+    pub(crate) const RAX: u16 = 0;
+    pub(crate) const RDI: u16 = 5;
+    pub(crate) const RBP: u16 = 6;
+    pub(crate) const RSP: u16 = 7;
+    pub(crate) const RIP: u16 = 16;
+    pub(crate) const XMM0: u16 = 17;
+}
+
+const fn x86_64_register(id: u16, roles: &'static [RegisterRole]) -> CoreRegister {
+    CoreRegister {
+        id: RegisterId(id),
+        roles,
+        data_type: RegisterDataType::UnsignedInteger(64),
+        unwind_rule: UnwindRule::Preserve,
+    }
+}
+
+/// The x86-64 general purpose registers, the instruction pointer and the low 64 bits of `xmm0`,
+/// with the register ids matching their DWARF register numbers.
+///
+/// Only `rdi` has the argument role, because the argument registers are found in the order of the
+/// core registers, while the first argument is passed in `rdi`.
+///
+/// The host compiled test fixtures are x86-64 binaries, for which there are no core
+/// registers in the crate.
+static X86_64_CORE_REGISTERS: [CoreRegister; 18] = [
+    x86_64_register(0, &[RegisterRole::Core("rax"), RegisterRole::Return("rax")]),
+    x86_64_register(1, &[RegisterRole::Core("rdx"), RegisterRole::Return("rdx")]),
+    x86_64_register(2, &[RegisterRole::Core("rcx")]),
+    x86_64_register(3, &[RegisterRole::Core("rbx")]),
+    x86_64_register(4, &[RegisterRole::Core("rsi")]),
+    x86_64_register(
+        5,
+        &[RegisterRole::Core("rdi"), RegisterRole::Argument("rdi")],
+    ),
+    x86_64_register(6, &[RegisterRole::Core("rbp"), RegisterRole::FramePointer]),
+    x86_64_register(7, &[RegisterRole::Core("rsp"), RegisterRole::StackPointer]),
+    x86_64_register(8, &[RegisterRole::Core("r8")]),
+    x86_64_register(9, &[RegisterRole::Core("r9")]),
+    x86_64_register(10, &[RegisterRole::Core("r10")]),
+    x86_64_register(11, &[RegisterRole::Core("r11")]),
+    x86_64_register(12, &[RegisterRole::Core("r12")]),
+    x86_64_register(13, &[RegisterRole::Core("r13")]),
+    x86_64_register(14, &[RegisterRole::Core("r14")]),
+    x86_64_register(15, &[RegisterRole::Core("r15")]),
+    x86_64_register(
+        16,
+        &[RegisterRole::Core("rip"), RegisterRole::ProgramCounter],
+    ),
+    x86_64_register(17, &[RegisterRole::Core("xmm0")]),
+];
+
+/// The x86-64 registers of the host compiled test fixtures, with the value of each register
+/// given by its DWARF register number.
+pub(crate) fn x86_64_registers(value: impl Fn(u16) -> u64) -> DebugRegisters {
+    DebugRegisters(
+        X86_64_CORE_REGISTERS
+            .iter()
+            .map(|core_register| DebugRegister {
+                dwarf_id: Some(core_register.id.0),
+                core_register,
+                value: Some(RegisterValue::U64(value(core_register.id.0))),
+                origin: None,
+            })
+            .collect(),
+    )
+}
+
+/// The return address register of [`MockCore`], which x86-64 keeps on the stack instead.
+static MOCK_RETURN_ADDRESS: CoreRegister =
+    x86_64_register(MockCore::RETURN_ADDRESS, &[RegisterRole::ReturnAddress]);
+
+/// The registers of [`MockCore`], in the order of their DWARF register numbers.
+static MOCK_CORE_REGISTERS: Lazy<CoreRegisters> = Lazy::new(|| {
+    CoreRegisters::new(
+        X86_64_CORE_REGISTERS
+            .iter()
+            .chain(std::iter::once(&MOCK_RETURN_ADDRESS))
+            .collect(),
+    )
+});
+
+/// A halted core with the x86-64 registers of the host compiled test fixtures, and a return address register.
+///
+/// Running the core stops at the hardware breakpoint, with the registers in `registers_at_breakpoint`,
+/// e.g. to step out of a function that returns a value.
+pub(crate) struct MockCore {
+    /// The register values by DWARF register number, and the return address.
+    pub(crate) registers: HashMap<u16, u64>,
+    /// The registers that change when the core runs to the breakpoint.
+    pub(crate) registers_at_breakpoint: HashMap<u16, u64>,
+    pub(crate) memory: MockMemory,
+    /// The registers of the core, which are the x86-64 registers and the return address by default.
+    pub(crate) core_registers: &'static CoreRegisters,
+    breakpoint: Option<u64>,
+}
+
+impl MockCore {
+    /// The register number of the return address.
+    pub(crate) const RETURN_ADDRESS: u16 = 18;
+
+    pub(crate) fn new(registers: HashMap<u16, u64>, memory: MockMemory) -> Self {
+        Self {
+            registers,
+            registers_at_breakpoint: HashMap::new(),
+            memory,
+            core_registers: &MOCK_CORE_REGISTERS,
+            breakpoint: None,
+        }
+    }
+
+    fn program_counter_value(&self) -> u64 {
+        self.registers
+            .get(&x86_64::RIP)
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+impl MemoryInterface for MockCore {
+    fn supports_native_64bit_access(&mut self) -> bool {
+        self.memory.supports_native_64bit_access()
+    }
+
+    fn read_word_64(&mut self, address: u64) -> Result<u64, crate::Error> {
+        self.memory.read_word_64(address)
+    }
+
+    fn read_word_32(&mut self, address: u64) -> Result<u32, crate::Error> {
+        self.memory.read_word_32(address)
+    }
+
+    fn read_word_16(&mut self, address: u64) -> Result<u16, crate::Error> {
+        self.memory.read_word_16(address)
+    }
+
+    fn read_word_8(&mut self, address: u64) -> Result<u8, crate::Error> {
+        self.memory.read_word_8(address)
+    }
+
+    fn read_64(&mut self, address: u64, data: &mut [u64]) -> Result<(), crate::Error> {
+        self.memory.read_64(address, data)
+    }
+
+    fn read_32(&mut self, address: u64, data: &mut [u32]) -> Result<(), crate::Error> {
+        self.memory.read_32(address, data)
+    }
+
+    fn read_16(&mut self, address: u64, data: &mut [u16]) -> Result<(), crate::Error> {
+        self.memory.read_16(address, data)
+    }
+
+    fn read_8(&mut self, address: u64, data: &mut [u8]) -> Result<(), crate::Error> {
+        self.memory.read_8(address, data)
+    }
+
+    fn write_word_64(&mut self, address: u64, data: u64) -> Result<(), crate::Error> {
+        self.memory.write_word_64(address, data)
+    }
+
+    fn write_word_32(&mut self, address: u64, data: u32) -> Result<(), crate::Error> {
+        self.memory.write_word_32(address, data)
+    }
+
+    fn write_word_16(&mut self, address: u64, data: u16) -> Result<(), crate::Error> {
+        self.memory.write_word_16(address, data)
+    }
+
+    fn write_word_8(&mut self, address: u64, data: u8) -> Result<(), crate::Error> {
+        self.memory.write_word_8(address, data)
+    }
+
+    fn write_64(&mut self, address: u64, data: &[u64]) -> Result<(), crate::Error> {
+        self.memory.write_64(address, data)
+    }
+
+    fn write_32(&mut self, address: u64, data: &[u32]) -> Result<(), crate::Error> {
+        self.memory.write_32(address, data)
+    }
+
+    fn write_16(&mut self, address: u64, data: &[u16]) -> Result<(), crate::Error> {
+        self.memory.write_16(address, data)
+    }
+
+    fn write_8(&mut self, address: u64, data: &[u8]) -> Result<(), crate::Error> {
+        self.memory.write_8(address, data)
+    }
+
+    fn supports_8bit_transfers(&self) -> Result<bool, crate::Error> {
+        self.memory.supports_8bit_transfers()
+    }
+
+    fn flush(&mut self) -> Result<(), crate::Error> {
+        Ok(())
+    }
+}
+
+impl CoreInterface for MockCore {
+    fn wait_for_core_halted(&mut self, _timeout: Duration) -> Result<(), crate::Error> {
+        Ok(())
+    }
+
+    fn core_halted(&mut self) -> Result<bool, crate::Error> {
+        Ok(true)
+    }
+
+    fn status(&mut self) -> Result<CoreStatus, crate::Error> {
+        Ok(CoreStatus::Halted(HaltReason::Request))
+    }
+
+    fn halt(&mut self, _timeout: Duration) -> Result<CoreInformation, crate::Error> {
+        Ok(CoreInformation {
+            pc: self.program_counter_value(),
+        })
+    }
+
+    fn run(&mut self) -> Result<(), crate::Error> {
+        let Some(breakpoint) = self.breakpoint else {
+            todo!("Running without a breakpoint")
+        };
+        self.registers.extend(
+            self.registers_at_breakpoint
+                .iter()
+                .map(|(&id, &value)| (id, value)),
+        );
+        self.registers.insert(x86_64::RIP, breakpoint);
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<(), crate::Error> {
+        todo!()
+    }
+
+    fn reset_and_halt(&mut self, _timeout: Duration) -> Result<CoreInformation, crate::Error> {
+        todo!()
+    }
+
+    fn step(&mut self) -> Result<CoreInformation, crate::Error> {
+        todo!()
+    }
+
+    fn read_core_reg(&mut self, address: RegisterId) -> Result<RegisterValue, crate::Error> {
+        Ok(RegisterValue::U64(
+            self.registers.get(&address.0).copied().unwrap_or_default(),
+        ))
+    }
+
+    fn write_core_reg(
+        &mut self,
+        address: RegisterId,
+        value: RegisterValue,
+    ) -> Result<(), crate::Error> {
+        self.registers.insert(address.0, value.try_into()?);
+        Ok(())
+    }
+
+    fn available_breakpoint_units(&mut self) -> Result<u32, crate::Error> {
+        Ok(1)
+    }
+
+    fn hw_breakpoints(&mut self) -> Result<Vec<Option<u64>>, crate::Error> {
+        Ok(vec![self.breakpoint])
+    }
+
+    fn enable_breakpoints(&mut self, _state: bool) -> Result<(), crate::Error> {
+        Ok(())
+    }
+
+    fn set_hw_breakpoint(&mut self, _unit_index: usize, addr: u64) -> Result<(), crate::Error> {
+        self.breakpoint = Some(addr);
+        Ok(())
+    }
+
+    fn clear_hw_breakpoint(&mut self, _unit_index: usize) -> Result<(), crate::Error> {
+        self.breakpoint = None;
+        Ok(())
+    }
+
+    fn registers(&self) -> &'static CoreRegisters {
+        self.core_registers
+    }
+
+    fn program_counter(&self) -> &'static CoreRegister {
+        &X86_64_CORE_REGISTERS[x86_64::RIP as usize]
+    }
+
+    fn frame_pointer(&self) -> &'static CoreRegister {
+        &X86_64_CORE_REGISTERS[x86_64::RBP as usize]
+    }
+
+    fn stack_pointer(&self) -> &'static CoreRegister {
+        &X86_64_CORE_REGISTERS[x86_64::RSP as usize]
+    }
+
+    fn return_address(&self) -> &'static CoreRegister {
+        &MOCK_RETURN_ADDRESS
+    }
+
+    fn hw_breakpoints_enabled(&self) -> bool {
+        true
+    }
+
+    fn architecture(&self) -> Architecture {
+        todo!()
+    }
+
+    fn core_type(&self) -> CoreType {
+        todo!()
+    }
+
+    fn instruction_set(&mut self) -> Result<InstructionSet, crate::Error> {
+        todo!()
+    }
+
+    fn fpu_support(&mut self) -> Result<bool, crate::Error> {
+        Ok(false)
+    }
+
+    fn floating_point_register_count(&mut self) -> Result<usize, crate::Error> {
+        Ok(0)
+    }
+
+    fn reset_catch_set(&mut self) -> Result<(), crate::Error> {
+        todo!()
+    }
+
+    fn reset_catch_clear(&mut self) -> Result<(), crate::Error> {
+        todo!()
+    }
+
+    fn debug_core_stop(&mut self) -> Result<(), crate::Error> {
+        todo!()
+    }
+}
+
 #[test]
 fn mock_memory_read() {
     let mut mock_memory = MockMemory::new();
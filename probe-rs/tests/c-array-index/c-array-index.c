@@ -0,0 +1,22 @@
+// Source for the `c-array-index` test binary. See `../README.md` for build instructions.
+#include <stdint.h>
+
+struct Point {
+    int32_t x;
+    int32_t y;
+};
+
+volatile uint32_t selected = 1;
+
+int32_t select_values(void) {
+    int32_t values[4] = {10, 20, 30, 40};
+    struct Point points[2] = {{1, 2}, {3, 4}};
+    uint32_t i = selected;
+    return values[i] + points[i].y;
+}
+
+void _start(void) {
+    for (;;) {
+        select_values();
+    }
+}
@@ -0,0 +1,25 @@
+// Source for the `c-bitfields` test binaries. See `../README.md` for build instructions.
+#include <stdint.h>
+
+// Bitfields that are wider than 32 bits, or span more than one byte or word of their storage unit.
+struct Wide {
+    uint64_t low : 20;
+    uint64_t timestamp : 40;
+    uint64_t flags : 4;
+    int64_t delta : 40;
+    uint64_t top : 24;
+};
+
+volatile struct Wide wide = {
+    .low = 0xABCDE,
+    .timestamp = 0x123456789A,
+    .flags = 0x9,
+    .delta = -5,
+    .top = 0x123456,
+};
+
+void _start(void) {
+    for (;;) {
+        wide.timestamp++;
+    }
+}
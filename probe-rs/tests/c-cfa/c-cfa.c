@@ -0,0 +1,11 @@
+// Source for the `c-cfa` test binary. See `../README.md` for build instructions.
+int scale(int value, int factor) {
+    int product = value * factor;
+    return product;
+}
+
+void _start(void) {
+    for (;;) {
+        scale(6, 7);
+    }
+}
@@ -0,0 +1,276 @@
+	.file	"c-cfa.c"
+	.text
+.Ltext0:
+	.cfi_sections	.debug_frame
+	.file 0 "/tmp/ccfa" "c-cfa.c"
+	.globl	scale
+	.type	scale, @function
+scale:
+.LFB0:
+	.file 1 "c-cfa.c"
+	.loc 1 2 34
+	.cfi_startproc
+	pushq	%rbp
+	.cfi_def_cfa_offset 16
+	.cfi_offset 6, -16
+	movq	%rsp, %rbp
+	.cfi_def_cfa_register 6
+	movl	%edi, -20(%rbp)
+	movl	%esi, -24(%rbp)
+	.loc 1 3 9
+	movl	-20(%rbp), %eax
+	imull	-24(%rbp), %eax
+	movl	%eax, -4(%rbp)
+	.loc 1 4 12
+	movl	-4(%rbp), %eax
+	.loc 1 5 1
+	popq	%rbp
+	.cfi_def_cfa 7, 8
+	ret
+	.cfi_endproc
+.LFE0:
+	.size	scale, .-scale
+	.globl	_start
+	.type	_start, @function
+_start:
+.LFB1:
+	.loc 1 7 19
+	.cfi_startproc
+	pushq	%rbp
+	.cfi_def_cfa_offset 16
+	.cfi_offset 6, -16
+	movq	%rsp, %rbp
+	.cfi_def_cfa_register 6
+.L4:
+	.loc 1 9 9 discriminator 1
+	movl	$7, %esi
+	movl	$6, %edi
+	call	scale
+	jmp	.L4
+	.cfi_endproc
+.LFE1:
+	.size	_start, .-_start
+.Letext0:
+	.section	.debug_info,"",@progbits
+.Ldebug_info0:
+	.long	0x9c
+	.value	0x5
+	.byte	0x1
+	.byte	0x8
+	.long	.Ldebug_abbrev0
+	.uleb128 0x2
+	.long	.LASF4
+	.byte	0x1d
+	.long	.LASF0
+	.long	.LASF1
+	.quad	.Ltext0
+	.quad	.Letext0-.Ltext0
+	.long	.Ldebug_line0
+	.uleb128 0x3
+	.long	.LASF5
+	.byte	0x1
+	.byte	0x7
+	.byte	0x6
+	.quad	.LFB1
+	.quad	.LFE1-.LFB1
+	.uleb128 0x1
+	.byte	0x9c
+	.uleb128 0x4
+	.long	.LASF6
+	.byte	0x1
+	.byte	0x2
+	.byte	0x5
+	.long	0x98
+	.quad	.LFB0
+	.quad	.LFE0-.LFB0
+	.long	0x98
+	.uleb128 0x1
+	.long	.LASF2
+	.byte	0xf
+	.long	0x98
+	.uleb128 0x4
+	.byte	0x9c
+	.byte	0x11
+	.sleb128 -36
+	.byte	0x22
+	.uleb128 0x1
+	.long	.LASF3
+	.byte	0x1a
+	.long	0x98
+	.uleb128 0x4
+	.byte	0x9c
+	.byte	0x11
+	.sleb128 -40
+	.byte	0x22
+	.uleb128 0x5
+	.long	.LASF7
+	.byte	0x1
+	.byte	0x3
+	.byte	0x9
+	.long	0x98
+	.uleb128 0x4
+	.byte	0x9c
+	.byte	0x11
+	.sleb128 -20
+	.byte	0x22
+	.byte	0
+	.uleb128 0x6
+	.byte	0x4
+	.byte	0x5
+	.string	"int"
+	.byte	0
+	.section	.debug_abbrev,"",@progbits
+.Ldebug_abbrev0:
+	.uleb128 0x1
+	.uleb128 0x5
+	.byte	0
+	.uleb128 0x3
+	.uleb128 0xe
+	.uleb128 0x3a
+	.uleb128 0x21
+	.sleb128 1
+	.uleb128 0x3b
+	.uleb128 0x21
+	.sleb128 2
+	.uleb128 0x39
+	.uleb128 0xb
+	.uleb128 0x49
+	.uleb128 0x13
+	.uleb128 0x2
+	.uleb128 0x18
+	.byte	0
+	.byte	0
+	.uleb128 0x2
+	.uleb128 0x11
+	.byte	0x1
+	.uleb128 0x25
+	.uleb128 0xe
+	.uleb128 0x13
+	.uleb128 0xb
+	.uleb128 0x3
+	.uleb128 0x1f
+	.uleb128 0x1b
+	.uleb128 0x1f
+	.uleb128 0x11
+	.uleb128 0x1
+	.uleb128 0x12
+	.uleb128 0x7
+	.uleb128 0x10
+	.uleb128 0x17
+	.byte	0
+	.byte	0
+	.uleb128 0x3
+	.uleb128 0x2e
+	.byte	0
+	.uleb128 0x3f
+	.uleb128 0x19
+	.uleb128 0x3
+	.uleb128 0xe
+	.uleb128 0x3a
+	.uleb128 0xb
+	.uleb128 0x3b
+	.uleb128 0xb
+	.uleb128 0x39
+	.uleb128 0xb
+	.uleb128 0x27
+	.uleb128 0x19
+	.uleb128 0x11
+	.uleb128 0x1
+	.uleb128 0x12
+	.uleb128 0x7
+	.uleb128 0x40
+	.uleb128 0x18
+	.uleb128 0x7c
+	.uleb128 0x19
+	.byte	0
+	.byte	0
+	.uleb128 0x4
+	.uleb128 0x2e
+	.byte	0x1
+	.uleb128 0x3f
+	.uleb128 0x19
+	.uleb128 0x3
+	.uleb128 0xe
+	.uleb128 0x3a
+	.uleb128 0xb
+	.uleb128 0x3b
+	.uleb128 0xb
+	.uleb128 0x39
+	.uleb128 0xb
+	.uleb128 0x27
+	.uleb128 0x19
+	.uleb128 0x49
+	.uleb128 0x13
+	.uleb128 0x11
+	.uleb128 0x1
+	.uleb128 0x12
+	.uleb128 0x7
+	.uleb128 0x7a
+	.uleb128 0x19
+	.uleb128 0x1
+	.uleb128 0x13
+	.byte	0
+	.byte	0
+	.uleb128 0x5
+	.uleb128 0x34
+	.byte	0
+	.uleb128 0x3
+	.uleb128 0xe
+	.uleb128 0x3a
+	.uleb128 0xb
+	.uleb128 0x3b
+	.uleb128 0xb
+	.uleb128 0x39
+	.uleb128 0xb
+	.uleb128 0x49
+	.uleb128 0x13
+	.uleb128 0x2
+	.uleb128 0x18
+	.byte	0
+	.byte	0
+	.uleb128 0x6
+	.uleb128 0x24
+	.byte	0
+	.uleb128 0xb
+	.uleb128 0xb
+	.uleb128 0x3e
+	.uleb128 0xb
+	.uleb128 0x3
+	.uleb128 0x8
+	.byte	0
+	.byte	0
+	.byte	0
+	.section	.debug_aranges,"",@progbits
+	.long	0x2c
+	.value	0x2
+	.long	.Ldebug_info0
+	.byte	0x8
+	.byte	0
+	.value	0
+	.value	0
+	.quad	.Ltext0
+	.quad	.Letext0-.Ltext0
+	.quad	0
+	.quad	0
+	.section	.debug_line,"",@progbits
+.Ldebug_line0:
+	.section	.debug_str,"MS",@progbits,1
+.LASF3:
+	.string	"factor"
+.LASF6:
+	.string	"scale"
+.LASF4:
+	.string	"GNU C17 12.2.0 -mtune=generic -march=x86-64 -g -gdwarf-5 -O0 -fno-asynchronous-unwind-tables"
+.LASF2:
+	.string	"value"
+.LASF5:
+	.string	"_start"
+.LASF7:
+	.string	"product"
+	.section	.debug_line_str,"MS",@progbits,1
+.LASF1:
+	.string	"/tmp/ccfa"
+.LASF0:
+	.string	"c-cfa.c"
+	.ident	"GCC: (Debian 12.2.0-14+deb12u1) 12.2.0"
+	.section	.note.GNU-stack,"",@progbits
@@ -0,0 +1,23 @@
+// Source for the `c-cold-split` test binary. See `../README.md` for build instructions.
+#include <stdint.h>
+
+volatile uint32_t errors;
+
+__attribute__((cold, noinline)) void report_error(uint32_t value) {
+    errors += value;
+}
+
+__attribute__((noinline)) uint32_t checked_double(uint32_t value) {
+    if (__builtin_expect(value > 1000, 0)) {
+        report_error(value);
+        errors++;
+        return 0;
+    }
+    return value * 2;
+}
+
+void _start(void) {
+    for (uint32_t value = 0;; value++) {
+        errors += checked_double(value);
+    }
+}
@@ -0,0 +1,4 @@
+// Part of the `c-dwarf5-directories` test binary. See `../../README.md` for build instructions.
+static inline int scale(int value) {
+    return value * 3;
+}
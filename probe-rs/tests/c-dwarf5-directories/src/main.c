@@ -0,0 +1,10 @@
+// Source for the `c-dwarf5-directories` test binary. See `../../README.md` for build instructions.
+#include "scale.h"
+
+volatile int result;
+
+void _start(void) {
+    for (;;) {
+        result = scale(result);
+    }
+}
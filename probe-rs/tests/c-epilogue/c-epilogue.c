@@ -0,0 +1,13 @@
+// Test program for the variables of a function while it returns.
+// GCC does not mark the start of epilogues in the line program, so `epilogue_begin` is added to the assembly.
+
+int checksum(int a, int b) {
+    int sum = a ^ b;
+    return sum;
+}
+
+void _start(void) {
+    for (;;) {
+        checksum(0x12, 0x34);
+    }
+}
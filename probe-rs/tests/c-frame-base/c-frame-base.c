@@ -0,0 +1,14 @@
+// Test program for a frame base that is a location list.
+// Strict DWARF 2 does not have `DW_OP_call_frame_cfa`, so gcc describes the frame base of each function
+// with a location list, which changes from the stack pointer to the frame pointer during the prologue.
+
+int add(int a, int b) {
+    int sum = a + b;
+    return sum;
+}
+
+void _start(void) {
+    for (;;) {
+        add(1, 2);
+    }
+}
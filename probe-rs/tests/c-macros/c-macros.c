@@ -0,0 +1,21 @@
+#include "registers.h"
+
+/*
+ * The code of the register access macros is attributed to `registers.h`,
+ * interleaved with the lines of `configure` that invoke them.
+ */
+void configure(unsigned long base)
+{
+    REG_WRITE(base, 1);
+    REG_SET_BITS(base + 4, 0x10);
+    REG_WRITE(base + 8, 2);
+    unsigned int done = 1;
+    REG_SET_BITS(base, done);
+}
+
+void _start(void)
+{
+    configure(0x40000000);
+    for (;;) {
+    }
+}
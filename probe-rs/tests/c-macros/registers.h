@@ -0,0 +1,15 @@
+/* Register access helpers, in the style of CMSIS, where the macros expand to forced inline functions. */
+
+#define REG_WRITE(address, value) reg_write((volatile unsigned int *)(unsigned long)(address), (value))
+#define REG_SET_BITS(address, bits) reg_set_bits((volatile unsigned int *)(unsigned long)(address), (bits))
+
+static inline __attribute__((always_inline)) void reg_write(volatile unsigned int *reg, unsigned int value)
+{
+    *reg = value;
+}
+
+static inline __attribute__((always_inline)) void reg_set_bits(volatile unsigned int *reg, unsigned int bits)
+{
+    unsigned int current = *reg;
+    *reg = current | bits;
+}
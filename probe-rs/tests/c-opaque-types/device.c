@@ -0,0 +1,16 @@
+// Source for the `c-opaque-types` test binary. See `../README.md` for build instructions.
+#include "device.h"
+
+struct Device {
+    uint32_t id;
+    uint16_t flags;
+};
+
+struct Device device = {
+    .id = 7,
+    .flags = 3,
+};
+
+struct Device *get_device(void) {
+    return &device;
+}
@@ -0,0 +1,10 @@
+// Source for the `c-opaque-types` test binary. See `../README.md` for build instructions.
+#include <stdint.h>
+
+// `struct Device` is only defined in `device.c`, so it is an incomplete type in `main.c`.
+struct Device;
+
+// `struct Missing` is never defined.
+struct Missing;
+
+struct Device *get_device(void);
@@ -0,0 +1,12 @@
+// Source for the `c-opaque-types` test binary. See `../README.md` for build instructions.
+#include "device.h"
+
+struct Device *device_handle;
+struct Missing *missing_handle;
+
+void _start(void) {
+    device_handle = get_device();
+    missing_handle = (struct Missing *)device_handle;
+    for (;;) {
+    }
+}
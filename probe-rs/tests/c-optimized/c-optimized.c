@@ -0,0 +1,68 @@
+// Test program for the debug information of optimized code.
+// The functions are kept out of line, so that each has its own location lists.
+
+volatile int sink;
+
+__attribute__((noinline)) void consume(int value) {
+    sink = value;
+}
+
+// `first` is only live until the first call to `consume`,
+// because it is kept in a caller saved register, and never used again.
+// It is read from a volatile, so that the compiler can not recompute it later.
+__attribute__((noinline)) int partially_live(int a) {
+    int first = sink;
+    consume(first);
+    consume(a);
+    return a + 1;
+}
+
+// Three levels of functions, which are always inlined into `nested_inlines`.
+static inline __attribute__((always_inline)) void innermost(int value) {
+    consume(value * 7);
+}
+
+static inline __attribute__((always_inline)) void middle(int value) {
+    innermost(value + 5);
+}
+
+static inline __attribute__((always_inline)) void outermost(int value) {
+    middle(value * 3);
+}
+
+__attribute__((noinline)) void nested_inlines(int value) {
+    outermost(value);
+}
+
+// Values that are returned in one, and in two registers on 32-bit targets.
+__attribute__((noinline)) unsigned int return_u32(void) {
+    return sink * 3u;
+}
+
+__attribute__((noinline)) unsigned long long return_u64(void) {
+    return (unsigned long long)sink << 33;
+}
+
+struct Pair {
+    int left;
+    int right;
+};
+
+// `pair.right` is only used by the first call to `consume`, while `pair.left` is also returned,
+// so only some of the members of `pair` have a location at the end of the function.
+__attribute__((noinline)) int partial_struct(int value) {
+    struct Pair pair = {value, sink};
+    consume(pair.right);
+    consume(pair.left);
+    return pair.left;
+}
+
+void _start(void) {
+    partially_live(sink);
+    nested_inlines(sink);
+    sink = return_u32();
+    sink = (int)return_u64();
+    sink = partial_struct(sink);
+    for (;;) {
+    }
+}
@@ -0,0 +1,45 @@
+// Test program for the values that are returned from functions.
+// The functions are kept out of line, so that each returns its value through the ABI.
+
+volatile int sink;
+
+__attribute__((noinline)) float return_float(void) {
+    return sink * 1.5f;
+}
+
+__attribute__((noinline)) double return_double(void) {
+    return sink * 2.25;
+}
+
+struct Small {
+    int left;
+    int right;
+};
+
+// Returned in a register.
+__attribute__((noinline)) struct Small return_small(void) {
+    struct Small small = {sink, sink * 2};
+    return small;
+}
+
+struct Large {
+    int values[8];
+};
+
+// Too large for the return registers, so it is returned in a buffer that is provided by the caller.
+__attribute__((noinline)) struct Large return_large(void) {
+    struct Large large;
+    for (int index = 0; index < 8; index++) {
+        large.values[index] = sink + index;
+    }
+    return large;
+}
+
+void _start(void) {
+    sink = (int)return_float();
+    sink = (int)return_double();
+    sink = return_small().right;
+    sink = return_large().values[7];
+    for (;;) {
+    }
+}
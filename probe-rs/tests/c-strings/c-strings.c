@@ -0,0 +1,14 @@
+// Source for the `c-strings` test binary. See `../README.md` for build instructions.
+
+const char *greeting = "Hello, world!";
+const char *no_string = 0;
+const char *dangling = (const char *)0x10;
+unsigned char *bytes = (unsigned char *)"\x01\x02";
+#define TEN "0123456789"
+const char *long_text = TEN TEN TEN TEN TEN TEN TEN TEN TEN TEN TEN TEN TEN TEN TEN TEN TEN TEN TEN TEN TEN TEN TEN TEN TEN TEN TEN TEN TEN TEN;
+int *numbers = 0;
+
+void _start(void) {
+    for (;;) {
+    }
+}
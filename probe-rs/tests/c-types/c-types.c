@@ -0,0 +1,92 @@
+// Source for the `c-types` test binaries. See `../README.md` for build instructions.
+#include <stdint.h>
+
+typedef struct {
+    uint16_t x;
+    uint16_t y;
+} Point;
+
+enum Mode {
+    MODE_OFF = 0,
+    MODE_ON = 1,
+    MODE_AUTO = 2,
+};
+
+struct Flags {
+    uint32_t enabled : 1;
+    uint32_t mode : 2;
+    uint32_t reserved : 5;
+    uint32_t count : 12;
+};
+
+struct Nested {
+    uint8_t id;
+    Point origin;
+    struct Flags flags;
+    union {
+        uint32_t raw;
+        float value;
+    };
+    struct {
+        uint8_t a;
+        uint8_t b;
+    } pair;
+    enum Mode mode;
+    const Point *next;
+};
+
+// Identical layouts and values, so that gcc encodes the member offsets, the byte sizes
+// and the enumerator values as `DW_FORM_implicit_const` when using DWARF 5.
+enum RxBaud {
+    RX_BAUD_DEFAULT = 115200,
+};
+
+enum TxBaud {
+    TX_BAUD_DEFAULT = 115200,
+};
+
+struct RxFrame {
+    uint8_t data[256];
+    uint32_t crc;
+    enum RxBaud baud;
+};
+
+struct TxFrame {
+    uint8_t data[256];
+    uint32_t crc;
+    enum TxBaud baud;
+};
+
+volatile struct RxFrame rx_frame;
+volatile struct TxFrame tx_frame;
+
+volatile struct Nested nested = {
+    .id = 1,
+    .origin = {.x = 2, .y = 3},
+    .flags = {.enabled = 1, .mode = 2, .reserved = 0, .count = 100},
+    .raw = 0x12345678,
+    .pair = {.a = 4, .b = 5},
+    .mode = MODE_AUTO,
+    .next = 0,
+};
+
+uint32_t count_calls(void) {
+    static uint32_t counter = 0;
+    counter++;
+    return counter;
+}
+
+uint32_t sum_values(const volatile uint8_t *values, uint32_t count) {
+    uint32_t sum = 0;
+    for (uint32_t index = 0; index < count; index++) {
+        sum += values[index];
+    }
+    return sum;
+}
+
+void _start(void) {
+    for (;;) {
+        nested.flags.count = count_calls();
+        rx_frame.crc = sum_values(rx_frame.data, sizeof(rx_frame.data));
+    }
+}
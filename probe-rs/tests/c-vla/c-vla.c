@@ -0,0 +1,20 @@
+// Source for the `c-vla` test binary. See `../README.md` for build instructions.
+#include <stdint.h>
+
+uint32_t sum_squares(uint32_t count) {
+    uint32_t squares[count];
+    uint32_t sum = 0;
+    for (uint32_t index = 0; index < count; index++) {
+        squares[index] = index * index;
+    }
+    for (uint32_t index = 0; index < count; index++) {
+        sum += squares[index];
+    }
+    return sum;
+}
+
+void _start(void) {
+    for (;;) {
+        sum_squares(4);
+    }
+}
@@ -0,0 +1,531 @@
+	.file	"c-vla.c"
+	.text
+.Ltext0:
+	.cfi_sections	.debug_frame
+	.file 0 "/tmp/cvla" "c-vla.c"
+	.globl	sum_squares
+	.type	sum_squares, @function
+sum_squares:
+.LFB0:
+	.file 1 "c-vla.c"
+	.loc 1 4 38
+	.cfi_startproc
+	pushq	%rbp
+	.cfi_def_cfa_offset 16
+	.cfi_offset 6, -16
+	movq	%rsp, %rbp
+	.cfi_def_cfa_register 6
+	pushq	%rbx
+	subq	$56, %rsp
+	.cfi_offset 3, -24
+	movl	%edi, -52(%rbp)
+	.loc 1 4 38
+	movq	%rsp, %rax
+	movq	%rax, %rsi
+	.loc 1 5 5
+	movl	-52(%rbp), %eax
+	.loc 1 5 14
+	movl	%eax, %edx
+	subq	$1, %rdx
+	movq	%rdx, -40(%rbp)
+	movl	%eax, %edx
+	movq	%rdx, %r8
+	movl	$0, %r9d
+	movl	%eax, %edx
+	movq	%rdx, %rcx
+	movl	$0, %ebx
+	movl	%eax, %eax
+	leaq	0(,%rax,4), %rdx
+	movl	$16, %eax
+	subq	$1, %rax
+	addq	%rdx, %rax
+	movl	$16, %ebx
+	movl	$0, %edx
+	divq	%rbx
+	imulq	$16, %rax, %rax
+	subq	%rax, %rsp
+	movq	%rsp, %rax
+	addq	$3, %rax
+	shrq	$2, %rax
+	salq	$2, %rax
+	movq	%rax, -48(%rbp)
+	.loc 1 6 14
+	movl	$0, -20(%rbp)
+.LBB2:
+	.loc 1 7 19
+	movl	$0, -24(%rbp)
+	.loc 1 7 5
+	jmp	.L2
+.L3:
+	.loc 1 8 32 discriminator 3
+	movl	-24(%rbp), %eax
+	imull	%eax, %eax
+	movl	%eax, %ecx
+	.loc 1 8 24 discriminator 3
+	movq	-48(%rbp), %rax
+	movl	-24(%rbp), %edx
+	movl	%ecx, (%rax,%rdx,4)
+	.loc 1 7 50 discriminator 3
+	addl	$1, -24(%rbp)
+.L2:
+	.loc 1 7 36 discriminator 1
+	movl	-24(%rbp), %eax
+	cmpl	-52(%rbp), %eax
+	jb	.L3
+.LBE2:
+.LBB3:
+	.loc 1 10 19
+	movl	$0, -28(%rbp)
+	.loc 1 10 5
+	jmp	.L4
+.L5:
+	.loc 1 11 23 discriminator 3
+	movq	-48(%rbp), %rax
+	movl	-28(%rbp), %edx
+	movl	(%rax,%rdx,4), %eax
+	.loc 1 11 13 discriminator 3
+	addl	%eax, -20(%rbp)
+	.loc 1 10 50 discriminator 3
+	addl	$1, -28(%rbp)
+.L4:
+	.loc 1 10 36 discriminator 1
+	movl	-28(%rbp), %eax
+	cmpl	-52(%rbp), %eax
+	jb	.L5
+.LBE3:
+	.loc 1 13 12
+	movl	-20(%rbp), %eax
+	movq	%rsi, %rsp
+	.loc 1 14 1
+	movq	-8(%rbp), %rbx
+	leave
+	.cfi_def_cfa 7, 8
+	ret
+	.cfi_endproc
+.LFE0:
+	.size	sum_squares, .-sum_squares
+	.globl	_start
+	.type	_start, @function
+_start:
+.LFB1:
+	.loc 1 16 19
+	.cfi_startproc
+	pushq	%rbp
+	.cfi_def_cfa_offset 16
+	.cfi_offset 6, -16
+	movq	%rsp, %rbp
+	.cfi_def_cfa_register 6
+.L8:
+	.loc 1 18 9 discriminator 1
+	movl	$4, %edi
+	call	sum_squares
+	jmp	.L8
+	.cfi_endproc
+.LFE1:
+	.size	_start, .-_start
+.Letext0:
+	.file 2 "/usr/include/x86_64-linux-gnu/bits/types.h"
+	.file 3 "/usr/include/x86_64-linux-gnu/bits/stdint-uintn.h"
+	.section	.debug_info,"",@progbits
+.Ldebug_info0:
+	.long	0x144
+	.value	0x5
+	.byte	0x1
+	.byte	0x8
+	.long	.Ldebug_abbrev0
+	.uleb128 0x4
+	.long	.LASF14
+	.byte	0x1d
+	.long	.LASF0
+	.long	.LASF1
+	.quad	.Ltext0
+	.quad	.Letext0-.Ltext0
+	.long	.Ldebug_line0
+	.uleb128 0x1
+	.byte	0x1
+	.byte	0x8
+	.long	.LASF2
+	.uleb128 0x1
+	.byte	0x2
+	.byte	0x7
+	.long	.LASF3
+	.uleb128 0x1
+	.byte	0x4
+	.byte	0x7
+	.long	.LASF4
+	.uleb128 0x1
+	.byte	0x8
+	.byte	0x7
+	.long	.LASF5
+	.uleb128 0x1
+	.byte	0x1
+	.byte	0x6
+	.long	.LASF6
+	.uleb128 0x1
+	.byte	0x2
+	.byte	0x5
+	.long	.LASF7
+	.uleb128 0x5
+	.byte	0x4
+	.byte	0x5
+	.string	"int"
+	.uleb128 0x3
+	.long	.LASF10
+	.byte	0x2
+	.byte	0x2a
+	.byte	0x16
+	.long	0x3c
+	.uleb128 0x1
+	.byte	0x8
+	.byte	0x5
+	.long	.LASF8
+	.uleb128 0x1
+	.byte	0x1
+	.byte	0x6
+	.long	.LASF9
+	.uleb128 0x3
+	.long	.LASF11
+	.byte	0x3
+	.byte	0x1a
+	.byte	0x14
+	.long	0x5f
+	.uleb128 0x6
+	.long	.LASF15
+	.byte	0x1
+	.byte	0x10
+	.byte	0x6
+	.quad	.LFB1
+	.quad	.LFE1-.LFB1
+	.uleb128 0x1
+	.byte	0x9c
+	.uleb128 0x7
+	.long	.LASF16
+	.byte	0x1
+	.byte	0x4
+	.byte	0xa
+	.long	0x79
+	.quad	.LFB0
+	.quad	.LFE0-.LFB0
+	.uleb128 0x1
+	.byte	0x9c
+	.long	0x134
+.Lcount_die:
+	.uleb128 0x8
+	.long	.LASF17
+	.byte	0x1
+	.byte	0x4
+	.byte	0x1f
+	.long	0x79
+	.uleb128 0x3
+	.byte	0x91
+	.sleb128 -68
+	.uleb128 0x2
+	.long	.LASF12
+	.byte	0x5
+	.byte	0xe
+	.long	0x134
+	.uleb128 0x3
+	.byte	0x91
+	.sleb128 -64
+	.byte	0x6
+	.uleb128 0x9
+	.string	"sum"
+	.byte	0x1
+	.byte	0x6
+	.byte	0xe
+	.long	0x79
+	.uleb128 0x2
+	.byte	0x91
+	.sleb128 -36
+	.uleb128 0xa
+	.quad	.LBB2
+	.quad	.LBE2-.LBB2
+	.long	0x113
+	.uleb128 0x2
+	.long	.LASF13
+	.byte	0x7
+	.byte	0x13
+	.long	0x79
+	.uleb128 0x2
+	.byte	0x91
+	.sleb128 -40
+	.byte	0
+	.uleb128 0xb
+	.quad	.LBB3
+	.quad	.LBE3-.LBB3
+	.uleb128 0x2
+	.long	.LASF13
+	.byte	0xa
+	.byte	0x13
+	.long	0x79
+	.uleb128 0x2
+	.byte	0x91
+	.sleb128 -44
+	.byte	0
+	.byte	0
+	.uleb128 0xc
+	.long	0x79
+	.uleb128 0xd
+	.long	0x43
+	.uleb128 0x7
+	.byte	0xfd
+	.long	.Lcount_die-.Ldebug_info0
+	.byte	0x31
+	.byte	0x1c
+	.byte	0
+	.byte	0
+	.section	.debug_abbrev,"",@progbits
+.Ldebug_abbrev0:
+	.uleb128 0x1
+	.uleb128 0x24
+	.byte	0
+	.uleb128 0xb
+	.uleb128 0xb
+	.uleb128 0x3e
+	.uleb128 0xb
+	.uleb128 0x3
+	.uleb128 0xe
+	.byte	0
+	.byte	0
+	.uleb128 0x2
+	.uleb128 0x34
+	.byte	0
+	.uleb128 0x3
+	.uleb128 0xe
+	.uleb128 0x3a
+	.uleb128 0x21
+	.sleb128 1
+	.uleb128 0x3b
+	.uleb128 0xb
+	.uleb128 0x39
+	.uleb128 0xb
+	.uleb128 0x49
+	.uleb128 0x13
+	.uleb128 0x2
+	.uleb128 0x18
+	.byte	0
+	.byte	0
+	.uleb128 0x3
+	.uleb128 0x16
+	.byte	0
+	.uleb128 0x3
+	.uleb128 0xe
+	.uleb128 0x3a
+	.uleb128 0xb
+	.uleb128 0x3b
+	.uleb128 0xb
+	.uleb128 0x39
+	.uleb128 0xb
+	.uleb128 0x49
+	.uleb128 0x13
+	.byte	0
+	.byte	0
+	.uleb128 0x4
+	.uleb128 0x11
+	.byte	0x1
+	.uleb128 0x25
+	.uleb128 0xe
+	.uleb128 0x13
+	.uleb128 0xb
+	.uleb128 0x3
+	.uleb128 0x1f
+	.uleb128 0x1b
+	.uleb128 0x1f
+	.uleb128 0x11
+	.uleb128 0x1
+	.uleb128 0x12
+	.uleb128 0x7
+	.uleb128 0x10
+	.uleb128 0x17
+	.byte	0
+	.byte	0
+	.uleb128 0x5
+	.uleb128 0x24
+	.byte	0
+	.uleb128 0xb
+	.uleb128 0xb
+	.uleb128 0x3e
+	.uleb128 0xb
+	.uleb128 0x3
+	.uleb128 0x8
+	.byte	0
+	.byte	0
+	.uleb128 0x6
+	.uleb128 0x2e
+	.byte	0
+	.uleb128 0x3f
+	.uleb128 0x19
+	.uleb128 0x3
+	.uleb128 0xe
+	.uleb128 0x3a
+	.uleb128 0xb
+	.uleb128 0x3b
+	.uleb128 0xb
+	.uleb128 0x39
+	.uleb128 0xb
+	.uleb128 0x27
+	.uleb128 0x19
+	.uleb128 0x11
+	.uleb128 0x1
+	.uleb128 0x12
+	.uleb128 0x7
+	.uleb128 0x40
+	.uleb128 0x18
+	.uleb128 0x7c
+	.uleb128 0x19
+	.byte	0
+	.byte	0
+	.uleb128 0x7
+	.uleb128 0x2e
+	.byte	0x1
+	.uleb128 0x3f
+	.uleb128 0x19
+	.uleb128 0x3
+	.uleb128 0xe
+	.uleb128 0x3a
+	.uleb128 0xb
+	.uleb128 0x3b
+	.uleb128 0xb
+	.uleb128 0x39
+	.uleb128 0xb
+	.uleb128 0x27
+	.uleb128 0x19
+	.uleb128 0x49
+	.uleb128 0x13
+	.uleb128 0x11
+	.uleb128 0x1
+	.uleb128 0x12
+	.uleb128 0x7
+	.uleb128 0x40
+	.uleb128 0x18
+	.uleb128 0x7a
+	.uleb128 0x19
+	.uleb128 0x1
+	.uleb128 0x13
+	.byte	0
+	.byte	0
+	.uleb128 0x8
+	.uleb128 0x5
+	.byte	0
+	.uleb128 0x3
+	.uleb128 0xe
+	.uleb128 0x3a
+	.uleb128 0xb
+	.uleb128 0x3b
+	.uleb128 0xb
+	.uleb128 0x39
+	.uleb128 0xb
+	.uleb128 0x49
+	.uleb128 0x13
+	.uleb128 0x2
+	.uleb128 0x18
+	.byte	0
+	.byte	0
+	.uleb128 0x9
+	.uleb128 0x34
+	.byte	0
+	.uleb128 0x3
+	.uleb128 0x8
+	.uleb128 0x3a
+	.uleb128 0xb
+	.uleb128 0x3b
+	.uleb128 0xb
+	.uleb128 0x39
+	.uleb128 0xb
+	.uleb128 0x49
+	.uleb128 0x13
+	.uleb128 0x2
+	.uleb128 0x18
+	.byte	0
+	.byte	0
+	.uleb128 0xa
+	.uleb128 0xb
+	.byte	0x1
+	.uleb128 0x11
+	.uleb128 0x1
+	.uleb128 0x12
+	.uleb128 0x7
+	.uleb128 0x1
+	.uleb128 0x13
+	.byte	0
+	.byte	0
+	.uleb128 0xb
+	.uleb128 0xb
+	.byte	0x1
+	.uleb128 0x11
+	.uleb128 0x1
+	.uleb128 0x12
+	.uleb128 0x7
+	.byte	0
+	.byte	0
+	.uleb128 0xc
+	.uleb128 0x1
+	.byte	0x1
+	.uleb128 0x49
+	.uleb128 0x13
+	.byte	0
+	.byte	0
+	.uleb128 0xd
+	.uleb128 0x21
+	.byte	0
+	.uleb128 0x49
+	.uleb128 0x13
+	.uleb128 0x2f
+	.uleb128 0x18
+	.byte	0
+	.byte	0
+	.byte	0
+	.section	.debug_aranges,"",@progbits
+	.long	0x2c
+	.value	0x2
+	.long	.Ldebug_info0
+	.byte	0x8
+	.byte	0
+	.value	0
+	.value	0
+	.quad	.Ltext0
+	.quad	.Letext0-.Ltext0
+	.quad	0
+	.quad	0
+	.section	.debug_line,"",@progbits
+.Ldebug_line0:
+	.section	.debug_str,"MS",@progbits,1
+.LASF4:
+	.string	"unsigned int"
+.LASF17:
+	.string	"count"
+.LASF10:
+	.string	"__uint32_t"
+.LASF12:
+	.string	"squares"
+.LASF5:
+	.string	"long unsigned int"
+.LASF2:
+	.string	"unsigned char"
+.LASF9:
+	.string	"char"
+.LASF11:
+	.string	"uint32_t"
+.LASF8:
+	.string	"long int"
+.LASF13:
+	.string	"index"
+.LASF3:
+	.string	"short unsigned int"
+.LASF14:
+	.string	"GNU C17 12.2.0 -mtune=generic -march=x86-64 -g -gdwarf-5 -O0 -fno-asynchronous-unwind-tables"
+.LASF15:
+	.string	"_start"
+.LASF7:
+	.string	"short int"
+.LASF6:
+	.string	"signed char"
+.LASF16:
+	.string	"sum_squares"
+	.section	.debug_line_str,"MS",@progbits,1
+.LASF1:
+	.string	"/tmp/cvla"
+.LASF0:
+	.string	"c-vla.c"
+	.ident	"GCC: (Debian 12.2.0-14+deb12u1) 12.2.0"
+	.section	.note.GNU-stack,"",@progbits
@@ -0,0 +1,25 @@
+// Test program for compiler generated (`DW_AT_artificial`) variables.
+// `Counter::sum` has the artificial `this` parameter, and the range based `for`
+// loop creates the artificial `__for_range`, `__for_begin` and `__for_end` variables.
+
+class Counter {
+  public:
+    int values[3];
+
+    int sum() {
+        int total = 0;
+        for (int value : values) {
+            total += value;
+        }
+        return total;
+    }
+};
+
+Counter counter = {{1, 2, 3}};
+volatile int result;
+
+extern "C" void _start(void) {
+    result = counter.sum();
+    for (;;) {
+    }
+}
@@ -0,0 +1,29 @@
+// Test program for the qualified names of C++ static members and namespaced globals.
+// The definitions refer to their declarations with `DW_AT_specification`.
+
+class Widget {
+  public:
+    static int instance_count;
+    int id;
+};
+
+int Widget::instance_count = 3;
+
+namespace app {
+struct Registry {
+    static unsigned int entries;
+};
+
+extern int level;
+} // namespace app
+
+unsigned int app::Registry::entries = 5;
+int app::level = 7;
+
+extern "C" void _start(void) {
+    Widget::instance_count++;
+    app::Registry::entries++;
+    app::level++;
+    for (;;) {
+    }
+}
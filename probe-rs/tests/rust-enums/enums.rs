@@ -0,0 +1,72 @@
+#![no_std]
+#![no_main]
+
+use core::hint::black_box;
+
+/// The layout of `Sample` is fixed, so that the niche of `valid` is at offset 4.
+#[repr(C)]
+pub struct Sample {
+    pub value: u32,
+    pub valid: bool,
+}
+
+/// The discriminant of `Reading` is stored in the niche of `Sample::valid`, after `Sample::value`.
+pub enum Reading {
+    Sample(Sample),
+    Missing,
+    Disconnected,
+}
+
+/// A signed discriminant, with explicit negative values.
+#[repr(i8)]
+pub enum Direction {
+    Backward(u16) = -1,
+    Stopped = 0,
+    Forward(u16) = 1,
+}
+
+#[inline(never)]
+fn enums() {
+    let sample = Reading::Sample(Sample { value: 42, valid: true });
+    let missing = Reading::Missing;
+    let disconnected = Reading::Disconnected;
+    let backward = Direction::Backward(3);
+    let forward = Direction::Forward(4);
+    black_box((&sample, &missing, &disconnected, &backward, &forward));
+}
+
+/// The error of a `Result`, with a payload in one of its variants.
+pub enum SensorError {
+    Timeout,
+    Checksum(u8),
+}
+
+#[inline(never)]
+fn results() {
+    let ok: Result<u32, SensorError> = Ok(7);
+    let err: Result<u32, SensorError> = Err(SensorError::Checksum(5));
+    black_box((&ok, &err));
+}
+
+#[no_mangle]
+pub extern "C" fn _start() -> ! {
+    enums();
+    results();
+    loop {}
+}
+
+#[panic_handler]
+fn panic(_info: &core::panic::PanicInfo) -> ! {
+    loop {}
+}
+
+#[no_mangle]
+extern "C" fn rust_eh_personality() {}
+
+#[no_mangle]
+unsafe extern "C" fn memcpy(dest: *mut u8, src: *const u8, count: usize) -> *mut u8 {
+    for index in 0..count {
+        *dest.add(index) = *src.add(index);
+    }
+    dest
+}
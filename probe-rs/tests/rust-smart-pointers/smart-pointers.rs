@@ -0,0 +1,93 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{borrow::Cow, boxed::Box, rc::Rc, string::String, sync::Arc};
+use core::alloc::{GlobalAlloc, Layout};
+use core::cell::UnsafeCell;
+use core::hint::black_box;
+
+struct BumpAllocator {
+    heap: UnsafeCell<[u8; 1024]>,
+    next: UnsafeCell<usize>,
+}
+
+unsafe impl Sync for BumpAllocator {}
+
+unsafe impl GlobalAlloc for BumpAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let next = &mut *self.next.get();
+        let start = (*next + layout.align() - 1) & !(layout.align() - 1);
+        *next = start + layout.size();
+        (self.heap.get() as *mut u8).add(start)
+    }
+
+    unsafe fn dealloc(&self, _ptr: *mut u8, _layout: Layout) {}
+}
+
+#[global_allocator]
+static ALLOCATOR: BumpAllocator = BumpAllocator {
+    heap: UnsafeCell::new([0; 1024]),
+    next: UnsafeCell::new(0),
+};
+
+#[inline(never)]
+fn smart_pointers() {
+    let shared = Rc::new(42_u32);
+    let shared_clone = Rc::clone(&shared);
+    let atomic = Arc::new(7_u16);
+    let borrowed: Cow<str> = Cow::Borrowed("borrowed");
+    let owned: Cow<str> = Cow::Owned(String::from("owned"));
+    black_box((&shared, &shared_clone, &atomic, &borrowed, &owned));
+}
+
+#[allow(dead_code)]
+struct Node {
+    value: u32,
+    next: Option<Box<Node>>,
+}
+
+#[inline(never)]
+fn linked_list() {
+    let tail = Node {
+        value: 2,
+        next: None,
+    };
+    let list = Some(Box::new(Node {
+        value: 1,
+        next: Some(Box::new(tail)),
+    }));
+    black_box(&list);
+}
+
+#[no_mangle]
+pub extern "C" fn _start() -> ! {
+    smart_pointers();
+    linked_list();
+    loop {}
+}
+
+#[panic_handler]
+fn panic(_info: &core::panic::PanicInfo) -> ! {
+    loop {}
+}
+
+#[no_mangle]
+extern "C" fn rust_eh_personality() {}
+
+#[no_mangle]
+unsafe extern "C" fn memcpy(dest: *mut u8, src: *const u8, count: usize) -> *mut u8 {
+    for index in 0..count {
+        *dest.add(index) = *src.add(index);
+    }
+    dest
+}
+
+#[no_mangle]
+unsafe extern "C" fn memset(dest: *mut u8, value: i32, count: usize) -> *mut u8 {
+    for index in 0..count {
+        *dest.add(index) = value as u8;
+    }
+    dest
+}
@@ -1,4 +1,4 @@
-use probe_rs::debug::{debug_info::DebugInfo, ColumnType, SourceLocation};
+use probe_rs::debug::{debug_info::DebugInfo, ColumnType, SourceLocation, SourcePathTranslation};
 use std::path::PathBuf;
 use typed_path::UnixPathBuf;
 
@@ -32,7 +32,7 @@ fn breakpoint_location_absolute() {
 
         assert_eq!(
             *addr,
-            di.get_breakpoint_location(&path, *line, col)
+            di.get_breakpoint_location(&path, *line, col, &SourcePathTranslation::default())
                 .expect("Failed to find breakpoint location.")
                 .address,
             "Addresses do not match for data path={:?}, line={:?}, col={:?}",
@@ -67,7 +67,7 @@ fn breakpoint_location_inexact() {
 
         assert_eq!(
             *addr,
-            di.get_breakpoint_location(&path, *line, col)
+            di.get_breakpoint_location(&path, *line, col, &SourcePathTranslation::default())
                 .expect("Failed to find valid breakpoint locations.")
                 .address,
             "Addresses do not match for data path={:?}, line={:?}, col={:?}",
@@ -111,6 +111,55 @@ fn find_non_existing_unit_by_path() {
     let debug_info = DebugInfo::from_file("tests/probe-rs-debugger-test").unwrap();
 
     assert!(debug_info
-        .get_breakpoint_location(&unit_path, 14, None)
+        .get_breakpoint_location(&unit_path, 14, None, &SourcePathTranslation::default())
         .is_err());
 }
+
+#[test]
+fn resolve_breakpoint_unique_line() {
+    let debug_info = DebugInfo::from_file("tests/probe-rs-debugger-test").unwrap();
+
+    let path = UnixPathBuf::from("/Users/jacknoppe/dev/probe-rs-debugger-test/src/main.rs")
+        .to_typed_path_buf();
+
+    let resolution = debug_info
+        .resolve_breakpoint(&path, 240, None)
+        .expect("Failed to resolve breakpoint.");
+
+    assert_eq!(resolution.address, 0x80006EA);
+    assert_eq!(resolution.source_location.line, Some(240));
+    assert_eq!(
+        resolution.source_location.column,
+        Some(ColumnType::Column(28))
+    );
+    assert!(resolution.alternatives.is_empty());
+}
+
+#[test]
+fn resolve_breakpoint_ambiguous_line() {
+    let debug_info = DebugInfo::from_file("tests/probe-rs-debugger-test").unwrap();
+
+    let path = UnixPathBuf::from("/Users/jacknoppe/dev/probe-rs-debugger-test/src/main.rs")
+        .to_typed_path_buf();
+
+    // Line 198 is inlined in two places.
+    let resolution = debug_info
+        .resolve_breakpoint(&path, 198, None)
+        .expect("Failed to resolve breakpoint.");
+
+    assert_eq!(resolution.address, 0x8000820);
+    assert_eq!(resolution.source_location.line, Some(198));
+    assert_eq!(resolution.alternatives, vec![0x8000A70]);
+
+    // The requested column is preferred, if it matches one of the statements on the line.
+    let resolution = debug_info
+        .resolve_breakpoint(&path, 212, Some(24))
+        .expect("Failed to resolve breakpoint.");
+    assert_eq!(
+        resolution.source_location.column,
+        Some(ColumnType::Column(24))
+    );
+
+    // Line 212 is not inlined, so the other statements on the line are in the same instance, and not alternatives.
+    assert!(resolution.alternatives.is_empty());
+}